@@ -1,12 +1,27 @@
+use std::fmt;
+
 use rand::Rng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::{rng::MyRng, search_space::SearchSpace};
+use crate::{fitness::FitnessFunction, rng::MyRng, search_space::SearchSpace};
 
+pub mod ant_system;
+pub mod eda;
+pub mod genetic_algorithm;
 pub mod mmas;
+pub mod mu_plus_lambda_ea;
+pub mod one_comma_lambda_ea;
+pub mod one_plus_lambda_lambda_ga;
 pub mod one_plus_one_ea;
 pub mod simulated_annealing;
 
-#[derive(Debug)]
+// Number of iterations after which algorithms accumulating the current fitness by delta evaluation
+// re-evaluate the current solution, as rounding errors of non-integer distances add up over the deltas
+pub(crate) const REEVALUATION_INTERVAL: u64 = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct SimulationState<S: SearchSpace> {
     pub iteration: u64,
     pub current_solution: S,
@@ -18,7 +33,35 @@ pub trait AlgorithmCore {
     fn iterate<R: MyRng>(&mut self, rng: &mut R);
     fn current_fitness(&self) -> f64;
     fn iterations(&self) -> u64;
+    // Number of fitness evaluations, as the number of evaluations per iteration differs between algorithms,
+    // making it the measure used to compare algorithms fairly
+    fn evaluations(&self) -> u64;
+    // Compare two fitness values in the direction of the fitness function, where Greater means a is better
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering;
     fn status_json(&self) -> serde_json::Value;
+    // Save the full state of the algorithm, such that restoring it on an algorithm created
+    // with the same parameters continues identically given the same RNG state
+    fn checkpoint(&self) -> serde_json::Value;
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError>;
+}
+
+// Status of an algorithm extended by the problem-specific properties of its current solution,
+// as given by the fitness function
+pub(crate) fn solution_status<S, F>(mut status: Value, fitness_function: &F, solution: &S) -> Value
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+{
+    if let Value::Object(fields) = &mut status {
+        fields.extend(fitness_function.solution_properties(solution));
+    }
+    status
+}
+
+// Iteration of the algorithms whose iteration differs between bitstrings and permutations,
+// allowing AlgorithmCore to be implemented once for all search spaces with only the step specialised
+pub trait Step {
+    fn step<R: MyRng>(&mut self, rng: &mut R);
 }
 
 // Trait used by the server implementation,
@@ -28,7 +71,11 @@ pub trait Algorithm<R: Rng>: Send {
     fn iterate(&mut self, rng: &mut R);
     fn current_fitness(&self) -> f64;
     fn iterations(&self) -> u64;
+    fn evaluations(&self) -> u64;
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering;
     fn status_json(&self) -> serde_json::Value;
+    fn checkpoint(&self) -> serde_json::Value;
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError>;
 }
 
 // Algorithm is implemented for types implementing AlgorithmCore, using a fixed RNG
@@ -45,7 +92,167 @@ impl<T: AlgorithmCore + Send, R: Rng> Algorithm<R> for T {
         self.iterations()
     }
 
+    fn evaluations(&self) -> u64 {
+        self.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.compare(a, b)
+    }
+
     fn status_json(&self) -> serde_json::Value {
         self.status_json()
     }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        self.checkpoint()
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        self.restore(checkpoint)
+    }
+}
+
+// Error returned when restoring a checkpoint that does not match the algorithm,
+// e.g. a checkpoint of another algorithm or of a problem of a different size
+#[derive(Debug, PartialEq)]
+pub enum CheckpointError {
+    MissingField(&'static str),
+    InvalidField(&'static str, String),
+    SizeMismatch {
+        field: &'static str,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::MissingField(field) => write!(f, "missing field {}", field),
+            CheckpointError::InvalidField(field, reason) => {
+                write!(f, "invalid field {}: {}", field, reason)
+            }
+            CheckpointError::SizeMismatch {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {} of size {}, found size {}",
+                field, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+// Read a field of a checkpoint
+pub(crate) fn checkpoint_field<T: DeserializeOwned>(
+    checkpoint: &Value,
+    field: &'static str,
+) -> Result<T, CheckpointError> {
+    let value = checkpoint
+        .get(field)
+        .ok_or(CheckpointError::MissingField(field))?;
+    T::deserialize(value).map_err(|e| CheckpointError::InvalidField(field, e.to_string()))
+}
+
+// Read a field of a checkpoint that may be missing, using the default value in that case,
+// such as the number of evaluations of checkpoints created before evaluations were counted
+pub(crate) fn optional_checkpoint_field<T: DeserializeOwned + Default>(
+    checkpoint: &Value,
+    field: &'static str,
+) -> Result<T, CheckpointError> {
+    match checkpoint.get(field) {
+        Some(_) => checkpoint_field(checkpoint, field),
+        None => Ok(T::default()),
+    }
+}
+
+// Check that a restored field has the size of the field it replaces
+pub(crate) fn check_size(
+    field: &'static str,
+    expected: usize,
+    found: usize,
+) -> Result<(), CheckpointError> {
+    if expected != found {
+        return Err(CheckpointError::SizeMismatch {
+            field,
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
+
+// Check that a restored matrix has the dimensions of the matrix it replaces
+pub(crate) fn check_matrix_size(
+    field: &'static str,
+    expected: &[Vec<f64>],
+    found: &[Vec<f64>],
+) -> Result<(), CheckpointError> {
+    check_size(field, expected.len(), found.len())?;
+    for (expected_row, found_row) in expected.iter().zip(found) {
+        check_size(field, expected_row.len(), found_row.len())?;
+    }
+    Ok(())
+}
+
+// Read the simulation state of a checkpoint, checking that the solution has the size of the current solution
+pub(crate) fn checkpoint_state<S: SearchSpace>(
+    state: &SimulationState<S>,
+    checkpoint: &Value,
+) -> Result<SimulationState<S>, CheckpointError> {
+    let restored: SimulationState<S> = checkpoint_field(checkpoint, "state")?;
+    check_size(
+        "solution",
+        state.current_solution.size(),
+        restored.current_solution.size(),
+    )?;
+    Ok(restored)
+}
+
+// Check that an algorithm continues identically after restoring a checkpoint.
+// The algorithm is run for a few iterations and checkpointed through the JSON text of the checkpoint,
+// which is restored on an algorithm created with the same parameters but a different RNG.
+// Both algorithms must then report the same status every iteration when given the same RNG state
+#[cfg(test)]
+pub(crate) fn assert_checkpoint_roundtrip<A, C>(create: C)
+where
+    A: AlgorithmCore,
+    C: Fn(&mut rand_pcg::Pcg64) -> A,
+{
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64;
+
+    let mut rng = Pcg64::seed_from_u64(1);
+    let mut original = create(&mut rng);
+    for _ in 0..5 {
+        original.iterate(&mut rng);
+    }
+
+    let text = original.checkpoint().to_string();
+    let mut restored = create(&mut Pcg64::seed_from_u64(2));
+    restored
+        .restore(&serde_json::from_str(&text).unwrap())
+        .unwrap();
+    assert_eq!(restored.status_json(), original.status_json());
+
+    let mut restored_rng = rng.clone();
+    for _ in 0..5 {
+        original.iterate(&mut rng);
+        restored.iterate(&mut restored_rng);
+        assert_eq!(restored.status_json(), original.status_json());
+    }
+}
+
+// TSP instance with irrational distances, whose deltas are not exactly representable
+#[cfg(test)]
+pub(crate) fn irrational_tsp(size: usize) -> crate::fitness::tsp::TSP {
+    let distances = (0..size)
+        .map(|i| (0..size).map(|j| ((i * j + i + j) as f64).sqrt()).collect())
+        .collect();
+    crate::fitness::tsp::TSP::new(distances)
 }
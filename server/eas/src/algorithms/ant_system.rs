@@ -0,0 +1,436 @@
+use super::{
+    check_matrix_size, checkpoint_field, checkpoint_state,
+    mmas::{heuristic_matrix, nearest_neighbor},
+    optional_checkpoint_field, solution_status, AlgorithmCore, CheckpointError, SimulationState,
+};
+use crate::{
+    fitness::{EvaluationCounter, FitnessFunction},
+    rng::MyRng,
+    search_space::{Permutation, SearchSpace},
+};
+use serde_json::json;
+
+// Enum representing the Ant System variants for TSP other than MMAS
+// Elitist: all ants deposit 1/L_k, and the best-so-far tour additionally deposits e/L_bs
+// RankBased: the (w-1) best ants of the iteration deposit (w-r)/L_r, and the best-so-far tour deposits w/L_bs
+// AntColonySystem: ants choose the best edge with probability q0 and apply the local update
+// t_ij = (1-xi) * t_ij + xi * t_0 after each step. Only the best-so-far tour is evaporated and reinforced.
+// The initial pheromone is proportional to e + m and w(w-1), requiring e >= 0 and w >= 2
+#[derive(Debug, Clone, Copy)]
+pub enum AntSystemVariant {
+    Elitist { elitist_weight: f64 },
+    RankBased { ranks: usize },
+    AntColonySystem { q0: f64, xi: f64 },
+}
+
+pub struct AntSystem<F: FitnessFunction<Permutation>> {
+    pub state: SimulationState<Permutation>,
+    fitness_function: EvaluationCounter<F>,
+    pheromone: Vec<Vec<f64>>,
+    heuristic: Vec<Vec<f64>>,
+    // Cached pheromone^alpha * heuristic^beta used for the choice of the next vertex
+    choice_info: Vec<Vec<f64>>,
+    size: usize,
+    ants: usize,
+    alpha: f64,
+    beta: f64,
+    evap_factor: f64,
+    variant: AntSystemVariant,
+    t_0: f64,
+}
+
+impl<F> AntSystem<F>
+where
+    F: FitnessFunction<Permutation>,
+{
+    // The initial pheromone t_0 is based on the length C_nn of the nearest neighbor tour,
+    // following the recommended settings of Dorigo & Stützle, "Ant Colony Optimization"
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<R: MyRng>(
+        graph: Vec<Vec<f64>>,
+        fitness_function: F,
+        size: usize,
+        ants: usize,
+        alpha: f64,
+        beta: f64,
+        evap_factor: f64,
+        variant: AntSystemVariant,
+        near_neigh: bool,
+        rng: &mut R,
+    ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        match variant {
+            AntSystemVariant::RankBased { ranks } => {
+                assert!(ranks >= 2, "number of ranks must be at least 2")
+            }
+            AntSystemVariant::Elitist { elitist_weight } => assert!(
+                elitist_weight.is_finite() && elitist_weight >= 0.0,
+                "elitist weight must be a non-negative number"
+            ),
+            AntSystemVariant::AntColonySystem { .. } => {}
+        }
+        let nn_tour = nearest_neighbor(&graph, size);
+        let nn_length = fitness_function.evaluate(&nn_tour);
+
+        let current_solution = if near_neigh {
+            nn_tour
+        } else {
+            Permutation::new_random(size, rng)
+        };
+        let current_fitness = fitness_function.evaluate(&current_solution);
+
+        let t_0 = match variant {
+            AntSystemVariant::Elitist { elitist_weight } => {
+                (elitist_weight + ants as f64) / (evap_factor * nn_length)
+            }
+            AntSystemVariant::RankBased { ranks } => {
+                0.5 * (ranks * (ranks - 1)) as f64 / (evap_factor * nn_length)
+            }
+            AntSystemVariant::AntColonySystem { .. } => 1.0 / (size as f64 * nn_length),
+        };
+
+        let mut ant_system = AntSystem {
+            state: SimulationState {
+                iteration: 0,
+                current_solution,
+                current_fitness,
+            },
+            fitness_function,
+            pheromone: vec![vec![t_0; size]; size],
+            heuristic: heuristic_matrix(&graph, size),
+            choice_info: vec![vec![0.0; size]; size],
+            size,
+            ants,
+            alpha,
+            beta,
+            evap_factor,
+            variant,
+            t_0,
+        };
+        ant_system.compute_choice_info();
+        ant_system
+    }
+
+    fn compute_choice_info(&mut self) {
+        for i in 0..self.size {
+            for j in 0..self.size {
+                self.choice_info[i][j] = self.choice(i, j);
+            }
+        }
+    }
+
+    fn choice(&self, i: usize, j: usize) -> f64 {
+        self.pheromone[i][j].powf(self.alpha) * self.heuristic[i][j].powf(self.beta)
+    }
+
+    // Choose the next vertex from the current vertex. For Ant Colony System the best edge is chosen
+    // with probability q0 (pseudo-random proportional rule), otherwise the next vertex is chosen
+    // proportionally to the choice info of the unvisited vertices
+    fn next_vertex<R: MyRng>(&self, current: usize, visited: &[bool], rng: &mut R) -> usize {
+        let unvisited = (0..self.size).filter(|&j| !visited[j]);
+        let weights = &self.choice_info[current];
+
+        if let AntSystemVariant::AntColonySystem { q0, .. } = self.variant {
+            if rng.random_range_float(0.0..1.0) < q0 {
+                return unvisited
+                    .max_by(|&a, &b| weights[a].total_cmp(&weights[b]))
+                    .unwrap();
+            }
+        }
+
+        let total = unvisited.clone().map(|j| weights[j]).sum::<f64>();
+        let mut choice = rng.random_range_float(0.0..1.0) * total;
+        let mut last = current;
+        for j in unvisited {
+            if choice < weights[j] {
+                return j;
+            }
+            choice -= weights[j];
+            last = j;
+        }
+        last
+    }
+
+    fn construct<R: MyRng>(&mut self, rng: &mut R) -> Permutation {
+        let mut path = Vec::<usize>::with_capacity(self.size);
+        let mut visited = vec![false; self.size];
+
+        let mut current = rng.random_range(0..self.size);
+        visited[current] = true;
+        path.push(current);
+
+        for _ in 0..self.size - 1 {
+            let next = self.next_vertex(current, &visited, rng);
+            if let AntSystemVariant::AntColonySystem { xi, .. } = self.variant {
+                self.local_update(current, next, xi);
+            }
+            current = next;
+            visited[current] = true;
+            path.push(current);
+        }
+        if let AntSystemVariant::AntColonySystem { xi, .. } = self.variant {
+            self.local_update(current, path[0], xi);
+        }
+        Permutation::new(path)
+    }
+
+    fn local_update(&mut self, i: usize, j: usize, xi: f64) {
+        let value = (1.0 - xi) * self.pheromone[i][j] + xi * self.t_0;
+        self.pheromone[i][j] = value;
+        self.pheromone[j][i] = value;
+        self.choice_info[i][j] = self.choice(i, j);
+        self.choice_info[j][i] = self.choice_info[i][j];
+    }
+
+    fn evaporate(&mut self) {
+        for row in self.pheromone.iter_mut() {
+            for t in row.iter_mut() {
+                *t *= 1.0 - self.evap_factor;
+            }
+        }
+    }
+
+    // Add the given amount of pheromone (scaled by the evaporation factor when evaporate is set)
+    // to every edge of the tour
+    fn deposit(&mut self, p: &Permutation, amount: f64, evaporate: bool) {
+        let perm = p.permutation();
+        for k in 0..perm.len() {
+            let (i, j) = (perm[k], perm[(k + 1) % perm.len()]);
+            let value = if evaporate {
+                (1.0 - self.evap_factor) * self.pheromone[i][j] + self.evap_factor * amount
+            } else {
+                self.pheromone[i][j] + amount
+            };
+            self.pheromone[i][j] = value;
+            self.pheromone[j][i] = value;
+        }
+    }
+
+    fn update(&mut self, mut paths: Vec<(Permutation, f64)>) {
+        let fitness_function = &self.fitness_function;
+        paths.sort_by(|a, b| fitness_function.compare(b.1, a.1));
+        if self
+            .fitness_function
+            .compare(paths[0].1, self.state.current_fitness)
+            == std::cmp::Ordering::Greater
+        {
+            (self.state.current_solution, self.state.current_fitness) = paths[0].clone();
+        }
+        let best_so_far = self.state.current_solution.clone();
+        let best_so_far_length = self.state.current_fitness;
+
+        match self.variant {
+            AntSystemVariant::Elitist { elitist_weight } => {
+                self.evaporate();
+                for (path, length) in &paths {
+                    self.deposit(path, 1.0 / length, false);
+                }
+                self.deposit(&best_so_far, elitist_weight / best_so_far_length, false);
+            }
+            AntSystemVariant::RankBased { ranks } => {
+                self.evaporate();
+                for (r, (path, length)) in paths.iter().take(ranks - 1).enumerate() {
+                    // Ranks start at 1
+                    let weight = (ranks - (r + 1)) as f64;
+                    self.deposit(path, weight / length, false);
+                }
+                self.deposit(&best_so_far, ranks as f64 / best_so_far_length, false);
+            }
+            AntSystemVariant::AntColonySystem { .. } => {
+                self.deposit(&best_so_far, 1.0 / best_so_far_length, true);
+            }
+        }
+        self.compute_choice_info();
+    }
+}
+
+impl<F> AlgorithmCore for AntSystem<F>
+where
+    F: FitnessFunction<Permutation>,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        let mut paths = Vec::with_capacity(self.ants);
+
+        for _ in 0..self.ants {
+            let path = self.construct(rng);
+            let length = self.fitness_function.evaluate(&path);
+            paths.push((path, length));
+        }
+        self.update(paths);
+
+        self.state.iteration += 1;
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    // The variants have no pheromone bounds, so the range of the current pheromone values
+    // is reported in place of t_min and t_max
+    fn status_json(&self) -> serde_json::Value {
+        let (mut t_min, mut t_max) = (f64::INFINITY, 0.0_f64);
+        for i in 0..self.size {
+            for j in (i + 1)..self.size {
+                t_min = t_min.min(self.pheromone[i][j]);
+                t_max = t_max.max(self.pheromone[i][j]);
+            }
+        }
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string(),
+                "pheromones": self.pheromone,
+                "t_max": t_max,
+                "t_min": t_min,
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        json!({
+            "state": self.state,
+            "pheromones": self.pheromone,
+            "evaluations": self.fitness_function.evaluations(),
+        })
+    }
+
+    // The choice info is derived from the pheromones, and is recomputed after restoring them
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        let pheromone: Vec<Vec<f64>> = checkpoint_field(checkpoint, "pheromones")?;
+        check_matrix_size("pheromones", &self.pheromone, &pheromone)?;
+        self.state = state;
+        self.pheromone = pheromone;
+        self.compute_choice_info();
+        self.fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::assert_checkpoint_roundtrip;
+    use crate::{fitness::tsp::TSP, rng::MockRng};
+
+    // Four cities on a cycle 0-1-2-3 of length 3, where cities 0 and 1 are at the same location
+    fn graph() -> Vec<Vec<f64>> {
+        vec![
+            vec![0.0, 0.0, 2.0, 1.0],
+            vec![0.0, 0.0, 1.0, 2.0],
+            vec![2.0, 1.0, 0.0, 1.0],
+            vec![1.0, 2.0, 1.0, 0.0],
+        ]
+    }
+
+    fn create(variant: AntSystemVariant) -> AntSystem<TSP> {
+        AntSystem::new(
+            graph(),
+            TSP::new(graph()),
+            4,
+            1,
+            1.0,
+            1.0,
+            0.5,
+            variant,
+            true,
+            &mut MockRng::default(),
+        )
+    }
+
+    #[test]
+    fn test_zero_distance_heuristic() {
+        let ant_system = create(AntSystemVariant::Elitist {
+            elitist_weight: 1.0,
+        });
+        // The zero distance is replaced by half of the smallest positive distance
+        assert_eq!(ant_system.heuristic[0][1], 2.0);
+        assert!(ant_system
+            .choice_info
+            .iter()
+            .flatten()
+            .all(|c| c.is_finite()));
+        assert_eq!(ant_system.current_fitness(), 3.0);
+    }
+
+    #[test]
+    fn test_elitist_update() {
+        let mut ant_system = create(AntSystemVariant::Elitist {
+            elitist_weight: 1.0,
+        });
+        // t_0 = (e + m) / (rho * C_nn)
+        assert!((ant_system.pheromone[0][1] - 4.0 / 3.0).abs() < 1e-12);
+
+        // The ant starts at city 0 and always takes the first unvisited city, building the tour 0-1-2-3
+        let mut rng = MockRng::new_float(vec![0.0; 3]).with_range(vec![0]);
+        ant_system.iterate(&mut rng);
+
+        // Edges of the tour are evaporated and reinforced by the ant and the equally long best-so-far tour
+        assert!((ant_system.pheromone[0][1] - 4.0 / 3.0).abs() < 1e-12);
+        assert!((ant_system.pheromone[3][0] - 4.0 / 3.0).abs() < 1e-12);
+        assert!((ant_system.pheromone[0][2] - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(ant_system.evaluations(), 3);
+    }
+
+    #[test]
+    fn test_ant_colony_system_update() {
+        let mut ant_system = create(AntSystemVariant::AntColonySystem { q0: 0.9, xi: 0.1 });
+        let t_0 = 1.0 / 12.0;
+
+        // Starting at city 2, the ant greedily builds the tour 2-3-0-1
+        let mut rng = MockRng::new_float(vec![0.0; 3]).with_range(vec![2]);
+        ant_system.iterate(&mut rng);
+
+        // Only the best-so-far tour is reinforced, as the local update keeps t_0 on the other edges
+        // The global update evaporates with rho = 0.5 towards 1 / L_bs
+        let reinforced = 0.5 * t_0 + 0.5 / 3.0;
+        assert!((ant_system.pheromone[0][1] - reinforced).abs() < 1e-12);
+        assert!((ant_system.pheromone[1][2] - reinforced).abs() < 1e-12);
+        assert!((ant_system.pheromone[0][2] - t_0).abs() < 1e-12);
+        assert_eq!(ant_system.current_fitness(), 3.0);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        let variants = [
+            AntSystemVariant::Elitist {
+                elitist_weight: 4.0,
+            },
+            AntSystemVariant::RankBased { ranks: 3 },
+            AntSystemVariant::AntColonySystem { q0: 0.9, xi: 0.1 },
+        ];
+        for variant in variants {
+            assert_checkpoint_roundtrip(|rng| {
+                AntSystem::new(
+                    graph(),
+                    TSP::new(graph()),
+                    4,
+                    3,
+                    1.0,
+                    2.0,
+                    0.5,
+                    variant,
+                    false,
+                    rng,
+                )
+            });
+        }
+    }
+}
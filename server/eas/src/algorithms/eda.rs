@@ -0,0 +1,550 @@
+use super::{
+    check_size, checkpoint_field, checkpoint_state, optional_checkpoint_field, solution_status,
+    AlgorithmCore, CheckpointError, SimulationState,
+};
+use crate::{
+    fitness::{EvaluationCounter, FitnessFunction},
+    rng::MyRng,
+    search_space::{Bitstring, SearchSpace},
+};
+use serde_json::json;
+
+// Estimation-of-distribution algorithms on bitstrings.
+// All algorithms keep a vector of marginal probabilities p_i of sampling a one at position i,
+// which are kept within the borders [1/n, 1-1/n] like the pheromones of MMASbs
+
+// Borders of the marginal probabilities, using [1/2, 1/2] for bitstrings with less than two bits
+// where 1/n would exceed 1-1/n
+fn borders(size: usize) -> (f64, f64) {
+    let p_min = (1.0 / size as f64).min(0.5);
+    (p_min, 1.0 - p_min)
+}
+
+// Sample a bitstring where bit i is one with probability p_i
+fn sample<R: MyRng>(probabilities: &[f64], rng: &mut R) -> Bitstring {
+    let bits = probabilities
+        .iter()
+        .map(|&p| rng.random_range_float(0.0..1.0) < p)
+        .collect::<Vec<_>>();
+    Bitstring::new(bits)
+}
+
+// Sample lambda bitstrings and return the mu best, ordered from best to worst
+fn sample_best<F, R>(
+    probabilities: &[f64],
+    mu: usize,
+    lambda: usize,
+    fitness_function: &F,
+    rng: &mut R,
+) -> Vec<(Bitstring, f64)>
+where
+    F: FitnessFunction<Bitstring>,
+    R: MyRng,
+{
+    let mut samples = (0..lambda)
+        .map(|_| {
+            let x = sample(probabilities, rng);
+            let fitness = fitness_function.evaluate(&x);
+            (x, fitness)
+        })
+        .collect::<Vec<_>>();
+    samples.sort_by(|a, b| fitness_function.compare(b.1, a.1));
+    samples.truncate(mu);
+    samples
+}
+
+// Frequency of ones at each position among the given bitstrings
+fn frequencies(selected: &[(Bitstring, f64)], size: usize) -> Vec<f64> {
+    let mut ones = vec![0; size];
+    for (x, _) in selected {
+        for (count, b) in ones.iter_mut().zip(x.iter()) {
+            if b {
+                *count += 1;
+            }
+        }
+    }
+    ones.iter()
+        .map(|&c| c as f64 / selected.len() as f64)
+        .collect()
+}
+
+// Update the best solution found so far if the given candidate is better
+fn update_best<F: FitnessFunction<Bitstring>>(
+    state: &mut SimulationState<Bitstring>,
+    fitness_function: &F,
+    candidate: &Bitstring,
+    fitness: f64,
+) {
+    if fitness_function.compare(fitness, state.current_fitness) == std::cmp::Ordering::Greater {
+        state.current_fitness = fitness;
+        state.current_solution = candidate.clone();
+    }
+}
+
+fn initial_state<F, R>(size: usize, fitness_function: &F, rng: &mut R) -> SimulationState<Bitstring>
+where
+    F: FitnessFunction<Bitstring>,
+    R: MyRng,
+{
+    let current_solution = Bitstring::new_random(size, rng);
+    let current_fitness = fitness_function.evaluate(&current_solution);
+    SimulationState {
+        iteration: 0,
+        current_solution,
+        current_fitness,
+    }
+}
+
+// Marginal probabilities of an EDA together with their borders,
+// which make up the state of an EDA besides its best solution
+struct Marginals {
+    probabilities: Vec<f64>,
+    p_min: f64,
+    p_max: f64,
+}
+
+impl Marginals {
+    // Uniform marginals of 1/2 for bitstrings of the given size
+    fn new(size: usize) -> Self {
+        let (p_min, p_max) = borders(size);
+        Marginals {
+            probabilities: vec![0.5; size],
+            p_min,
+            p_max,
+        }
+    }
+
+    // Set each marginal p_i to update(i, p_i), kept within the borders
+    fn update(&mut self, update: impl Fn(usize, f64) -> f64) {
+        for (i, p) in self.probabilities.iter_mut().enumerate() {
+            *p = update(i, *p).clamp(self.p_min, self.p_max);
+        }
+    }
+
+    fn status_json<F: FitnessFunction<Bitstring>>(
+        &self,
+        state: &SimulationState<Bitstring>,
+        fitness_function: &EvaluationCounter<F>,
+    ) -> serde_json::Value {
+        solution_status(
+            json!({
+                "iterations": state.iteration,
+                "evaluations": fitness_function.evaluations(),
+                "current_fitness": state.current_fitness,
+                "current_solution": state.current_solution.to_string(),
+                "probabilities": self.probabilities,
+                "p_max": self.p_max,
+                "p_min": self.p_min,
+            }),
+            fitness_function,
+            &state.current_solution,
+        )
+    }
+
+    fn checkpoint<F>(
+        &self,
+        state: &SimulationState<Bitstring>,
+        fitness_function: &EvaluationCounter<F>,
+    ) -> serde_json::Value {
+        json!({
+            "state": state,
+            "probabilities": self.probabilities,
+            "evaluations": fitness_function.evaluations(),
+        })
+    }
+
+    // Restore the state, marginals and evaluations of an EDA,
+    // where the borders follow from the size and are not part of the checkpoint
+    fn restore<F>(
+        &mut self,
+        state: &mut SimulationState<Bitstring>,
+        fitness_function: &mut EvaluationCounter<F>,
+        checkpoint: &serde_json::Value,
+    ) -> Result<(), CheckpointError> {
+        let restored_state = checkpoint_state(state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        let probabilities: Vec<f64> = checkpoint_field(checkpoint, "probabilities")?;
+        check_size(
+            "probabilities",
+            self.probabilities.len(),
+            probabilities.len(),
+        )?;
+        *state = restored_state;
+        self.probabilities = probabilities;
+        fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
+// Implementation of the compact genetic algorithm (cGA) with hypothetical population size K
+// Each iteration two bitstrings are sampled, and each marginal where they differ
+// is moved by 1/K towards the value of the better one
+pub struct CompactGA<F: FitnessFunction<Bitstring>> {
+    pub state: SimulationState<Bitstring>,
+    fitness_function: EvaluationCounter<F>,
+    marginals: Marginals,
+    k: f64,
+}
+
+impl<F> CompactGA<F>
+where
+    F: FitnessFunction<Bitstring>,
+{
+    pub fn new<R: MyRng>(fitness_function: F, size: usize, k: f64, rng: &mut R) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        let state = initial_state(size, &fitness_function, rng);
+        CompactGA {
+            state,
+            fitness_function,
+            marginals: Marginals::new(size),
+            k,
+        }
+    }
+
+    pub fn probabilities(&self) -> &Vec<f64> {
+        &self.marginals.probabilities
+    }
+}
+
+impl<F> AlgorithmCore for CompactGA<F>
+where
+    F: FitnessFunction<Bitstring>,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        let x = sample(&self.marginals.probabilities, rng);
+        let y = sample(&self.marginals.probabilities, rng);
+        let fitness_x = self.fitness_function.evaluate(&x);
+        let fitness_y = self.fitness_function.evaluate(&y);
+
+        let (winner, winner_fitness, loser) =
+            if self.fitness_function.compare(fitness_x, fitness_y) == std::cmp::Ordering::Less {
+                (y, fitness_y, x)
+            } else {
+                (x, fitness_x, y)
+            };
+
+        let step = 1.0 / self.k;
+        self.marginals
+            .update(|i, p| match (winner.get(i), loser.get(i)) {
+                (true, false) => p + step,
+                (false, true) => p - step,
+                _ => p,
+            });
+
+        update_best(
+            &mut self.state,
+            &self.fitness_function,
+            &winner,
+            winner_fitness,
+        );
+        self.state.iteration += 1;
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        self.marginals
+            .status_json(&self.state, &self.fitness_function)
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        self.marginals
+            .checkpoint(&self.state, &self.fitness_function)
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        self.marginals
+            .restore(&mut self.state, &mut self.fitness_function, checkpoint)
+    }
+}
+
+// Implementation of the univariate marginal distribution algorithm (UMDA)
+// Each iteration lambda bitstrings are sampled, and the marginals are set
+// to the frequency of ones among the mu best
+pub struct UMDA<F: FitnessFunction<Bitstring>> {
+    pub state: SimulationState<Bitstring>,
+    fitness_function: EvaluationCounter<F>,
+    marginals: Marginals,
+    size: usize,
+    mu: usize,
+    lambda: usize,
+}
+
+impl<F> UMDA<F>
+where
+    F: FitnessFunction<Bitstring>,
+{
+    pub fn new<R: MyRng>(
+        fitness_function: F,
+        size: usize,
+        mu: usize,
+        lambda: usize,
+        rng: &mut R,
+    ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        assert!(
+            0 < mu && mu <= lambda,
+            "mu must be positive and at most lambda"
+        );
+        let state = initial_state(size, &fitness_function, rng);
+        UMDA {
+            state,
+            fitness_function,
+            marginals: Marginals::new(size),
+            size,
+            mu,
+            lambda,
+        }
+    }
+
+    pub fn probabilities(&self) -> &Vec<f64> {
+        &self.marginals.probabilities
+    }
+}
+
+impl<F> AlgorithmCore for UMDA<F>
+where
+    F: FitnessFunction<Bitstring>,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        let selected = sample_best(
+            &self.marginals.probabilities,
+            self.mu,
+            self.lambda,
+            &self.fitness_function,
+            rng,
+        );
+
+        let frequencies = frequencies(&selected, self.size);
+        self.marginals.update(|i, _| frequencies[i]);
+
+        update_best(
+            &mut self.state,
+            &self.fitness_function,
+            &selected[0].0,
+            selected[0].1,
+        );
+        self.state.iteration += 1;
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        self.marginals
+            .status_json(&self.state, &self.fitness_function)
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        self.marginals
+            .checkpoint(&self.state, &self.fitness_function)
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        self.marginals
+            .restore(&mut self.state, &mut self.fitness_function, checkpoint)
+    }
+}
+
+// Implementation of population-based incremental learning (PBIL)
+// Like UMDA, but the marginals are moved towards the frequencies of the mu best
+// with the given learning rate instead of being replaced
+pub struct PBIL<F: FitnessFunction<Bitstring>> {
+    pub state: SimulationState<Bitstring>,
+    fitness_function: EvaluationCounter<F>,
+    marginals: Marginals,
+    size: usize,
+    mu: usize,
+    lambda: usize,
+    learning_rate: f64,
+}
+
+impl<F> PBIL<F>
+where
+    F: FitnessFunction<Bitstring>,
+{
+    pub fn new<R: MyRng>(
+        fitness_function: F,
+        size: usize,
+        mu: usize,
+        lambda: usize,
+        learning_rate: f64,
+        rng: &mut R,
+    ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        assert!(
+            0 < mu && mu <= lambda,
+            "mu must be positive and at most lambda"
+        );
+        let state = initial_state(size, &fitness_function, rng);
+        PBIL {
+            state,
+            fitness_function,
+            marginals: Marginals::new(size),
+            size,
+            mu,
+            lambda,
+            learning_rate,
+        }
+    }
+
+    pub fn probabilities(&self) -> &Vec<f64> {
+        &self.marginals.probabilities
+    }
+}
+
+impl<F> AlgorithmCore for PBIL<F>
+where
+    F: FitnessFunction<Bitstring>,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        let selected = sample_best(
+            &self.marginals.probabilities,
+            self.mu,
+            self.lambda,
+            &self.fitness_function,
+            rng,
+        );
+
+        let frequencies = frequencies(&selected, self.size);
+        let learning_rate = self.learning_rate;
+        self.marginals
+            .update(|i, p| (1.0 - learning_rate) * p + learning_rate * frequencies[i]);
+
+        update_best(
+            &mut self.state,
+            &self.fitness_function,
+            &selected[0].0,
+            selected[0].1,
+        );
+        self.state.iteration += 1;
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        self.marginals
+            .status_json(&self.state, &self.fitness_function)
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        self.marginals
+            .checkpoint(&self.state, &self.fitness_function)
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        self.marginals
+            .restore(&mut self.state, &mut self.fitness_function, checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::assert_checkpoint_roundtrip;
+    use crate::{fitness::one_max::OneMax, rng::MockRng};
+
+    // Bit i of a sample is one if the float value is below p_i
+    fn sample_floats(bits: &str) -> Vec<f64> {
+        bits.chars()
+            .map(|c| if c == '1' { 0.1 } else { 0.9 })
+            .collect()
+    }
+
+    // Samples 1110, 1100 and 0000 from the uniform distribution, after an initial solution 0000
+    fn rng_with_samples() -> MockRng {
+        MockRng::new_float(sample_floats("111011000000")).with_bool(vec![false; 4])
+    }
+
+    #[test]
+    fn test_compact_ga() {
+        // Equally good samples 1100 and 0110, where the first one wins the tie
+        let mut rng = MockRng::new_float(sample_floats("11000110")).with_bool(vec![false; 4]);
+        let mut cga = CompactGA::new(OneMax, 4, 4.0, &mut rng);
+        cga.iterate(&mut rng);
+        assert_eq!(*cga.probabilities(), vec![0.75, 0.5, 0.25, 0.5]);
+        assert_eq!(cga.current_fitness(), 2.0);
+        assert_eq!(cga.evaluations(), 3);
+    }
+
+    #[test]
+    fn test_umda() {
+        let mut rng = rng_with_samples();
+        let mut umda = UMDA::new(OneMax, 4, 2, 3, &mut rng);
+        umda.iterate(&mut rng);
+        // Frequencies 1, 1, 1/2 and 0 of the two best samples, kept within [1/4, 3/4]
+        assert_eq!(*umda.probabilities(), vec![0.75, 0.75, 0.5, 0.25]);
+        assert_eq!(umda.current_fitness(), 3.0);
+        assert_eq!(umda.evaluations(), 4);
+    }
+
+    #[test]
+    fn test_pbil() {
+        let mut rng = rng_with_samples();
+        let mut pbil = PBIL::new(OneMax, 4, 2, 3, 0.25, &mut rng);
+        pbil.iterate(&mut rng);
+        assert_eq!(*pbil.probabilities(), vec![0.625, 0.625, 0.5, 0.375]);
+        assert_eq!(pbil.current_fitness(), 3.0);
+        assert_eq!(pbil.evaluations(), 4);
+    }
+
+    #[test]
+    fn test_single_bit() {
+        let mut rng = MockRng::new_float(sample_floats("1010")).with_bool(vec![false, false]);
+        let mut cga = CompactGA::new(OneMax, 1, 1.0, &mut rng);
+        let mut umda = UMDA::new(OneMax, 1, 1, 2, &mut rng);
+        cga.iterate(&mut rng);
+        umda.iterate(&mut rng);
+        assert_eq!(*cga.probabilities(), vec![0.5]);
+        assert_eq!(*umda.probabilities(), vec![0.5]);
+        assert_eq!(umda.current_fitness(), 1.0);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        assert_checkpoint_roundtrip(|rng| CompactGA::new(OneMax, 30, 10.0, rng));
+        assert_checkpoint_roundtrip(|rng| UMDA::new(OneMax, 30, 5, 10, rng));
+        assert_checkpoint_roundtrip(|rng| PBIL::new(OneMax, 30, 5, 10, 0.1, rng));
+    }
+}
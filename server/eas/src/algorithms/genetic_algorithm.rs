@@ -0,0 +1,361 @@
+use super::{
+    check_size, checkpoint_field, checkpoint_state, optional_checkpoint_field, solution_status,
+    AlgorithmCore, CheckpointError, SimulationState,
+};
+use crate::{
+    crossover::Crossover,
+    fitness::{EvaluationCounter, FitnessFunction},
+    mutation::Mutation,
+    rng::MyRng,
+    search_space::SearchSpace,
+};
+use serde_json::json;
+
+// Enum representing the parent selection schemes of the genetic algorithm
+// Tournament(k) picks the best of k uniformly chosen individuals,
+// Roulette picks individuals proportional to their fitness (1/fitness when minimizing),
+// and Truncation(f) picks uniformly among the best fraction f of the population
+#[derive(Debug, Clone, Copy)]
+pub enum Selection {
+    Tournament(usize),
+    Roulette,
+    Truncation(f64),
+}
+
+// Enum representing how offspring replace the population
+// Generational creates a full new population each iteration, keeping the `elitism` best individuals,
+// while SteadyState creates a single offspring each iteration, replacing the worst individual if not worse
+#[derive(Debug, Clone, Copy)]
+pub enum Replacement {
+    Generational { elitism: usize },
+    SteadyState,
+}
+
+// Implementation of a genetic algorithm independent of search space, fitness function,
+// crossover and mutation operator. Each offspring is created by selecting a parent,
+// recombining it with a second selected parent with the given crossover probability, and mutating the result
+pub struct GeneticAlgorithm<S, F, C, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    C: Crossover<S>,
+    M: Mutation<S>,
+{
+    pub state: SimulationState<S>,
+    // Population ordered from best to worst fitness
+    population: Vec<(S, f64)>,
+    fitness_function: EvaluationCounter<F>,
+    crossover: C,
+    mutator: M,
+    crossover_probability: f64,
+    selection: Selection,
+    replacement: Replacement,
+}
+
+impl<S, F, C, M> GeneticAlgorithm<S, F, C, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    C: Crossover<S>,
+    M: Mutation<S>,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<R: MyRng>(
+        size: usize,
+        population_size: usize,
+        crossover_probability: f64,
+        selection: Selection,
+        replacement: Replacement,
+        crossover: C,
+        mutator: M,
+        fitness_function: F,
+        rng: &mut R,
+    ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        assert!(population_size > 0, "population size must be positive");
+
+        let mut population = (0..population_size)
+            .map(|_| {
+                let solution = S::new_random(size, rng);
+                let fitness = fitness_function.evaluate(&solution);
+                (solution, fitness)
+            })
+            .collect::<Vec<_>>();
+        population.sort_by(|a, b| fitness_function.compare(b.1, a.1));
+
+        let (current_solution, current_fitness) = population[0].clone();
+        GeneticAlgorithm {
+            state: SimulationState {
+                iteration: 0,
+                current_solution,
+                current_fitness,
+            },
+            population,
+            fitness_function,
+            crossover,
+            mutator,
+            crossover_probability,
+            selection,
+            replacement,
+        }
+    }
+
+    // Fitness values of the current population, ordered from best to worst
+    pub fn population_fitness(&self) -> Vec<f64> {
+        self.population.iter().map(|i| i.1).collect()
+    }
+
+    // Select the index of a parent in the population
+    fn select<R: MyRng>(&self, rng: &mut R) -> usize {
+        let n = self.population.len();
+        match self.selection {
+            // As the population is sorted, the best individual of the tournament has the lowest index
+            Selection::Tournament(k) => {
+                (0..k.max(1)).map(|_| rng.random_range(0..n)).min().unwrap()
+            }
+            Selection::Roulette => {
+                let maximizing = self.fitness_function.is_maximizing();
+                let weights = self
+                    .population
+                    .iter()
+                    .map(|(_, f)| {
+                        let w = if maximizing { *f } else { 1.0 / *f };
+                        if w.is_finite() {
+                            w.max(0.0)
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let total = weights.iter().sum::<f64>();
+                if total <= 0.0 {
+                    return rng.random_range(0..n);
+                }
+                let mut choice = rng.random_range_float(0.0..total);
+                for (i, w) in weights.iter().enumerate() {
+                    if choice < *w {
+                        return i;
+                    }
+                    choice -= w;
+                }
+                n - 1
+            }
+            Selection::Truncation(fraction) => {
+                let count = ((fraction * n as f64).ceil() as usize).clamp(1, n);
+                rng.random_range(0..count)
+            }
+        }
+    }
+
+    fn create_offspring<R: MyRng>(&self, rng: &mut R) -> (S, f64) {
+        let first = &self.population[self.select(rng)].0;
+        let child = if rng.random_bool(self.crossover_probability) {
+            let second = &self.population[self.select(rng)].0;
+            self.crossover.apply(first, second, rng)
+        } else {
+            first.clone()
+        };
+        let child = self.mutator.apply(&child, rng);
+        let fitness = self.fitness_function.evaluate(&child);
+        (child, fitness)
+    }
+}
+
+impl<S, F, C, M> AlgorithmCore for GeneticAlgorithm<S, F, C, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    C: Crossover<S>,
+    M: Mutation<S>,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        match self.replacement {
+            Replacement::Generational { elitism } => {
+                let size = self.population.len();
+                let elites = elitism.min(size);
+                let mut next = self.population[..elites].to_vec();
+                while next.len() < size {
+                    next.push(self.create_offspring(rng));
+                }
+                let fitness_function = &self.fitness_function;
+                next.sort_by(|a, b| fitness_function.compare(b.1, a.1));
+                self.population = next;
+            }
+            Replacement::SteadyState => {
+                let offspring = self.create_offspring(rng);
+                let worst = self.population.len() - 1;
+                if self
+                    .fitness_function
+                    .compare(offspring.1, self.population[worst].1)
+                    != std::cmp::Ordering::Less
+                {
+                    self.population.pop();
+                    let position = self.population.partition_point(|i| {
+                        self.fitness_function.compare(i.1, offspring.1)
+                            == std::cmp::Ordering::Greater
+                    });
+                    self.population.insert(position, offspring);
+                }
+            }
+        }
+
+        self.state.iteration += 1;
+
+        let (best, best_fitness) = &self.population[0];
+        if self
+            .fitness_function
+            .compare(*best_fitness, self.state.current_fitness)
+            == std::cmp::Ordering::Greater
+        {
+            self.state.current_fitness = *best_fitness;
+            self.state.current_solution = best.clone();
+        }
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        let population_fitness = self.population_fitness();
+        let mean_fitness = population_fitness.iter().sum::<f64>() / population_fitness.len() as f64;
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string(),
+                "population_fitness": population_fitness,
+                "best_fitness": population_fitness[0],
+                "worst_fitness": population_fitness[population_fitness.len() - 1],
+                "mean_fitness": mean_fitness,
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        json!({
+            "state": self.state,
+            "population": self.population,
+            "evaluations": self.fitness_function.evaluations(),
+        })
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        let population: Vec<(S, f64)> = checkpoint_field(checkpoint, "population")?;
+        check_size("population", self.population.len(), population.len())?;
+        for (solution, _) in &population {
+            check_size("solution", state.current_solution.size(), solution.size())?;
+        }
+        self.state = state;
+        self.population = population;
+        self.fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::assert_checkpoint_roundtrip;
+    use crate::{
+        crossover::OnePointCrossover, fitness::one_max::OneMax, mutation::SingleBitflip,
+        rng::MockRng, search_space::Bitstring,
+    };
+
+    // Initial population 1110 and 0001
+    fn initial_bits() -> Vec<bool> {
+        vec![true, true, true, false, false, false, false, true]
+    }
+
+    #[test]
+    fn test_steady_state() {
+        // Tournament picks 1110, crossover with 0001 at cut 3 gives 1111, and flipping bit 0 gives 0111
+        let mut bits = initial_bits();
+        bits.push(true);
+        let mut rng = MockRng::default()
+            .with_bool(bits)
+            .with_range(vec![0, 1, 1, 1, 3, 0]);
+        let mut ga = GeneticAlgorithm::new(
+            4,
+            2,
+            1.0,
+            Selection::Tournament(2),
+            Replacement::SteadyState,
+            OnePointCrossover,
+            SingleBitflip,
+            OneMax,
+            &mut rng,
+        );
+        assert_eq!(ga.population_fitness(), vec![3.0, 1.0]);
+        ga.iterate(&mut rng);
+
+        // The offspring replaces the worst individual and is placed before the equally good parent
+        assert_eq!(ga.population_fitness(), vec![3.0, 3.0]);
+        assert_eq!(
+            ga.population[0].0,
+            Bitstring::from_bitstring("0111").unwrap()
+        );
+        assert_eq!(ga.current_fitness(), 3.0);
+        assert_eq!(ga.evaluations(), 3);
+    }
+
+    #[test]
+    fn test_generational_elitism() {
+        // The only offspring copies 0001 without crossover, and flipping bit 3 gives 0000
+        let mut bits = initial_bits();
+        bits.push(false);
+        let mut rng = MockRng::default().with_bool(bits).with_range(vec![1, 1, 3]);
+        let mut ga = GeneticAlgorithm::new(
+            4,
+            2,
+            0.5,
+            Selection::Tournament(2),
+            Replacement::Generational { elitism: 1 },
+            OnePointCrossover,
+            SingleBitflip,
+            OneMax,
+            &mut rng,
+        );
+        ga.iterate(&mut rng);
+
+        // The best individual survives as elite even though the offspring is worse
+        assert_eq!(ga.population_fitness(), vec![3.0, 0.0]);
+        assert_eq!(ga.current_fitness(), 3.0);
+        assert_eq!(ga.iterations(), 1);
+        assert_eq!(ga.evaluations(), 3);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        assert_checkpoint_roundtrip(|rng| {
+            GeneticAlgorithm::new(
+                30,
+                10,
+                0.8,
+                Selection::Tournament(2),
+                Replacement::Generational { elitism: 1 },
+                OnePointCrossover,
+                SingleBitflip,
+                OneMax,
+                rng,
+            )
+        });
+    }
+}
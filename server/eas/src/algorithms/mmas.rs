@@ -1,6 +1,10 @@
-use super::{AlgorithmCore, SimulationState};
+use super::{
+    check_matrix_size, checkpoint_field, checkpoint_state, optional_checkpoint_field,
+    solution_status, AlgorithmCore, CheckpointError, SimulationState,
+};
 use crate::{
-    fitness::FitnessFunction,
+    fitness::{EvaluationCounter, FitnessFunction},
+    local_search::LocalSearch,
     rng::MyRng,
     search_space::{Bitstring, Permutation, SearchSpace},
 };
@@ -9,7 +13,7 @@ use std::{usize, vec};
 
 pub struct MMAStsp<F: FitnessFunction<Permutation>> {
     pub state: SimulationState<Permutation>,
-    fitness_function: F,
+    fitness_function: EvaluationCounter<F>,
     pheromone: Vec<Vec<f64>>,
     heuristic: Vec<Vec<f64>>,
     size: usize,
@@ -22,6 +26,11 @@ pub struct MMAStsp<F: FitnessFunction<Permutation>> {
     t_max: f64,
     q: f64,
     probabilities: Vec<Vec<f64>>,
+    candidates: Option<Vec<Vec<usize>>>,
+    local_search: Option<(LocalSearch, LocalSearchScope)>,
+    // Number of tours improved by the local search, whose moves are evaluated
+    // on the distances directly and are not counted as fitness evaluations
+    local_searches: u64,
 }
 impl<F> MMAStsp<F>
 where
@@ -41,8 +50,9 @@ where
         q: f64,
         rng: &mut R,
     ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
         let path = if near_neigh {
-            Self::nearest_neighbor(&graph, size)
+            nearest_neighbor(&graph, size)
         } else {
             Permutation::new_random(size, rng)
         };
@@ -61,15 +71,8 @@ where
         };
 
         let pheromone = vec![vec![t_max; size]; size];
-        let mut heuristic = vec![vec![0.0; size]; size];
-        for i in 0..size {
-            for ii in i..size {
-                let val = 1.0 / graph[i][ii];
-                heuristic[i][ii] = val;
-                heuristic[ii][i] = val;
-            }
-        }
-        let probabilities = vec![vec![0.0;size];size];
+        let heuristic = heuristic_matrix(&graph, size);
+        let probabilities = vec![vec![0.0; size]; size];
         MMAStsp {
             state: SimulationState {
                 iteration: 0,
@@ -89,36 +92,39 @@ where
             t_max,
             q,
             probabilities,
+            candidates: None,
+            local_search: None,
+            local_searches: 0,
         }
     }
 
-    fn nearest_neighbor(graph: &Vec<Vec<f64>>, size: usize) -> Permutation {
-        let mut path = Vec::<usize>::with_capacity(size);
-        let mut visited = vec![false; size];
-        visited[0] = true;
-        let mut neighbors = &graph[0];
-        while path.len() != size {
-            let mut min_neighbor = (f64::MAX, 0); //TODO
-            for i in 0..neighbors.len() {
-                if visited[i] {
-                    continue;
-                }
-                if neighbors[i] < min_neighbor.0 {
-                    min_neighbor = (neighbors[i], i)
-                }
-            }
-            visited[min_neighbor.1] = true;
-            neighbors = &graph[min_neighbor.1];
-            path.push(min_neighbor.1);
-        }
+    // Restrict tour construction to the k nearest neighbors of the current vertex.
+    // Falls back to scanning all vertices when every candidate has been visited
+    pub fn with_candidate_lists(mut self, k: usize) -> Self {
+        let k = k.min(self.size - 1);
+        let candidates = (0..self.size)
+            .map(|i| {
+                let mut neighbors = (0..self.size).filter(|&j| j != i).collect::<Vec<_>>();
+                // The heuristic is the inverse distance, so nearest neighbors have the largest heuristic
+                neighbors.sort_by(|&a, &b| self.heuristic[i][b].total_cmp(&self.heuristic[i][a]));
+                neighbors.truncate(k);
+                neighbors
+            })
+            .collect();
+        self.candidates = Some(candidates);
+        self
+    }
 
-        Permutation::new(path)
+    // Apply the local search as daemon action to the constructed tours before the pheromone update
+    pub fn with_local_search(mut self, local_search: LocalSearch, scope: LocalSearchScope) -> Self {
+        self.local_search = Some((local_search, scope));
+        self
     }
 
-    fn probability(&mut self, i: usize, j: usize) -> f64{
+    fn probability(&mut self, i: usize, j: usize) -> f64 {
         if self.probabilities[i][j] == 0.0 {
-            self.probabilities[i][j] = self.pheromone[i][j].powf(self.alpha)
-            * self.heuristic[i][j].powf(self.beta);
+            self.probabilities[i][j] =
+                self.pheromone[i][j].powf(self.alpha) * self.heuristic[i][j].powf(self.beta);
             self.probabilities[j][i] = self.probabilities[i][j];
         }
         self.probabilities[i][j]
@@ -130,6 +136,7 @@ where
         let mut visited = vec![false; self.size];
         let mut neighbors = vec![0; self.size];
         let mut neighbor_weights = vec![0.0; self.size];
+        let candidates = self.candidates.take();
 
         let mut current_node = start_node;
         visited[current_node] = true;
@@ -137,14 +144,28 @@ where
 
         for _ in 0..self.size - 1 {
             let mut added = 1;
-            for i in 0..self.size {
-                if visited[i] {
-                    continue;
+            if let Some(lists) = &candidates {
+                for &i in &lists[current_node] {
+                    if visited[i] {
+                        continue;
+                    }
+                    neighbors[added] = i;
+                    neighbor_weights[added] =
+                        self.probability(current_node, i) + neighbor_weights[added - 1];
+                    added += 1;
+                }
+            }
+            // Scan all vertices if no candidate lists are used or all candidates are visited
+            if added == 1 {
+                for i in 0..self.size {
+                    if visited[i] {
+                        continue;
+                    }
+                    neighbors[added] = i;
+                    neighbor_weights[added] =
+                        self.probability(current_node, i) + neighbor_weights[added - 1];
+                    added += 1;
                 }
-                neighbors[added] = i;
-                neighbor_weights[added] = self.probability(current_node, i)
-                    + neighbor_weights[added - 1];
-                added += 1;
             }
             let total_weight = neighbor_weights[added - 1];
             let choice = rng.random_range_float(0.0..1.0);
@@ -156,6 +177,7 @@ where
             path.push(current_node);
             visited[current_node] = true;
         }
+        self.candidates = candidates;
         Permutation::new(path)
     }
 
@@ -170,15 +192,14 @@ where
         }
     }
 
-    fn update(&mut self, paths: &Vec<Permutation>) {
+    fn update(&mut self, paths: &[(Permutation, f64)]) {
         let mut generation_best = (f64::MAX, Permutation::new(vec![0]));
-        for path in paths {
+        for (path, fit_val) in paths {
             // Check if there is a new better solution
-            let fit_val = self.fitness_function.evaluate(path);
-            if self.fitness_function.compare(fit_val, generation_best.0)
+            if self.fitness_function.compare(*fit_val, generation_best.0)
                 == std::cmp::Ordering::Greater
             {
-                generation_best = (fit_val, path.clone());
+                generation_best = (*fit_val, path.clone());
             }
         }
         if self
@@ -191,25 +212,28 @@ where
 
         match self.update_strategy {
             PheromoneUpdateStrategy::AllAnts => {
-                for path in paths {
-                    self.apply(path);
+                for (path, fit_val) in paths {
+                    self.apply(path, *fit_val);
                 }
             }
             PheromoneUpdateStrategy::GenerationBest => {
-                self.apply(&generation_best.1.clone());
+                self.apply(&generation_best.1.clone(), generation_best.0);
             }
             PheromoneUpdateStrategy::BestSoFar => {
-                self.apply(&self.state.current_solution.clone());
+                self.apply(
+                    &self.state.current_solution.clone(),
+                    self.state.current_fitness,
+                );
             }
         }
     }
 
-    fn apply(&mut self, p: &Permutation) {
+    fn apply(&mut self, p: &Permutation, fit_val: f64) {
         let perm = p.permutation();
         let p_val = if self.q == 0.0 {
             self.evap_factor
         } else {
-            self.q / self.fitness_function.evaluate(p)
+            self.q / fit_val
         };
         self.pheromone[perm[0]][perm[perm.len() - 1]] = self
             .t_max
@@ -224,6 +248,58 @@ where
     }
 }
 
+// Heuristic information 1/d_ij of the edges. Zero distances would give an infinite heuristic,
+// so they are replaced by half of the smallest positive distance of the instance
+pub(super) fn heuristic_matrix(graph: &[Vec<f64>], size: usize) -> Vec<Vec<f64>> {
+    let min_distance = graph
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&d| d > 0.0)
+        .fold(f64::INFINITY, f64::min);
+    let zero_distance = if min_distance.is_finite() {
+        min_distance / 2.0
+    } else {
+        1.0
+    };
+    let mut heuristic = vec![vec![0.0; size]; size];
+    for i in 0..size {
+        for j in 0..size {
+            let d = if graph[i][j] > 0.0 {
+                graph[i][j]
+            } else {
+                zero_distance
+            };
+            heuristic[i][j] = 1.0 / d;
+        }
+    }
+    heuristic
+}
+
+// Construct a tour by greedily moving to the nearest unvisited vertex
+pub(super) fn nearest_neighbor(graph: &[Vec<f64>], size: usize) -> Permutation {
+    let mut path = Vec::<usize>::with_capacity(size);
+    let mut visited = vec![false; size];
+    visited[0] = true;
+    let mut neighbors = &graph[0];
+    while path.len() != size {
+        let mut min_neighbor = (f64::MAX, 0); //TODO
+        for i in 0..neighbors.len() {
+            if visited[i] {
+                continue;
+            }
+            if neighbors[i] < min_neighbor.0 {
+                min_neighbor = (neighbors[i], i)
+            }
+        }
+        visited[min_neighbor.1] = true;
+        neighbors = &graph[min_neighbor.1];
+        path.push(min_neighbor.1);
+    }
+
+    Permutation::new(path)
+}
+
 impl<F> AlgorithmCore for MMAStsp<F>
 where
     F: FitnessFunction<Permutation>,
@@ -235,9 +311,33 @@ where
         for _ in 0..self.ants {
             paths.push(self.construct(rng));
         }
+        // Daemon actions, evaluating each tour once such that the pheromone update reuses the fitness
+        let fitness_function = &self.fitness_function;
+        if let Some((local_search, LocalSearchScope::AllAnts)) = &self.local_search {
+            for path in paths.iter_mut() {
+                *path = local_search.apply(path);
+            }
+            self.local_searches += paths.len() as u64;
+        }
+        let mut tours = paths
+            .into_iter()
+            .map(|p| {
+                let fitness = fitness_function.evaluate(&p);
+                (p, fitness)
+            })
+            .collect::<Vec<_>>();
+        if let Some((local_search, LocalSearchScope::IterationBest)) = &self.local_search {
+            let best = (0..tours.len())
+                .max_by(|&a, &b| fitness_function.compare(tours[a].1, tours[b].1))
+                .unwrap();
+            let path = local_search.apply(&tours[best].0);
+            let fitness = fitness_function.evaluate(&path);
+            tours[best] = (path, fitness);
+            self.local_searches += 1;
+        }
         // Update pheromones
         self.decrease();
-        self.update(&paths);
+        self.update(&tours);
 
         // Update iteration
         self.state.iteration += 1;
@@ -251,21 +351,66 @@ where
         self.state.iteration
     }
 
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
     fn status_json(&self) -> serde_json::Value {
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string(),
+                "pheromones": self.pheromone,
+                "t_max" : self.t_max,
+                "t_min" : self.t_min,
+                "local_searches": self.local_searches,
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    // The pheromone bounds are included, as they depend on the initial solution when p_best is given.
+    // The cached probabilities are reset every iteration, and are not part of the state
+    fn checkpoint(&self) -> serde_json::Value {
         json!({
-            "iterations": self.state.iteration,
-            "current_fitness": self.state.current_fitness,
-            "current_solution": self.state.current_solution.to_string(),
+            "state": self.state,
             "pheromones": self.pheromone,
-            "t_max" : self.t_max,
-            "t_min" : self.t_min,
+            "t_max": self.t_max,
+            "t_min": self.t_min,
+            "evaluations": self.fitness_function.evaluations(),
+            "local_searches": self.local_searches,
         })
     }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        let local_searches = optional_checkpoint_field(checkpoint, "local_searches")?;
+        let pheromone: Vec<Vec<f64>> = checkpoint_field(checkpoint, "pheromones")?;
+        check_matrix_size("pheromones", &self.pheromone, &pheromone)?;
+        let t_max = checkpoint_field(checkpoint, "t_max")?;
+        let t_min = checkpoint_field(checkpoint, "t_min")?;
+        self.state = state;
+        self.pheromone = pheromone;
+        self.t_max = t_max;
+        self.t_min = t_min;
+        self.probabilities = vec![vec![0.0; self.size]; self.size];
+        self.fitness_function.set_evaluations(evaluations);
+        self.local_searches = local_searches;
+        Ok(())
+    }
 }
 
 pub struct MMASbs<F: FitnessFunction<Bitstring>> {
     pub state: SimulationState<Bitstring>,
-    fitness_function: F,
+    fitness_function: EvaluationCounter<F>,
     pheromone: Vec<Vec<f64>>,
     size: usize,
     ants: usize,
@@ -286,6 +431,7 @@ where
         evap_factor: f64,
         rng: &mut R,
     ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
         let path = Bitstring::new_random(size, rng);
         let current_solution = path;
         let current_fitness = fitness_function.evaluate(&current_solution);
@@ -316,7 +462,9 @@ where
 
         for step in 0..self.size {
             let r = rng.random_range_float(0.0..1.0);
-            path[step] = r < self.pheromone[step][0].powf(self.alpha)/(self.pheromone[step][0].powf(self.alpha) + self.pheromone[step][1].powf(self.alpha))
+            path[step] = r < self.pheromone[step][0].powf(self.alpha)
+                / (self.pheromone[step][0].powf(self.alpha)
+                    + self.pheromone[step][1].powf(self.alpha))
         }
         Bitstring::new(path)
     }
@@ -335,14 +483,19 @@ where
             }
 
             // Apply new pheromones
-            let bits = self.state.current_solution.bits();
             for i in 0..self.size {
-                if bits[i] {
-                    self.pheromone[i][0] = ((1.0 - self.evap_factor) * self.pheromone[i][0] + self.evap_factor).min(self.t_max);
-                    self.pheromone[i][1] = ((1.0 - self.evap_factor) * self.pheromone[i][1]).max(self.t_min);
+                if self.state.current_solution.get(i) {
+                    self.pheromone[i][0] = ((1.0 - self.evap_factor) * self.pheromone[i][0]
+                        + self.evap_factor)
+                        .min(self.t_max);
+                    self.pheromone[i][1] =
+                        ((1.0 - self.evap_factor) * self.pheromone[i][1]).max(self.t_min);
                 } else {
-                    self.pheromone[i][1] = ((1.0 - self.evap_factor) * self.pheromone[i][1] + self.evap_factor).min(self.t_max);
-                    self.pheromone[i][0] = ((1.0 - self.evap_factor) * self.pheromone[i][0]).max(self.t_min);
+                    self.pheromone[i][1] = ((1.0 - self.evap_factor) * self.pheromone[i][1]
+                        + self.evap_factor)
+                        .min(self.t_max);
+                    self.pheromone[i][0] =
+                        ((1.0 - self.evap_factor) * self.pheromone[i][0]).max(self.t_min);
                 }
             }
         }
@@ -377,13 +530,221 @@ where
         self.state.iteration
     }
 
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
     fn status_json(&self) -> serde_json::Value {
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string()
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
         json!({
-            "iterations": self.state.iteration,
-            "current_fitness": self.state.current_fitness,
-            "current_solution": self.state.current_solution.to_string()
+            "state": self.state,
+            "pheromones": self.pheromone,
+            "evaluations": self.fitness_function.evaluations(),
         })
     }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        let pheromone: Vec<Vec<f64>> = checkpoint_field(checkpoint, "pheromones")?;
+        check_matrix_size("pheromones", &self.pheromone, &pheromone)?;
+        self.state = state;
+        self.pheromone = pheromone;
+        self.fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
+// MMAS for assignment problems on permutations, such as QAP (Stützle and Hoos),
+// where pheromone[i][j] is the desirability of assigning facility i to location j.
+// Ants assign the facilities in random order, choosing among the free locations proportional to pheromone^alpha.
+// The pheromone bounds and deposits follow the best-so-far fitness of a minimization problem,
+// using its absolute value of at least 1, as QAP instances with negative or zero flows can reach such fitness
+pub struct MMASqap<F: FitnessFunction<Permutation>> {
+    pub state: SimulationState<Permutation>,
+    fitness_function: EvaluationCounter<F>,
+    pheromone: Vec<Vec<f64>>,
+    size: usize,
+    ants: usize,
+    alpha: f64,
+    evap_factor: f64,
+    t_min: f64,
+    t_max: f64,
+}
+impl<F> MMASqap<F>
+where
+    F: FitnessFunction<Permutation>,
+{
+    pub fn new<R: MyRng>(
+        fitness_function: F,
+        size: usize,
+        ants: usize,
+        alpha: f64,
+        evap_factor: f64,
+        rng: &mut R,
+    ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        let current_solution = Permutation::new_random(size, rng);
+        let current_fitness = fitness_function.evaluate(&current_solution);
+        let mut mmas = MMASqap {
+            state: SimulationState {
+                iteration: 0,
+                current_solution,
+                current_fitness,
+            },
+            fitness_function,
+            pheromone: vec![],
+            size,
+            ants,
+            alpha,
+            evap_factor,
+            t_min: 0.0,
+            t_max: 0.0,
+        };
+        mmas.update_bounds();
+        mmas.pheromone = vec![vec![mmas.t_max; size]; size];
+        mmas
+    }
+
+    // t_max = 1 / (rho * f(best)) and t_min = t_max / (2n)
+    fn update_bounds(&mut self) {
+        self.t_max = 1.0 / (self.evap_factor * self.fitness_scale());
+        self.t_min = self.t_max / (2.0 * self.size as f64);
+    }
+
+    fn fitness_scale(&self) -> f64 {
+        self.state.current_fitness.abs().max(1.0)
+    }
+
+    fn construct<R: MyRng>(&self, rng: &mut R) -> Permutation {
+        let mut order = (0..self.size).collect::<Vec<_>>();
+        rng.shuffle_vec(&mut order);
+        let mut assignment = vec![0; self.size];
+        let mut free = (0..self.size).collect::<Vec<_>>();
+        let mut weights = vec![0.0; self.size];
+
+        for facility in order {
+            let mut total_weight = 0.0;
+            for (k, &location) in free.iter().enumerate() {
+                total_weight += self.pheromone[facility][location].powf(self.alpha);
+                weights[k] = total_weight;
+            }
+            let choice = rng.random_range_float(0.0..1.0) * total_weight;
+            let k = weights[..free.len() - 1]
+                .iter()
+                .position(|&w| choice < w)
+                .unwrap_or(free.len() - 1);
+            assignment[facility] = free.swap_remove(k);
+        }
+        Permutation::new(assignment)
+    }
+
+    fn update(&mut self, solutions: &[Permutation]) {
+        for solution in solutions {
+            let fitness = self.fitness_function.evaluate(solution);
+            if self
+                .fitness_function
+                .compare(fitness, self.state.current_fitness)
+                == std::cmp::Ordering::Greater
+            {
+                self.state.current_fitness = fitness;
+                self.state.current_solution = solution.clone();
+                self.update_bounds();
+            }
+        }
+
+        // Evaporate and reinforce the assignments of the best-so-far solution
+        let deposit = 1.0 / self.fitness_scale();
+        let best = self.state.current_solution.permutation();
+        for (row, &location) in self.pheromone.iter_mut().zip(best) {
+            for (j, pheromone) in row.iter_mut().enumerate() {
+                *pheromone *= 1.0 - self.evap_factor;
+                if location == j {
+                    *pheromone += deposit;
+                }
+                *pheromone = pheromone.clamp(self.t_min, self.t_max);
+            }
+        }
+    }
+}
+
+impl<F> AlgorithmCore for MMASqap<F>
+where
+    F: FitnessFunction<Permutation>,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        let solutions = (0..self.ants)
+            .map(|_| self.construct(rng))
+            .collect::<Vec<_>>();
+        self.update(&solutions);
+        self.state.iteration += 1;
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string()
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        json!({
+            "state": self.state,
+            "pheromones": self.pheromone,
+            "evaluations": self.fitness_function.evaluations(),
+        })
+    }
+
+    // The pheromone bounds are derived from the fitness of the restored best-so-far solution
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        let pheromone: Vec<Vec<f64>> = checkpoint_field(checkpoint, "pheromones")?;
+        check_matrix_size("pheromones", &self.pheromone, &pheromone)?;
+        self.state = state;
+        self.pheromone = pheromone;
+        self.update_bounds();
+        self.fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
 }
 
 pub enum PheromoneUpdateStrategy {
@@ -391,3 +752,124 @@ pub enum PheromoneUpdateStrategy {
     GenerationBest,
     AllAnts,
 }
+
+// Which of the constructed tours the local search is applied to
+#[derive(Debug, Clone, Copy)]
+pub enum LocalSearchScope {
+    AllAnts,
+    IterationBest,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::assert_checkpoint_roundtrip;
+    use crate::fitness::{one_max::OneMax, qap::QAP, tsp::TSP};
+    use crate::local_search::LocalSearchOperator;
+    use crate::rng::MockRng;
+
+    // Symmetric instance on 10 cities with distinct distances
+    fn distances() -> Vec<Vec<f64>> {
+        (0..10)
+            .map(|i: usize| {
+                (0..10)
+                    .map(|j: usize| if i == j { 0.0 } else { (i * j + i + j) as f64 })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Instance on 5 facilities where the identity has fitness 52 * flow_scale
+    fn qap(flow_scale: f64) -> QAP {
+        let flows = (0..5)
+            .map(|i: usize| {
+                (0..5)
+                    .map(|j: usize| ((i + 2 * j) % 4) as f64 * flow_scale)
+                    .collect()
+            })
+            .collect();
+        let distances = (0..5)
+            .map(|i: usize| (0..5).map(|j: usize| i.abs_diff(j) as f64).collect())
+            .collect();
+        QAP::new(flows, distances)
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        assert_checkpoint_roundtrip(|rng| {
+            MMAStsp::new(
+                distances(),
+                TSP::new(distances()),
+                10,
+                5,
+                1.0,
+                2.0,
+                0.1,
+                PheromoneUpdateStrategy::GenerationBest,
+                false,
+                0.05,
+                0.0,
+                rng,
+            )
+        });
+        // The number of local searches is part of the status
+        assert_checkpoint_roundtrip(|rng| {
+            MMAStsp::new(
+                distances(),
+                TSP::new(distances()),
+                10,
+                5,
+                1.0,
+                2.0,
+                0.1,
+                PheromoneUpdateStrategy::BestSoFar,
+                false,
+                0.05,
+                0.0,
+                rng,
+            )
+            .with_local_search(
+                LocalSearch::new(distances(), 5, LocalSearchOperator::TwoOpt),
+                LocalSearchScope::IterationBest,
+            )
+        });
+        assert_checkpoint_roundtrip(|rng| MMASbs::new(OneMax, 30, 5, 1.0, 0.1, rng));
+        assert_checkpoint_roundtrip(|rng| MMASqap::new(qap(1.0), 5, 5, 1.0, 0.1, rng));
+    }
+
+    #[test]
+    fn test_qap_construct_and_update() {
+        // (flow scale, best fitness, best solution) after constructing a solution with fitness 61 * flow_scale,
+        // which only replaces the identity for negative flows
+        let testcases = vec![
+            (1.0, 52.0, vec![0, 1, 2, 3, 4]),
+            (-1.0, -61.0, vec![0, 3, 1, 4, 2]),
+            (0.0, 0.0, vec![0, 1, 2, 3, 4]),
+        ];
+        for t in testcases {
+            let mut mmas = MMASqap::new(qap(t.0), 5, 1, 1.0, 0.1, &mut MockRng::default());
+            let initial = 1.0 / (0.1 * f64::abs(52.0 * t.0).max(1.0));
+            assert_eq!(mmas.t_max, initial);
+
+            // Facilities are assigned in order, choosing among the free locations with equal pheromones
+            let mut rng = MockRng::new_float(vec![0.0, 0.99, 0.5, 0.0, 0.0]);
+            let solution = mmas.construct(&mut rng);
+            assert_eq!(solution.permutation(), &vec![0, 3, 1, 4, 2]);
+
+            mmas.update(&[solution]);
+            assert_eq!(mmas.state.current_fitness, t.1);
+            assert_eq!(mmas.state.current_solution.permutation(), &t.2);
+            let scale = f64::abs(t.1).max(1.0);
+            let t_max = 1.0 / (0.1 * scale);
+            assert_eq!(mmas.t_max, t_max);
+            assert_eq!(mmas.t_min, t_max / 10.0);
+            for (i, row) in mmas.pheromone.iter().enumerate() {
+                for (j, &pheromone) in row.iter().enumerate() {
+                    let deposit = if t.2[i] == j { 1.0 / scale } else { 0.0 };
+                    let expected = (0.9 * initial + deposit).clamp(t_max / 10.0, t_max);
+                    assert!((pheromone - expected).abs() < 1e-12, "{} {}", i, j);
+                }
+            }
+        }
+    }
+}
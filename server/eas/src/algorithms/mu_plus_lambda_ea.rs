@@ -0,0 +1,321 @@
+use super::{
+    check_size, checkpoint_field, checkpoint_state, optional_checkpoint_field, solution_status,
+    AlgorithmCore, CheckpointError, SimulationState,
+};
+use crate::{
+    fitness::{EvaluationCounter, FitnessFunction},
+    mutation::Mutation,
+    rng::MyRng,
+    search_space::SearchSpace,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// Enum representing how ties between individuals of equal fitness are broken
+// when selecting the mu survivors from the parents and offspring
+#[derive(Debug, Clone, Copy)]
+pub enum TieBreaking {
+    PreferOffspring,
+    PreferParents,
+    Random,
+}
+
+// Enum representing how offspring identical to an individual already in the population are handled
+#[derive(Debug, Clone, Copy)]
+pub enum DuplicateHandling {
+    Allow,
+    Reject,
+}
+
+// Individual of the population, keeping track of whether it was created in the current generation
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct Individual<S: SearchSpace> {
+    solution: S,
+    fitness: f64,
+    offspring: bool,
+}
+
+// Implementation of the (mu+lambda) EA independent of search space, fitness function and mutation operator
+// Each generation lambda offspring are created by mutating uniformly chosen parents,
+// and the mu best individuals among parents and offspring survive
+pub struct MuPlusLambdaEA<S: SearchSpace, F: FitnessFunction<S>, M: Mutation<S>> {
+    pub state: SimulationState<S>,
+    population: Vec<Individual<S>>,
+    fitness_function: EvaluationCounter<F>,
+    mutator: M,
+    mu: usize,
+    lambda: usize,
+    tie_breaking: TieBreaking,
+    duplicates: DuplicateHandling,
+}
+
+impl<S, F, M> MuPlusLambdaEA<S, F, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    M: Mutation<S>,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<R: MyRng>(
+        size: usize,
+        mu: usize,
+        lambda: usize,
+        tie_breaking: TieBreaking,
+        duplicates: DuplicateHandling,
+        mutator: M,
+        fitness_function: F,
+        rng: &mut R,
+    ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        assert!(mu > 0, "mu must be positive");
+        assert!(lambda > 0, "lambda must be positive");
+
+        let mut population = (0..mu)
+            .map(|_| {
+                let solution = S::new_random(size, rng);
+                let fitness = fitness_function.evaluate(&solution);
+                Individual {
+                    solution,
+                    fitness,
+                    offspring: false,
+                }
+            })
+            .collect::<Vec<_>>();
+        population.sort_by(|a, b| fitness_function.compare(b.fitness, a.fitness));
+
+        let current_solution = population[0].solution.clone();
+        let current_fitness = population[0].fitness;
+        MuPlusLambdaEA {
+            state: SimulationState {
+                iteration: 0,
+                current_solution,
+                current_fitness,
+            },
+            population,
+            fitness_function,
+            mutator,
+            mu,
+            lambda,
+            tie_breaking,
+            duplicates,
+        }
+    }
+
+    // Fitness values of the current population, ordered from best to worst
+    pub fn population_fitness(&self) -> Vec<f64> {
+        self.population.iter().map(|i| i.fitness).collect()
+    }
+
+    fn is_duplicate(&self, candidates: &[Individual<S>], solution: &S) -> bool {
+        match self.duplicates {
+            DuplicateHandling::Allow => false,
+            DuplicateHandling::Reject => candidates.iter().any(|i| i.solution == *solution),
+        }
+    }
+}
+
+impl<S, F, M> AlgorithmCore for MuPlusLambdaEA<S, F, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    M: Mutation<S>,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        let mut candidates = std::mem::take(&mut self.population);
+        for individual in candidates.iter_mut() {
+            individual.offspring = false;
+        }
+
+        // Create lambda offspring from uniformly chosen parents
+        for _ in 0..self.lambda {
+            let parent = &candidates[rng.random_range(0..self.mu)];
+            let solution = self.mutator.apply(&parent.solution, rng);
+            if self.is_duplicate(&candidates, &solution) {
+                continue;
+            }
+            let fitness = self.fitness_function.evaluate(&solution);
+            candidates.push(Individual {
+                solution,
+                fitness,
+                offspring: true,
+            });
+        }
+
+        // Select the mu best individuals. Sorting is stable, so ties are broken
+        // by the order of the candidates prior to sorting
+        let fitness_function = &self.fitness_function;
+        match self.tie_breaking {
+            TieBreaking::PreferOffspring => candidates.sort_by(|a, b| {
+                fitness_function
+                    .compare(b.fitness, a.fitness)
+                    .then(b.offspring.cmp(&a.offspring))
+            }),
+            TieBreaking::PreferParents => candidates.sort_by(|a, b| {
+                fitness_function
+                    .compare(b.fitness, a.fitness)
+                    .then(a.offspring.cmp(&b.offspring))
+            }),
+            TieBreaking::Random => {
+                rng.shuffle_vec(&mut candidates);
+                candidates.sort_by(|a, b| fitness_function.compare(b.fitness, a.fitness));
+            }
+        }
+        candidates.truncate(self.mu);
+        self.population = candidates;
+
+        self.state.iteration += 1;
+
+        let best = &self.population[0];
+        if self
+            .fitness_function
+            .compare(best.fitness, self.state.current_fitness)
+            == std::cmp::Ordering::Greater
+        {
+            self.state.current_fitness = best.fitness;
+            self.state.current_solution = best.solution.clone();
+        }
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        let population_fitness = self.population_fitness();
+        let mean_fitness = population_fitness.iter().sum::<f64>() / population_fitness.len() as f64;
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string(),
+                "population_fitness": population_fitness,
+                "best_fitness": population_fitness[0],
+                "worst_fitness": population_fitness[population_fitness.len() - 1],
+                "mean_fitness": mean_fitness,
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        json!({
+            "state": self.state,
+            "population": self.population,
+            "evaluations": self.fitness_function.evaluations(),
+        })
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        let population: Vec<Individual<S>> = checkpoint_field(checkpoint, "population")?;
+        check_size("population", self.mu, population.len())?;
+        for individual in &population {
+            check_size(
+                "solution",
+                state.current_solution.size(),
+                individual.solution.size(),
+            )?;
+        }
+        self.state = state;
+        self.population = population;
+        self.fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::assert_checkpoint_roundtrip;
+    use crate::mutation::Bitflip;
+    use crate::{fitness::one_max::OneMax, mutation::SingleBitflip, rng::MockRng};
+
+    // Initial population 110 and 100, where the offspring 111 is created from 110
+    // and the duplicate 110 is created from 100.
+    // Returns which survivors are offspring, and the number of evaluations
+    fn select_survivors(
+        tie_breaking: TieBreaking,
+        duplicates: DuplicateHandling,
+    ) -> (Vec<bool>, u64) {
+        let mut rng = MockRng::default()
+            .with_bool(vec![true, false, false, true, true, false])
+            .with_range(vec![0, 2, 1, 1]);
+        let mut ea = MuPlusLambdaEA::new(
+            3,
+            2,
+            2,
+            tie_breaking,
+            duplicates,
+            SingleBitflip,
+            OneMax,
+            &mut rng,
+        );
+        assert_eq!(ea.population_fitness(), vec![2.0, 1.0]);
+        ea.iterate(&mut rng);
+        assert_eq!(ea.population_fitness(), vec![3.0, 2.0]);
+        assert_eq!(ea.current_fitness(), 3.0);
+        let offspring = ea.population.iter().map(|i| i.offspring).collect();
+        (offspring, ea.evaluations())
+    }
+
+    #[test]
+    fn test_select_survivors() {
+        // (tie breaking, duplicate handling, expected offspring survivors, expected evaluations)
+        // A rejected duplicate is neither evaluated nor added to the population
+        let testcases = vec![
+            (
+                TieBreaking::PreferOffspring,
+                DuplicateHandling::Allow,
+                vec![true, true],
+                4,
+            ),
+            (
+                TieBreaking::PreferParents,
+                DuplicateHandling::Allow,
+                vec![true, false],
+                4,
+            ),
+            (
+                TieBreaking::PreferOffspring,
+                DuplicateHandling::Reject,
+                vec![true, false],
+                3,
+            ),
+        ];
+        for t in testcases {
+            assert_eq!(select_survivors(t.0, t.1), (t.2, t.3));
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        assert_checkpoint_roundtrip(|rng| {
+            MuPlusLambdaEA::new(
+                30,
+                4,
+                6,
+                TieBreaking::PreferOffspring,
+                DuplicateHandling::Reject,
+                Bitflip,
+                OneMax,
+                rng,
+            )
+        });
+    }
+}
@@ -0,0 +1,283 @@
+use super::{
+    checkpoint_field, checkpoint_state, optional_checkpoint_field, solution_status, AlgorithmCore,
+    CheckpointError, SimulationState,
+};
+use crate::{
+    fitness::{EvaluationCounter, FitnessFunction},
+    mutation::Mutation,
+    rng::MyRng,
+    search_space::SearchSpace,
+};
+use serde_json::json;
+
+// Enum representing how the offspring population size lambda is chosen each generation
+// Represents both a fixed lambda, and a self-adjusting lambda following the success rule:
+// on success lambda <- lambda / F, otherwise lambda <- lambda * F^(1/s)
+// With s = 4 this is the one-fifth success rule
+pub enum OffspringPopulation {
+    Static(usize),
+    SelfAdjusting {
+        update_factor: f64,
+        success_ratio: f64,
+    },
+}
+
+impl OffspringPopulation {
+    // Create a self-adjusting offspring population using the one-fifth success rule with F = 1.5
+    pub fn new_one_fifth() -> Self {
+        OffspringPopulation::SelfAdjusting {
+            update_factor: 1.5,
+            success_ratio: 4.0,
+        }
+    }
+}
+
+// Implementation of the (1,lambda) EA independent of search space, fitness function and mutation operator
+// Each generation lambda offspring are created from the parent, and the best offspring replaces the parent,
+// even if it is worse. The state therefore holds the current parent, not the best solution found so far
+pub struct OneCommaLambdaEA<S: SearchSpace, F: FitnessFunction<S>, M: Mutation<S>> {
+    pub state: SimulationState<S>,
+    fitness_function: EvaluationCounter<F>,
+    mutator: M,
+    population: OffspringPopulation,
+    lambda: f64,
+    max_lambda: f64,
+    best_fitness: f64,
+}
+
+impl<S, F, M> OneCommaLambdaEA<S, F, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    M: Mutation<S>,
+{
+    // For the self-adjusting variant, lambda starts at 1 and is capped at the problem size
+    // to bound the cost of a single generation
+    pub fn new<R: MyRng>(
+        size: usize,
+        population: OffspringPopulation,
+        mutator: M,
+        fitness_function: F,
+        rng: &mut R,
+    ) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        let lambda = match population {
+            OffspringPopulation::Static(lambda) => {
+                assert!(lambda > 0, "lambda must be positive");
+                lambda as f64
+            }
+            OffspringPopulation::SelfAdjusting { .. } => 1.0,
+        };
+        let current_solution = S::new_random(size, rng);
+        let current_fitness = fitness_function.evaluate(&current_solution);
+        OneCommaLambdaEA {
+            state: SimulationState {
+                iteration: 0,
+                current_solution,
+                current_fitness,
+            },
+            fitness_function,
+            mutator,
+            population,
+            lambda,
+            max_lambda: (size as f64).max(1.0),
+            best_fitness: current_fitness,
+        }
+    }
+
+    // Number of offspring created in the next generation
+    pub fn offspring_population_size(&self) -> usize {
+        (self.lambda.round() as usize).max(1)
+    }
+
+    // Best fitness seen so far, which can differ from the fitness of the current parent
+    pub fn best_fitness(&self) -> f64 {
+        self.best_fitness
+    }
+}
+
+impl<S, F, M> AlgorithmCore for OneCommaLambdaEA<S, F, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    M: Mutation<S>,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        let mut best_offspring = self.mutator.apply(&self.state.current_solution, rng);
+        let mut best_offspring_fitness = self.fitness_function.evaluate(&best_offspring);
+
+        for _ in 1..self.offspring_population_size() {
+            let offspring = self.mutator.apply(&self.state.current_solution, rng);
+            let fitness = self.fitness_function.evaluate(&offspring);
+            if self
+                .fitness_function
+                .compare(fitness, best_offspring_fitness)
+                == std::cmp::Ordering::Greater
+            {
+                best_offspring = offspring;
+                best_offspring_fitness = fitness;
+            }
+        }
+
+        let success = self
+            .fitness_function
+            .compare(best_offspring_fitness, self.state.current_fitness)
+            == std::cmp::Ordering::Greater;
+
+        // Comma selection: the parent is always replaced
+        self.state.current_solution = best_offspring;
+        self.state.current_fitness = best_offspring_fitness;
+        self.state.iteration += 1;
+
+        if self
+            .fitness_function
+            .compare(best_offspring_fitness, self.best_fitness)
+            == std::cmp::Ordering::Greater
+        {
+            self.best_fitness = best_offspring_fitness;
+        }
+
+        if let OffspringPopulation::SelfAdjusting {
+            update_factor,
+            success_ratio,
+        } = self.population
+        {
+            self.lambda = if success {
+                (self.lambda / update_factor).max(1.0)
+            } else {
+                (self.lambda * update_factor.powf(1.0 / success_ratio)).min(self.max_lambda)
+            };
+        }
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string(),
+                "best_fitness": self.best_fitness,
+                "lambda": self.offspring_population_size(),
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        json!({
+            "state": self.state,
+            "lambda": self.lambda,
+            "best_fitness": self.best_fitness,
+            "evaluations": self.fitness_function.evaluations(),
+        })
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        let lambda = checkpoint_field(checkpoint, "lambda")?;
+        let best_fitness = checkpoint_field(checkpoint, "best_fitness")?;
+        self.state = state;
+        self.lambda = lambda;
+        self.best_fitness = best_fitness;
+        self.fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::assert_checkpoint_roundtrip;
+    use crate::mutation::Bitflip;
+    use crate::{
+        fitness::one_max::OneMax, mutation::SingleBitflip, rng::MockRng, search_space::Bitstring,
+    };
+
+    #[test]
+    fn test_comma_selection() {
+        // Parent 110, where both offspring 010 and 100 are worse
+        let mut rng = MockRng::default()
+            .with_bool(vec![true, true, false])
+            .with_range(vec![0, 1]);
+        let mut ea = OneCommaLambdaEA::new(
+            3,
+            OffspringPopulation::Static(2),
+            SingleBitflip,
+            OneMax,
+            &mut rng,
+        );
+        ea.iterate(&mut rng);
+        assert_eq!(
+            ea.state.current_solution,
+            Bitstring::from_bitstring("010").unwrap()
+        );
+        assert_eq!(ea.current_fitness(), 1.0);
+        assert_eq!(ea.best_fitness(), 2.0);
+        assert_eq!(ea.evaluations(), 3);
+    }
+
+    #[test]
+    fn test_self_adjusting_lambda() {
+        // Parent 111, such that the first generation fails, the second using three offspring
+        // of parent 011 succeeds, and the third from the optimum 111 fails again
+        let mut rng = MockRng::default()
+            .with_bool(vec![true, true, true])
+            .with_range(vec![0, 1, 0, 2, 0, 1, 2]);
+        let mut ea = OneCommaLambdaEA::new(
+            3,
+            OffspringPopulation::new_one_fifth(),
+            SingleBitflip,
+            OneMax,
+            &mut rng,
+        );
+        assert_eq!(ea.offspring_population_size(), 1);
+
+        // Failure: lambda <- lambda * F^(1/s)
+        ea.iterate(&mut rng);
+        assert_eq!(ea.current_fitness(), 2.0);
+        assert!((ea.lambda - 1.5_f64.powf(0.25)).abs() < 1e-9);
+
+        // Success: lambda <- lambda / F
+        ea.lambda = 3.0;
+        ea.iterate(&mut rng);
+        assert_eq!(ea.current_fitness(), 3.0);
+        assert_eq!(ea.lambda, 2.0);
+
+        // Lambda is capped at the problem size
+        ea.lambda = 2.9;
+        ea.iterate(&mut rng);
+        assert_eq!(ea.lambda, 3.0);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        assert_checkpoint_roundtrip(|rng| {
+            OneCommaLambdaEA::new(
+                30,
+                OffspringPopulation::new_one_fifth(),
+                Bitflip,
+                OneMax,
+                rng,
+            )
+        });
+    }
+}
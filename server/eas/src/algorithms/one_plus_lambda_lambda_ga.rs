@@ -0,0 +1,289 @@
+use super::{
+    checkpoint_field, checkpoint_state, one_comma_lambda_ea::OffspringPopulation,
+    optional_checkpoint_field, solution_status, AlgorithmCore, CheckpointError, SimulationState,
+};
+use crate::{
+    fitness::{EvaluationCounter, FitnessFunction},
+    rng::MyRng,
+    search_space::{Bitstring, SearchSpace},
+};
+use serde_json::json;
+
+// Implementation of the (1+(lambda,lambda)) GA on bitstrings (Doerr, Doerr & Ebel, 2015).
+// Each generation consists of a mutation phase, creating lambda mutants of the parent that each flip
+// the same number l ~ Bin(n, lambda/n) of random bits, followed by a crossover phase, creating lambda offspring
+// by biased uniform crossover of the parent and the best mutant, taking each bit from the mutant with probability 1/lambda.
+// The best crossover offspring replaces the parent if it is at least as good.
+// A generation costs up to 2 lambda evaluations.
+// Offspring identical to the parent are not evaluated, as their fitness is known
+pub struct OnePlusLambdaLambdaGA<F: FitnessFunction<Bitstring>> {
+    pub state: SimulationState<Bitstring>,
+    fitness_function: EvaluationCounter<F>,
+    population: OffspringPopulation,
+    lambda: f64,
+    max_lambda: f64,
+}
+
+impl<F: FitnessFunction<Bitstring>> OnePlusLambdaLambdaGA<F> {
+    // Both a static lambda and the self-adjusting lambda of the (1,lambda) EA are supported,
+    // where the self-adjusting variant uses a success to be a strict improvement of the parent.
+    // The self-adjusting lambda starts at 1 and is capped at the problem size
+    pub fn new<R: MyRng>(
+        size: usize,
+        population: OffspringPopulation,
+        fitness_function: F,
+        rng: &mut R,
+    ) -> Self {
+        let lambda = match population {
+            OffspringPopulation::Static(lambda) => {
+                assert!(lambda > 0, "lambda must be positive");
+                lambda as f64
+            }
+            OffspringPopulation::SelfAdjusting { .. } => 1.0,
+        };
+        let fitness_function = EvaluationCounter::new(fitness_function);
+        let current_solution = Bitstring::new_random(size, rng);
+        let current_fitness = fitness_function.evaluate(&current_solution);
+        OnePlusLambdaLambdaGA {
+            state: SimulationState {
+                iteration: 0,
+                current_solution,
+                current_fitness,
+            },
+            fitness_function,
+            population,
+            lambda,
+            max_lambda: (size as f64).max(1.0),
+        }
+    }
+
+    // Number of mutants and crossover offspring created in the next generation
+    pub fn offspring_population_size(&self) -> usize {
+        (self.lambda.round() as usize).max(1)
+    }
+
+    // Sample l ~ Bin(n, p) as the number of successes of n trials,
+    // using the geometric distribution to draw the distance between successes
+    fn sample_flips<R: MyRng>(&self, p: f64, rng: &mut R) -> usize {
+        let n = self.state.current_solution.size();
+        let mut flips = 0;
+        let mut i = rng.sample_geometric(p) as usize;
+        while i < n {
+            flips += 1;
+            i += 1 + rng.sample_geometric(p) as usize;
+        }
+        flips
+    }
+
+    // Sample l distinct positions of the bitstring
+    fn sample_positions<R: MyRng>(&self, l: usize, rng: &mut R) -> Vec<usize> {
+        let n = self.state.current_solution.size();
+        let mut positions = Vec::with_capacity(l);
+        while positions.len() < l {
+            let i = rng.random_range(0..n);
+            if !positions.contains(&i) {
+                positions.push(i);
+            }
+        }
+        positions
+    }
+
+    // Evaluate an offspring, unless it is identical to the parent
+    fn evaluate(&self, offspring: &Bitstring, identical: bool) -> f64 {
+        if identical {
+            return self.state.current_fitness;
+        }
+        self.fitness_function.evaluate(offspring)
+    }
+
+    fn is_better(&self, a: f64, b: f64) -> bool {
+        self.fitness_function.compare(a, b) == std::cmp::Ordering::Greater
+    }
+}
+
+impl<F: FitnessFunction<Bitstring>> AlgorithmCore for OnePlusLambdaLambdaGA<F> {
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        let n = self.state.current_solution.size();
+        let lambda = self.offspring_population_size();
+        let p = (self.lambda / n as f64).min(1.0);
+        let c = 1.0 / self.lambda;
+
+        // Mutation phase, keeping the flipped positions of the best mutant for the crossover phase
+        let l = self.sample_flips(p, rng);
+        let mut best_mutant: Option<(Vec<usize>, f64)> = None;
+        for _ in 0..lambda {
+            let positions = self.sample_positions(l, rng);
+            let mut mutant = self.state.current_solution.clone();
+            for &i in &positions {
+                mutant.flip(i);
+            }
+            let fitness = self.evaluate(&mutant, l == 0);
+            if best_mutant
+                .as_ref()
+                .is_none_or(|&(_, best)| self.is_better(fitness, best))
+            {
+                best_mutant = Some((positions, fitness));
+            }
+        }
+        let (positions, _) = best_mutant.expect("lambda is positive");
+
+        // Crossover phase, where only the bits in which the parent and the best mutant differ can change
+        let mut best_offspring: Option<(Bitstring, f64)> = None;
+        for _ in 0..lambda {
+            let mut offspring = self.state.current_solution.clone();
+            let mut identical = true;
+            for &i in &positions {
+                if rng.random_bool(c) {
+                    offspring.flip(i);
+                    identical = false;
+                }
+            }
+            let fitness = self.evaluate(&offspring, identical);
+            if best_offspring
+                .as_ref()
+                .is_none_or(|&(_, best)| self.is_better(fitness, best))
+            {
+                best_offspring = Some((offspring, fitness));
+            }
+        }
+        let (offspring, fitness) = best_offspring.expect("lambda is positive");
+        self.state.iteration += 1;
+
+        let success = self.is_better(fitness, self.state.current_fitness);
+        if !self.is_better(self.state.current_fitness, fitness) {
+            self.state.current_solution = offspring;
+            self.state.current_fitness = fitness;
+        }
+
+        if let OffspringPopulation::SelfAdjusting {
+            update_factor,
+            success_ratio,
+        } = self.population
+        {
+            self.lambda = if success {
+                (self.lambda / update_factor).max(1.0)
+            } else {
+                (self.lambda * update_factor.powf(1.0 / success_ratio)).min(self.max_lambda)
+            };
+        }
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string(),
+                "lambda": self.offspring_population_size(),
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        json!({
+            "state": self.state,
+            "lambda": self.lambda,
+            "evaluations": self.fitness_function.evaluations(),
+        })
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let lambda = checkpoint_field(checkpoint, "lambda")?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        self.state = state;
+        self.lambda = lambda;
+        self.fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::assert_checkpoint_roundtrip;
+    use crate::fitness::one_max::OneMax;
+    use crate::rng::MockRng;
+
+    fn self_adjusting() -> OffspringPopulation {
+        OffspringPopulation::SelfAdjusting {
+            update_factor: 1.5,
+            success_ratio: 4.0,
+        }
+    }
+
+    #[test]
+    fn test_failure_increases_lambda() {
+        // Initial solution 0000 with lambda = 1
+        let mut ga = OnePlusLambdaLambdaGA::new(
+            4,
+            self_adjusting(),
+            OneMax,
+            &mut MockRng::default().with_bool(vec![false; 4]),
+        );
+        assert_eq!(ga.evaluations(), 1);
+
+        // The mutant flips bit 2, but the crossover offspring takes no bit from it,
+        // such that the offspring is identical to the parent and not evaluated
+        let mut rng = MockRng::new_geometric(vec![1, 10])
+            .with_range(vec![2])
+            .with_bool(vec![false]);
+        ga.iterate(&mut rng);
+        assert_eq!(ga.state.current_solution.to_string(), "0000");
+        assert_eq!(ga.current_fitness(), 0.0);
+        assert_eq!(ga.evaluations(), 2);
+        assert_eq!(ga.lambda, 1.5f64.powf(0.25));
+    }
+
+    #[test]
+    fn test_success_decreases_lambda() {
+        let mut ga = OnePlusLambdaLambdaGA::new(
+            4,
+            self_adjusting(),
+            OneMax,
+            &mut MockRng::default().with_bool(vec![false; 4]),
+        );
+        ga.lambda = 2.0;
+
+        // Two mutants flipping l = 2 bits, where the first of the equally good mutants is kept.
+        // The first crossover offspring is identical to the parent and not evaluated,
+        // while the second takes both bits from the mutant and improves the parent
+        let mut rng = MockRng::new_geometric(vec![0, 0, 10])
+            .with_range(vec![0, 1, 2, 3])
+            .with_bool(vec![false, false, true, true]);
+        ga.iterate(&mut rng);
+        assert_eq!(ga.state.current_solution.to_string(), "1100");
+        assert_eq!(ga.current_fitness(), 2.0);
+        assert_eq!(ga.evaluations(), 4);
+        assert_eq!(ga.lambda, 2.0 / 1.5);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        assert_checkpoint_roundtrip(|rng| {
+            OnePlusLambdaLambdaGA::new(30, OffspringPopulation::new_one_fifth(), OneMax, rng)
+        });
+        assert_checkpoint_roundtrip(|rng| {
+            OnePlusLambdaLambdaGA::new(30, OffspringPopulation::Static(4), OneMax, rng)
+        });
+    }
+}
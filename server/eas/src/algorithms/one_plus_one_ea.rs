@@ -1,18 +1,21 @@
-use super::{AlgorithmCore, SimulationState};
+use super::{
+    checkpoint_state, optional_checkpoint_field, solution_status, AlgorithmCore, CheckpointError,
+    SimulationState, Step, REEVALUATION_INTERVAL,
+};
 use crate::{
-    fitness::FitnessFunction,
-    mutation::Mutation,
+    fitness::{DeltaEvaluation, EvaluationCounter, FitnessFunction},
+    mutation::{Move, MoveMutation, Mutation},
     rng::MyRng,
     search_space::{Bitstring, Permutation, SearchSpace},
 };
 use serde_json::json;
 
 // Implementation of (1+1) EA independent of search space, fitness function and mutation operator
-// Implements AlgorithmCore separately for Bitstring and Permutation,
+// Implements the step separately for Bitstring and Permutation,
 // as the usage of the mutation operator is different between the two search spaces
 pub struct OnePlusOneEA<S: SearchSpace, F: FitnessFunction<S>, M: Mutation<S>> {
     pub state: SimulationState<S>,
-    fitness_function: F,
+    fitness_function: EvaluationCounter<F>,
     mutator: M,
 }
 
@@ -23,6 +26,7 @@ where
     M: Mutation<S>,
 {
     pub fn new<R: MyRng>(size: usize, mutator: M, fitness_function: F, rng: &mut R) -> Self {
+        let fitness_function = EvaluationCounter::new(fitness_function);
         let current_solution = S::new_random(size, rng);
         let current_fitness = fitness_function.evaluate(&current_solution);
         OnePlusOneEA {
@@ -37,14 +41,70 @@ where
     }
 }
 
+impl<S, F, M> AlgorithmCore for OnePlusOneEA<S, F, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    M: Mutation<S>,
+    Self: Step,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        self.step(rng)
+    }
+
+    fn current_fitness(&self) -> f64 {
+        self.state.current_fitness
+    }
+
+    fn iterations(&self) -> u64 {
+        self.state.iteration
+    }
+
+    fn evaluations(&self) -> u64 {
+        self.fitness_function.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn status_json(&self) -> serde_json::Value {
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness_function.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string()
+            }),
+            &self.fitness_function,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
+        json!({
+            "state": self.state,
+            "evaluations": self.fitness_function.evaluations(),
+        })
+    }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        self.state = state;
+        self.fitness_function.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
 // Implementation of (1+1) EA for a given fitness function and mutation operating on bitstrings.
 // Here the mutation is applied once each iteration.
-impl<F, M> AlgorithmCore for OnePlusOneEA<Bitstring, F, M>
+impl<F, M> Step for OnePlusOneEA<Bitstring, F, M>
 where
     F: FitnessFunction<Bitstring>,
     M: Mutation<Bitstring>,
 {
-    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+    fn step<R: MyRng>(&mut self, rng: &mut R) {
         let offspring = self.mutator.apply(&self.state.current_solution, rng);
 
         let new_fitness = self.fitness_function.evaluate(&offspring);
@@ -60,39 +120,33 @@ where
             self.state.current_solution = offspring;
         }
     }
-
-    fn current_fitness(&self) -> f64 {
-        self.state.current_fitness
-    }
-
-    fn iterations(&self) -> u64 {
-        self.state.iteration
-    }
-
-    fn status_json(&self) -> serde_json::Value {
-        json!({
-            "iterations": self.state.iteration,
-            "current_fitness": self.state.current_fitness,
-            "current_solution": self.state.current_solution.to_string()
-        })
-    }
 }
 
 // Implementation of (1+1) EA for a given fitness function and mutation operating on permutations.
 // Here the mutation is applied (x+1) times each iteration where x ~ Poisson(1).
-impl<F, M> AlgorithmCore for OnePlusOneEA<Permutation, F, M>
+// The moves are applied in place while accumulating their fitness change,
+// and undone in reverse order if the offspring is not accepted, counting one evaluation per offspring.
+// As the accumulated fitness drifts from the exact fitness through rounding errors,
+// the current solution is periodically re-evaluated
+impl<F, M> Step for OnePlusOneEA<Permutation, F, M>
 where
-    F: FitnessFunction<Permutation>,
-    M: Mutation<Permutation>,
+    F: DeltaEvaluation<Permutation, M::Move>,
+    M: MoveMutation<Permutation>,
 {
-    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+    fn step<R: MyRng>(&mut self, rng: &mut R) {
         let num_mutations = rng.sample_poisson();
-        let mut offspring = self.mutator.apply(&self.state.current_solution, rng);
-
-        for _ in 0..=num_mutations {
-            offspring = self.mutator.apply(&offspring, rng);
+        let mut moves = Vec::with_capacity(num_mutations as usize + 2);
+        let mut new_fitness = self.state.current_fitness;
+
+        for _ in 0..(num_mutations + 2) {
+            let m = self.mutator.sample_move(&self.state.current_solution, rng);
+            new_fitness += self
+                .fitness_function
+                .delta(&self.state.current_solution, &m);
+            m.apply(&mut self.state.current_solution);
+            moves.push(m);
         }
-        let new_fitness = self.fitness_function.evaluate(&offspring);
+        self.fitness_function.count();
         self.state.iteration += 1;
 
         let fitness_order = self
@@ -101,23 +155,77 @@ where
 
         if fitness_order == std::cmp::Ordering::Greater {
             self.state.current_fitness = new_fitness;
-            self.state.current_solution = offspring;
+        } else {
+            for m in moves.iter().rev() {
+                m.undo(&mut self.state.current_solution);
+            }
+        }
+
+        if self.state.iteration.is_multiple_of(REEVALUATION_INTERVAL) {
+            self.state.current_fitness =
+                self.fitness_function.evaluate(&self.state.current_solution);
         }
     }
+}
 
-    fn current_fitness(&self) -> f64 {
-        self.state.current_fitness
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{assert_checkpoint_roundtrip, irrational_tsp};
+    use crate::fitness::{max_sat::MaxSat, one_max::OneMax};
+    use crate::mutation::Bitflip;
+    use crate::mutation::TwoOpt;
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn test_reevaluation() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        let mut ea = OnePlusOneEA::new(30, TwoOpt, irrational_tsp(30), &mut rng);
+        for _ in 0..REEVALUATION_INTERVAL {
+            ea.iterate(&mut rng);
+        }
+        let exact = irrational_tsp(30).evaluate(&ea.state.current_solution);
+        assert_eq!(ea.current_fitness(), exact);
     }
 
-    fn iterations(&self) -> u64 {
-        self.state.iteration
+    #[test]
+    fn test_evaluations_per_offspring() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        let mut ea = OnePlusOneEA::new(30, TwoOpt, irrational_tsp(30), &mut rng);
+        for _ in 0..100 {
+            ea.iterate(&mut rng);
+        }
+        assert_eq!(ea.evaluations(), ea.iterations() + 1);
     }
 
-    fn status_json(&self) -> serde_json::Value {
-        json!({
-            "iterations": self.state.iteration,
-            "current_fitness": self.state.current_fitness,
-            "current_solution": self.state.current_solution.to_string()
-        })
+    #[test]
+    fn test_solution_properties() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        let instance = "p wcnf 2 2\n10 1 2 0\n3 -1 0\n";
+        let formula = MaxSat::from_dimacs(instance).unwrap();
+        let ea = OnePlusOneEA::new(2, Bitflip, formula, &mut rng);
+        let formula = MaxSat::from_dimacs(instance).unwrap();
+        let satisfied = formula.satisfied_clauses(&ea.state.current_solution);
+        assert_eq!(ea.status_json()["satisfied_clauses"], satisfied);
+        assert_eq!(ea.evaluations(), 1);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        assert_checkpoint_roundtrip(|rng| OnePlusOneEA::new(30, Bitflip, OneMax, rng));
+        assert_checkpoint_roundtrip(|rng| OnePlusOneEA::new(30, TwoOpt, irrational_tsp(30), rng));
+    }
+
+    #[test]
+    fn test_restore_without_evaluations() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        let mut ea = OnePlusOneEA::new(30, Bitflip, OneMax, &mut rng);
+        ea.iterate(&mut rng);
+        let mut checkpoint = ea.checkpoint();
+        checkpoint.as_object_mut().unwrap().remove("evaluations");
+        ea.restore(&checkpoint).unwrap();
+        assert_eq!(ea.iterations(), 1);
+        assert_eq!(ea.evaluations(), 0);
     }
 }
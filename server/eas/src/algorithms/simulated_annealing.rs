@@ -1,12 +1,22 @@
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::{fitness::FitnessFunction, mutation::Mutation, rng::MyRng, search_space::SearchSpace};
+use crate::{
+    fitness::{DeltaEvaluation, EvaluationCounter, FitnessFunction},
+    mutation::{Move, MoveMutation, Mutation},
+    rng::MyRng,
+    search_space::{Bitstring, Permutation, SearchSpace},
+};
 
-use super::{AlgorithmCore, SimulationState};
+use super::{
+    checkpoint_field, checkpoint_state, optional_checkpoint_field, solution_status, AlgorithmCore,
+    CheckpointError, SimulationState, Step, REEVALUATION_INTERVAL,
+};
 
 // Enum representing the cooling schemes used for simulated annealing
 // Represents both a cooling scheme with static temperature,
 // and the exponential cooling scheme T(t) = T_0 * alpha^t
+#[derive(Serialize, Deserialize)]
 pub enum CoolingSchedule {
     Static(f64),
     Exponential(f64, f64),
@@ -66,7 +76,8 @@ impl CoolingSchedule {
 }
 
 // Implementatiojn of Simulated Annealing. Implementating is generic and is shared
-// for all search spaces, fitness function and mutation operators
+// for all search spaces, fitness function and mutation operators,
+// with iteration implemented separately for bitstrings and permutations
 pub struct SimulatedAnnealing<S, F, M>
 where
     S: SearchSpace,
@@ -74,7 +85,7 @@ where
     M: Mutation<S>,
 {
     pub state: SimulationState<S>,
-    fitness: F,
+    fitness: EvaluationCounter<F>,
     mutator: M,
     cooling: CoolingSchedule,
 }
@@ -92,6 +103,7 @@ where
         cooling: CoolingSchedule,
         rng: &mut R,
     ) -> Self {
+        let fitness = EvaluationCounter::new(fitness);
         let current_solution = S::new_random(size, rng);
         let current_fitness = fitness.evaluate(&current_solution);
         SimulatedAnnealing {
@@ -109,39 +121,36 @@ where
     pub fn current_temperature(&self) -> f64 {
         self.cooling.temperature(self.state.iteration)
     }
-}
-
-impl<S, F, M> AlgorithmCore for SimulatedAnnealing<S, F, M>
-where
-    S: SearchSpace,
-    F: FitnessFunction<S>,
-    M: Mutation<S>,
-{
-    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
-        let neighbor = self.mutator.apply(&self.state.current_solution, rng);
-        let neighbor_fitness = self.fitness.evaluate(&neighbor);
 
+    // Decide whether to move to a neighbor with the given fitness.
+    // Improvements are always accepted, while worse neighbors are accepted
+    // with probability e^(-|difference| / T)
+    fn accept<R: MyRng>(&self, neighbor_fitness: f64, rng: &mut R) -> bool {
         let fitness_cmp = self
             .fitness
             .compare(neighbor_fitness, self.state.current_fitness);
 
         if fitness_cmp == std::cmp::Ordering::Greater {
-            self.state.current_solution = neighbor;
-            self.state.current_fitness = neighbor_fitness
+            true
         } else {
             let difference = (neighbor_fitness - self.state.current_fitness).abs();
             let temp = self.cooling.temperature(self.state.iteration);
             let accept_probability = (-difference / temp).exp();
             // Account for NaN when temp gets too close to 0
-            if accept_probability.is_finite() {
-                let accept = rng.random_bool(accept_probability);
-                if accept {
-                    self.state.current_solution = neighbor;
-                    self.state.current_fitness = neighbor_fitness
-                }
-            }
+            accept_probability.is_finite() && rng.random_bool(accept_probability)
         }
-        self.state.iteration += 1;
+    }
+}
+
+impl<S, F, M> AlgorithmCore for SimulatedAnnealing<S, F, M>
+where
+    S: SearchSpace,
+    F: FitnessFunction<S>,
+    M: Mutation<S>,
+    Self: Step,
+{
+    fn iterate<R: MyRng>(&mut self, rng: &mut R) {
+        self.step(rng)
     }
 
     fn current_fitness(&self) -> f64 {
@@ -152,12 +161,120 @@ where
         self.state.iteration
     }
 
+    fn evaluations(&self) -> u64 {
+        self.fitness.evaluations()
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness.compare(a, b)
+    }
+
     fn status_json(&self) -> serde_json::Value {
+        solution_status(
+            json!({
+                "iterations": self.state.iteration,
+                "evaluations": self.fitness.evaluations(),
+                "current_fitness": self.state.current_fitness,
+                "current_solution": self.state.current_solution.to_string(),
+                "temperature": format!("{:.5}", self.current_temperature()),
+            }),
+            &self.fitness,
+            &self.state.current_solution,
+        )
+    }
+
+    fn checkpoint(&self) -> serde_json::Value {
         json!({
-            "iterations": self.state.iteration,
-            "current_fitness": self.state.current_fitness,
-            "current_solution": self.state.current_solution.to_string(),
-            "temperature": format!("{:.5}", self.current_temperature()),
+            "state": self.state,
+            "cooling": self.cooling,
+            "evaluations": self.fitness.evaluations(),
         })
     }
+
+    fn restore(&mut self, checkpoint: &serde_json::Value) -> Result<(), CheckpointError> {
+        let state = checkpoint_state(&self.state, checkpoint)?;
+        let cooling = checkpoint_field(checkpoint, "cooling")?;
+        let evaluations = optional_checkpoint_field(checkpoint, "evaluations")?;
+        self.state = state;
+        self.cooling = cooling;
+        self.fitness.set_evaluations(evaluations);
+        Ok(())
+    }
+}
+
+// Implementation of Simulated Annealing on bitstrings, evaluating each neighbor fully
+impl<F, M> Step for SimulatedAnnealing<Bitstring, F, M>
+where
+    F: FitnessFunction<Bitstring>,
+    M: Mutation<Bitstring>,
+{
+    fn step<R: MyRng>(&mut self, rng: &mut R) {
+        let neighbor = self.mutator.apply(&self.state.current_solution, rng);
+        let neighbor_fitness = self.fitness.evaluate(&neighbor);
+
+        if self.accept(neighbor_fitness, rng) {
+            self.state.current_solution = neighbor;
+            self.state.current_fitness = neighbor_fitness
+        }
+        self.state.iteration += 1;
+    }
+}
+
+// Implementation of Simulated Annealing on permutations, using delta evaluation of the sampled move
+// such that the move is only applied to the current solution if it is accepted
+impl<F, M> Step for SimulatedAnnealing<Permutation, F, M>
+where
+    F: DeltaEvaluation<Permutation, M::Move>,
+    M: MoveMutation<Permutation>,
+{
+    fn step<R: MyRng>(&mut self, rng: &mut R) {
+        let m = self.mutator.sample_move(&self.state.current_solution, rng);
+        let neighbor_fitness =
+            self.state.current_fitness + self.fitness.delta(&self.state.current_solution, &m);
+        self.fitness.count();
+
+        if self.accept(neighbor_fitness, rng) {
+            m.apply(&mut self.state.current_solution);
+            self.state.current_fitness = neighbor_fitness
+        }
+        self.state.iteration += 1;
+
+        if self.state.iteration.is_multiple_of(REEVALUATION_INTERVAL) {
+            self.state.current_fitness = self.fitness.evaluate(&self.state.current_solution);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{assert_checkpoint_roundtrip, irrational_tsp};
+    use crate::mutation::TwoOpt;
+    use crate::{fitness::one_max::OneMax, mutation::Bitflip};
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn test_reevaluation() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        let cooling = CoolingSchedule::new_static(10.0);
+        let mut sa = SimulatedAnnealing::new(30, TwoOpt, irrational_tsp(30), cooling, &mut rng);
+        for _ in 0..REEVALUATION_INTERVAL {
+            sa.iterate(&mut rng);
+        }
+        let exact = irrational_tsp(30).evaluate(&sa.state.current_solution);
+        assert_eq!(sa.current_fitness(), exact);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        assert_checkpoint_roundtrip(|rng| {
+            let cooling = CoolingSchedule::new_default_bitstring(30, 1.0);
+            SimulatedAnnealing::new(30, Bitflip, OneMax, cooling, rng)
+        });
+        assert_checkpoint_roundtrip(|rng| {
+            let cooling = CoolingSchedule::new_default_tsp(30, 20.0);
+            SimulatedAnnealing::new(30, TwoOpt, irrational_tsp(30), cooling, rng)
+        });
+    }
 }
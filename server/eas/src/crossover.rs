@@ -0,0 +1,390 @@
+use super::rng::MyRng;
+use super::search_space::{Bitstring, Permutation, SearchSpace};
+
+// Crossover trait used by the genetic algorithm,
+// as the recombination operator creating one offspring from two parents on a given search space
+pub trait Crossover<S: SearchSpace> {
+    fn apply<R: MyRng>(&self, a: &S, b: &S, rng: &mut R) -> S;
+}
+
+// Uniform crossover, taking each bit from either parent with probability 1/2
+pub struct UniformCrossover;
+
+impl Crossover<Bitstring> for UniformCrossover {
+    fn apply<R: MyRng>(&self, a: &Bitstring, b: &Bitstring, rng: &mut R) -> Bitstring {
+        let bits = a
+            .iter()
+            .zip(b.iter())
+            .map(|(x, y)| if rng.random_ratio(1, 2) { y } else { x })
+            .collect::<Vec<_>>();
+        Bitstring::new(bits)
+    }
+}
+
+// One-point crossover, taking the bits before a random cut point from the first parent
+// and the remaining bits from the second parent.
+// Bitstrings with less than two bits have no cut point, so the first parent is copied
+pub struct OnePointCrossover;
+
+impl Crossover<Bitstring> for OnePointCrossover {
+    fn apply<R: MyRng>(&self, a: &Bitstring, b: &Bitstring, rng: &mut R) -> Bitstring {
+        if a.size() < 2 {
+            return a.clone();
+        }
+        let cut = rng.random_range(1..a.size());
+        let bits = (0..a.size())
+            .map(|i| if i < cut { a.get(i) } else { b.get(i) })
+            .collect::<Vec<_>>();
+        Bitstring::new(bits)
+    }
+}
+
+// k-point crossover, choosing k distinct cut points and alternating between the parents at each cut.
+// At most n - 1 cut points exist, so the first parent is copied for bitstrings with less than two bits
+pub struct KPointCrossover(pub usize);
+
+impl Crossover<Bitstring> for KPointCrossover {
+    fn apply<R: MyRng>(&self, a: &Bitstring, b: &Bitstring, rng: &mut R) -> Bitstring {
+        let n = a.size();
+        if n < 2 {
+            return a.clone();
+        }
+        let k = self.0.min(n - 1);
+        let mut cuts = Vec::with_capacity(k);
+        while cuts.len() < k {
+            let cut = rng.random_range(1..n);
+            if !cuts.contains(&cut) {
+                cuts.push(cut);
+            }
+        }
+        cuts.sort_unstable();
+
+        let mut bits = Vec::with_capacity(n);
+        let mut from_first = true;
+        let mut start = 0;
+        for end in cuts.into_iter().chain(std::iter::once(n)) {
+            let parent = if from_first { a } else { b };
+            bits.extend((start..end).map(|i| parent.get(i)));
+            from_first = !from_first;
+            start = end;
+        }
+        Bitstring::new(bits)
+    }
+}
+
+// Choose two distinct random indices of a permutation, returned in increasing order.
+// The permutation must have at least two elements
+fn random_segment<R: MyRng>(n: usize, rng: &mut R) -> (usize, usize) {
+    assert!(n >= 2, "a segment requires at least two elements");
+    let a = rng.random_range(0..n);
+    let mut b = a;
+    while b == a {
+        b = rng.random_range(0..n);
+    }
+    if a > b {
+        (b, a)
+    } else {
+        (a, b)
+    }
+}
+
+// Order crossover (OX), copying a random segment from the first parent
+// and filling the remaining positions, starting after the segment,
+// with the missing elements in the order they appear in the second parent.
+// Permutations with less than two elements are copied from the first parent
+pub struct OrderCrossover;
+
+impl Crossover<Permutation> for OrderCrossover {
+    fn apply<R: MyRng>(&self, a: &Permutation, b: &Permutation, rng: &mut R) -> Permutation {
+        let (p1, p2) = (a.permutation(), b.permutation());
+        let n = p1.len();
+        if n < 2 {
+            return a.clone();
+        }
+        let (start, end) = random_segment(n, rng);
+
+        let mut result = vec![0; n];
+        let mut used = vec![false; n];
+        for i in start..=end {
+            result[i] = p1[i];
+            used[p1[i]] = true;
+        }
+
+        let mut position = (end + 1) % n;
+        for offset in 1..=n {
+            let v = p2[(end + offset) % n];
+            if used[v] {
+                continue;
+            }
+            result[position] = v;
+            position = (position + 1) % n;
+        }
+        Permutation::new(result)
+    }
+}
+
+// Partially mapped crossover (PMX), copying a random segment from the first parent
+// and taking the remaining elements from the second parent,
+// following the mapping defined by the segment when an element is already used.
+// Permutations with less than two elements are copied from the first parent
+pub struct PartiallyMappedCrossover;
+
+impl Crossover<Permutation> for PartiallyMappedCrossover {
+    fn apply<R: MyRng>(&self, a: &Permutation, b: &Permutation, rng: &mut R) -> Permutation {
+        let (p1, p2) = (a.permutation(), b.permutation());
+        let n = p1.len();
+        if n < 2 {
+            return a.clone();
+        }
+        let (start, end) = random_segment(n, rng);
+
+        let mut position_in_p1 = vec![0; n];
+        for (i, &v) in p1.iter().enumerate() {
+            position_in_p1[v] = i;
+        }
+        let in_segment = |v: usize| (start..=end).contains(&position_in_p1[v]);
+
+        let mut result = p1.clone();
+        for i in (0..start).chain((end + 1)..n) {
+            let mut v = p2[i];
+            while in_segment(v) {
+                v = p2[position_in_p1[v]];
+            }
+            result[i] = v;
+        }
+        Permutation::new(result)
+    }
+}
+
+// Cycle crossover (CX), splitting the positions into cycles
+// and alternately taking the elements of each cycle from the first and second parent
+pub struct CycleCrossover;
+
+impl Crossover<Permutation> for CycleCrossover {
+    fn apply<R: MyRng>(&self, a: &Permutation, b: &Permutation, _: &mut R) -> Permutation {
+        let (p1, p2) = (a.permutation(), b.permutation());
+        let n = p1.len();
+
+        let mut position_in_p1 = vec![0; n];
+        for (i, &v) in p1.iter().enumerate() {
+            position_in_p1[v] = i;
+        }
+
+        let mut result = vec![0; n];
+        let mut assigned = vec![false; n];
+        let mut from_first = true;
+        for cycle_start in 0..n {
+            if assigned[cycle_start] {
+                continue;
+            }
+            let parent = if from_first { p1 } else { p2 };
+            let mut i = cycle_start;
+            while !assigned[i] {
+                result[i] = parent[i];
+                assigned[i] = true;
+                i = position_in_p1[p2[i]];
+            }
+            from_first = !from_first;
+        }
+        Permutation::new(result)
+    }
+}
+
+// Edge recombination crossover (ERX), building the offspring from the union of edges of both parents.
+// Starting from the first element of the first parent, the next element is the neighbor with the fewest
+// remaining neighbors (ties broken randomly), or a random unvisited element if no neighbors remain
+pub struct EdgeRecombination;
+
+impl Crossover<Permutation> for EdgeRecombination {
+    fn apply<R: MyRng>(&self, a: &Permutation, b: &Permutation, rng: &mut R) -> Permutation {
+        let n = a.size();
+        if n == 0 {
+            return a.clone();
+        }
+        let mut neighbors = vec![Vec::with_capacity(4); n];
+        for p in [a.permutation(), b.permutation()] {
+            for i in 0..n {
+                let v = p[i];
+                for w in [p[(i + n - 1) % n], p[(i + 1) % n]] {
+                    if w != v && !neighbors[v].contains(&w) {
+                        neighbors[v].push(w);
+                    }
+                }
+            }
+        }
+
+        let mut result = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        let mut current = a.permutation()[0];
+        loop {
+            result.push(current);
+            visited[current] = true;
+            if result.len() == n {
+                break;
+            }
+            for list in neighbors.iter_mut() {
+                list.retain(|&w| w != current);
+            }
+
+            let candidates = &neighbors[current];
+            current = if candidates.is_empty() {
+                let unvisited = (0..n).filter(|&v| !visited[v]).collect::<Vec<_>>();
+                unvisited[rng.random_range(0..unvisited.len())]
+            } else {
+                let fewest = candidates
+                    .iter()
+                    .map(|&w| neighbors[w].len())
+                    .min()
+                    .unwrap();
+                let ties = candidates
+                    .iter()
+                    .copied()
+                    .filter(|&w| neighbors[w].len() == fewest)
+                    .collect::<Vec<_>>();
+                if ties.len() == 1 {
+                    ties[0]
+                } else {
+                    ties[rng.random_range(0..ties.len())]
+                }
+            };
+        }
+        Permutation::new(result)
+    }
+}
+
+// Test of the defined crossover operators
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::MockRng;
+
+    fn bitstring_to_bools(s: &str) -> Vec<bool> {
+        s.chars()
+            .map(|c| match c {
+                '0' => false,
+                '1' => true,
+                _ => panic!("invalid character: {}", c),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_uniform_crossover() {
+        // (a, b, take from b, expected)
+        let testcases = vec![
+            ("00000000", "11111111", "10001001", "10001001"),
+            ("1001010110", "0110101001", "0000011111", "1001001001"),
+            ("10101", "01010", "00000", "10101"),
+        ];
+
+        for t in testcases {
+            let a = Bitstring::from_bitstring(t.0).unwrap();
+            let b = Bitstring::from_bitstring(t.1).unwrap();
+            let mut mock_rng = MockRng::new_ratio(bitstring_to_bools(t.2));
+            let got = UniformCrossover.apply(&a, &b, &mut mock_rng);
+            assert_eq!(got.to_string(), t.3)
+        }
+    }
+
+    #[test]
+    fn test_one_point_crossover() {
+        // (a, b, cut, expected)
+        let testcases = vec![
+            ("00000000", "11111111", 3, "00011111"),
+            ("1001010110", "0110101001", 1, "1110101001"),
+            ("10101", "01010", 4, "10100"),
+        ];
+
+        for t in testcases {
+            let a = Bitstring::from_bitstring(t.0).unwrap();
+            let b = Bitstring::from_bitstring(t.1).unwrap();
+            let mut mock_rng = MockRng::new_range(vec![t.2]);
+            let got = OnePointCrossover.apply(&a, &b, &mut mock_rng);
+            assert_eq!(got.to_string(), t.3)
+        }
+    }
+
+    #[test]
+    fn test_k_point_crossover() {
+        // (a, b, k, [cuts], expected)
+        let testcases = vec![
+            ("00000000", "11111111", 2, vec![5, 2], "00111000"),
+            ("00000000", "11111111", 3, vec![1, 1, 4, 6], "01110011"),
+            ("1001010110", "0110101001", 1, vec![1], "1110101001"),
+        ];
+
+        for t in testcases {
+            let a = Bitstring::from_bitstring(t.0).unwrap();
+            let b = Bitstring::from_bitstring(t.1).unwrap();
+            let mut mock_rng = MockRng::new_range(t.3);
+            let got = KPointCrossover(t.2).apply(&a, &b, &mut mock_rng);
+            assert_eq!(got.to_string(), t.4)
+        }
+    }
+
+    #[test]
+    fn test_bitstring_crossover_small_size() {
+        for size in 0..2 {
+            let a = Bitstring::new(vec![true; size]);
+            let b = Bitstring::new(vec![false; size]);
+            let mut mock_rng = MockRng::default();
+            assert_eq!(OnePointCrossover.apply(&a, &b, &mut mock_rng), a);
+            assert_eq!(KPointCrossover(0).apply(&a, &b, &mut mock_rng), a);
+            assert_eq!(KPointCrossover(3).apply(&a, &b, &mut mock_rng), a);
+        }
+    }
+
+    #[test]
+    fn test_permutation_crossover_small_size() {
+        for size in 0..2 {
+            let a = Permutation::new((0..size).collect());
+            let b = a.clone();
+            let mut mock_rng = MockRng::default();
+            assert_eq!(OrderCrossover.apply(&a, &b, &mut mock_rng), a);
+            assert_eq!(PartiallyMappedCrossover.apply(&a, &b, &mut mock_rng), a);
+            assert_eq!(CycleCrossover.apply(&a, &b, &mut mock_rng), a);
+            assert_eq!(EdgeRecombination.apply(&a, &b, &mut mock_rng), a);
+        }
+    }
+
+    // Parents used in the permutation crossover examples of Eiben & Smith,
+    // "Introduction to Evolutionary Computing", using 0-indexed elements
+    fn example_parents() -> (Permutation, Permutation) {
+        (
+            Permutation::new(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+            Permutation::new(vec![8, 2, 6, 7, 1, 5, 4, 0, 3]),
+        )
+    }
+
+    #[test]
+    fn test_order_crossover() {
+        let (a, b) = example_parents();
+        let mut mock_rng = MockRng::new_range(vec![6, 3]);
+        let result = OrderCrossover.apply(&a, &b, &mut mock_rng);
+        assert_eq!(*result.permutation(), vec![2, 7, 1, 3, 4, 5, 6, 0, 8])
+    }
+
+    #[test]
+    fn test_partially_mapped_crossover() {
+        let (a, b) = example_parents();
+        let mut mock_rng = MockRng::new_range(vec![3, 6]);
+        let result = PartiallyMappedCrossover.apply(&a, &b, &mut mock_rng);
+        assert_eq!(*result.permutation(), vec![8, 2, 1, 3, 4, 5, 6, 0, 7])
+    }
+
+    #[test]
+    fn test_cycle_crossover() {
+        let (a, b) = example_parents();
+        let result = CycleCrossover.apply(&a, &b, &mut MockRng::default());
+        assert_eq!(*result.permutation(), vec![0, 2, 6, 3, 1, 5, 4, 7, 8])
+    }
+
+    #[test]
+    fn test_edge_recombination() {
+        let a = Permutation::new(vec![0, 1, 2, 3, 4, 5]);
+        let b = Permutation::new(vec![1, 3, 5, 0, 2, 4]);
+        let mut mock_rng = MockRng::new_range(vec![1, 0, 1]);
+        let result = EdgeRecombination.apply(&a, &b, &mut mock_rng);
+        assert_eq!(*result.permutation(), vec![0, 5, 3, 2, 4, 1])
+    }
+}
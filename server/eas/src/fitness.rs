@@ -1,17 +1,48 @@
+use std::cell::Cell;
+
 use super::search_space::SearchSpace;
 
+pub mod bin_val;
+pub mod dimacs;
+pub mod graph;
+pub mod jump;
+pub mod knapsack;
 pub mod leading_ones;
+pub mod linear;
+pub mod max_cut;
+pub mod max_sat;
+pub mod nk_landscape;
 pub mod one_max;
+pub mod plateau;
+pub mod qap;
+pub mod qaplib;
+pub mod ridge;
+pub mod royal_road;
+pub mod trap;
 pub mod tsp;
+pub mod tsplib;
+pub mod two_max;
+pub mod vertex_cover;
 
 // Trait defining a fitness function given a search space
 // Needs to be able to evaluate a search point and yield a fitness value
 // Also defines whether the fitness function is maximizing or minimizing,
 // which is used to be able to compare two fitness values and return an std::cmd::Ordering
+// If known, the optimal fitness value for search points of the given size can be provided
 pub trait FitnessFunction<T: SearchSpace> {
     fn evaluate(&self, instance: &T) -> f64;
     fn is_maximizing(&self) -> bool;
 
+    fn optimal_fitness(&self, _size: usize) -> Option<f64> {
+        None
+    }
+
+    // Problem-specific properties of a solution reported in the status of the algorithms,
+    // such as the number of satisfied clauses for MAX-SAT
+    fn solution_properties(&self, _instance: &T) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::Map::new()
+    }
+
     fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
         if self.is_maximizing() {
             a.total_cmp(&b)
@@ -20,3 +51,132 @@ pub trait FitnessFunction<T: SearchSpace> {
         }
     }
 }
+
+// Boxed fitness functions, allowing the fitness function of an algorithm to be chosen at runtime
+impl<T: SearchSpace, F: FitnessFunction<T> + ?Sized> FitnessFunction<T> for Box<F> {
+    fn evaluate(&self, instance: &T) -> f64 {
+        (**self).evaluate(instance)
+    }
+
+    fn is_maximizing(&self) -> bool {
+        (**self).is_maximizing()
+    }
+
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        (**self).optimal_fitness(size)
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        (**self).compare(a, b)
+    }
+
+    fn solution_properties(&self, instance: &T) -> serde_json::Map<String, serde_json::Value> {
+        (**self).solution_properties(instance)
+    }
+}
+
+// Trait for fitness functions able to compute the fitness change caused by a move,
+// without constructing and evaluating the resulting search point
+pub trait DeltaEvaluation<T: SearchSpace, M>: FitnessFunction<T> {
+    fn delta(&self, instance: &T, m: &M) -> f64;
+}
+
+// Fitness function wrapper counting the number of evaluations,
+// used by the algorithms to report their cost independently of how many evaluations an iteration takes.
+// Delta evaluations are not counted, as a candidate may be reached by several moves.
+// Algorithms using them count one evaluation per candidate after accumulating its deltas
+pub struct EvaluationCounter<F> {
+    fitness_function: F,
+    evaluations: Cell<u64>,
+}
+
+impl<F> EvaluationCounter<F> {
+    pub fn new(fitness_function: F) -> Self {
+        EvaluationCounter {
+            fitness_function,
+            evaluations: Cell::new(0),
+        }
+    }
+
+    pub fn evaluations(&self) -> u64 {
+        self.evaluations.get()
+    }
+
+    // Used when restoring the state of an algorithm from a checkpoint
+    pub fn set_evaluations(&mut self, evaluations: u64) {
+        *self.evaluations.get_mut() = evaluations;
+    }
+
+    // Count the evaluation of a candidate whose fitness was obtained through delta evaluation
+    pub fn count(&self) {
+        self.evaluations.set(self.evaluations.get() + 1);
+    }
+}
+
+impl<T: SearchSpace, F: FitnessFunction<T>> FitnessFunction<T> for EvaluationCounter<F> {
+    fn evaluate(&self, instance: &T) -> f64 {
+        self.count();
+        self.fitness_function.evaluate(instance)
+    }
+
+    fn is_maximizing(&self) -> bool {
+        self.fitness_function.is_maximizing()
+    }
+
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        self.fitness_function.optimal_fitness(size)
+    }
+
+    fn compare(&self, a: f64, b: f64) -> std::cmp::Ordering {
+        self.fitness_function.compare(a, b)
+    }
+
+    fn solution_properties(&self, instance: &T) -> serde_json::Map<String, serde_json::Value> {
+        self.fitness_function.solution_properties(instance)
+    }
+}
+
+impl<T: SearchSpace, M, F: DeltaEvaluation<T, M>> DeltaEvaluation<T, M> for EvaluationCounter<F> {
+    fn delta(&self, instance: &T, m: &M) -> f64 {
+        self.fitness_function.delta(instance, m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fitness::{one_max::OneMax, tsp::TSP},
+        mutation::PermutationMove,
+        search_space::{Bitstring, Permutation},
+    };
+
+    #[test]
+    fn test_evaluation_counter() {
+        let fitness_function = EvaluationCounter::new(OneMax);
+        let x = Bitstring::new(vec![true, false, true]);
+        assert_eq!(fitness_function.evaluate(&x), 2.0);
+        assert_eq!(fitness_function.evaluate(&x), 2.0);
+        assert_eq!(
+            fitness_function.compare(2.0, 1.0),
+            std::cmp::Ordering::Greater
+        );
+        assert_eq!(fitness_function.evaluations(), 2);
+
+        let graph = vec![
+            vec![0.0, 1.0, 2.0],
+            vec![1.0, 0.0, 3.0],
+            vec![2.0, 3.0, 0.0],
+        ];
+        let mut tsp = EvaluationCounter::new(TSP::new(graph));
+        let p = Permutation::new(vec![0, 1, 2]);
+        tsp.delta(&p, &PermutationMove::Swap { i: 0, j: 1 });
+        tsp.delta(&p, &PermutationMove::Swap { i: 1, j: 2 });
+        assert_eq!(tsp.evaluations(), 0);
+        tsp.count();
+        assert_eq!(tsp.evaluations(), 1);
+        tsp.set_evaluations(10);
+        tsp.evaluate(&p);
+        assert_eq!(tsp.evaluations(), 11);
+    }
+}
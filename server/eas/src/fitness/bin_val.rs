@@ -0,0 +1,51 @@
+use super::FitnessFunction;
+use crate::search_space::{Bitstring, SearchSpace};
+
+// Implementation of the BinVal pseudo-boolean function
+// BinVal(x) = sum_i 2^(n-1-i) * x_i, interpreting the bitstring as a binary number
+// with the first bit being the most significant.
+// Fitness values are only exact up to 53 bits, after which less significant bits are rounded away
+pub struct BinVal;
+
+impl FitnessFunction<Bitstring> for BinVal {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        (0..instance.size()).fold(0.0, |acc, i| {
+            2.0 * acc + if instance.get(i) { 1.0 } else { 0.0 }
+        })
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    // Evaluated on 1^n, such that the optimum is reported exactly as evaluated for large n
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some(self.evaluate(&Bitstring::new(vec![true; size])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bin_val() {
+        // (bitstring, fitness)
+        let testcases = vec![
+            ("0000", 0.0),
+            ("0001", 1.0),
+            ("1000", 8.0),
+            ("1011", 11.0),
+            ("1111111111", 1023.0),
+        ];
+
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            let got = BinVal.evaluate(&b);
+            assert_eq!(
+                got, t.1,
+                "expected fitness of {} but got {} on \"{}\"",
+                t.1, got, t.0
+            );
+        }
+        assert_eq!(BinVal.optimal_fitness(10), Some(1023.0));
+    }
+}
@@ -0,0 +1,477 @@
+// Parser for boolean formulas in the DIMACS CNF format,
+// and the weighted WCNF format used by the MAX-SAT evaluations.
+// A formula starts with a "p cnf <variables> <clauses>" (or "p wcnf <variables> <clauses> [<top>]") line,
+// followed by clauses given as non-zero literals terminated by a 0, where -v is the negation of variable v.
+// In WCNF each clause is preceded by its weight, and clauses with weight at least top are hard clauses.
+// Also parses undirected graphs in the DIMACS graph format of the graph coloring benchmarks (.col),
+// as well as plain edge lists
+use std::collections::HashSet;
+
+use super::graph::Graph;
+
+// Largest number of vertices of a parsed graph, as a single line can otherwise allocate a huge graph
+const MAX_VERTICES: usize = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CnfInstance {
+    pub variables: usize,
+    pub clauses: Vec<Vec<i64>>,
+    // Weights of the clauses for WCNF instances, and None for unweighted CNF instances
+    pub weights: Option<Vec<f64>>,
+    pub top: Option<f64>,
+}
+
+// Error returned when parsing a DIMACS file fails,
+// with the 1-based line number where the problem was found if it is tied to a line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub reason: ParseErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorReason {
+    MissingProblemLine,
+    DuplicateProblemLine,
+    InvalidProblemLine(String),
+    InvalidLiteral(String),
+    InvalidWeight(String),
+    VariableOutOfRange(i64),
+    InvalidEdge(String),
+    VertexOutOfRange(usize),
+    TooManyVertices(usize),
+    UnterminatedClause,
+    ClauseCountMismatch { expected: usize, found: usize },
+    EdgeCountMismatch { expected: usize, found: usize },
+}
+
+impl ParseError {
+    fn at(line: usize, reason: ParseErrorReason) -> Self {
+        ParseError {
+            line: Some(line),
+            reason,
+        }
+    }
+
+    fn missing(reason: ParseErrorReason) -> Self {
+        ParseError { line: None, reason }
+    }
+}
+
+impl std::fmt::Display for ParseErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorReason::MissingProblemLine => {
+                write!(f, "missing problem line")
+            }
+            ParseErrorReason::DuplicateProblemLine => write!(f, "duplicate problem line"),
+            ParseErrorReason::InvalidProblemLine(line) => {
+                write!(f, "invalid or unsupported problem line '{}'", line)
+            }
+            ParseErrorReason::InvalidLiteral(literal) => write!(f, "invalid literal '{}'", literal),
+            ParseErrorReason::InvalidWeight(weight) => {
+                write!(
+                    f,
+                    "invalid clause weight '{}', expected a positive number",
+                    weight
+                )
+            }
+            ParseErrorReason::VariableOutOfRange(literal) => {
+                write!(
+                    f,
+                    "literal {} refers to a variable outside of the formula",
+                    literal
+                )
+            }
+            ParseErrorReason::InvalidEdge(line) => write!(f, "invalid edge '{}'", line),
+            ParseErrorReason::VertexOutOfRange(vertex) => {
+                write!(f, "vertex {} is outside of the graph", vertex)
+            }
+            ParseErrorReason::TooManyVertices(vertices) => write!(
+                f,
+                "graph with {} vertices exceeds the limit of {} vertices",
+                vertices, MAX_VERTICES
+            ),
+            ParseErrorReason::UnterminatedClause => {
+                write!(f, "last clause is not terminated by 0")
+            }
+            ParseErrorReason::ClauseCountMismatch { expected, found } => write!(
+                f,
+                "clause count mismatch, expected {} clauses but found {}",
+                expected, found
+            ),
+            ParseErrorReason::EdgeCountMismatch { expected, found } => write!(
+                f,
+                "edge count mismatch, expected {} edges but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.reason),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Parse a CNF or WCNF formula. Lines starting with 'c' are comments,
+// and a line starting with '%' ends the formula, as used by the SATLIB benchmark files
+pub fn parse_cnf(input: &str) -> Result<CnfInstance, ParseError> {
+    let mut header: Option<(usize, usize, bool, Option<f64>)> = None;
+    let mut clauses = vec![];
+    let mut weights = vec![];
+    let mut clause = vec![];
+    let mut weight = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('c') {
+            continue;
+        }
+        if line.starts_with('%') {
+            break;
+        }
+        if line.starts_with('p') {
+            if header.is_some() {
+                return Err(ParseError::at(
+                    number,
+                    ParseErrorReason::DuplicateProblemLine,
+                ));
+            }
+            header = Some(parse_problem_line(line).ok_or_else(|| {
+                ParseError::at(
+                    number,
+                    ParseErrorReason::InvalidProblemLine(line.to_string()),
+                )
+            })?);
+            continue;
+        }
+        let (variables, _, weighted, _) =
+            header.ok_or_else(|| ParseError::at(number, ParseErrorReason::MissingProblemLine))?;
+
+        for token in line.split_whitespace() {
+            if weighted && weight.is_none() {
+                weight = Some(
+                    token
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|w| w.is_finite() && *w > 0.0)
+                        .ok_or_else(|| {
+                            ParseError::at(
+                                number,
+                                ParseErrorReason::InvalidWeight(token.to_string()),
+                            )
+                        })?,
+                );
+                continue;
+            }
+            let literal = token.parse::<i64>().map_err(|_| {
+                ParseError::at(number, ParseErrorReason::InvalidLiteral(token.to_string()))
+            })?;
+            if literal == 0 {
+                clauses.push(std::mem::take(&mut clause));
+                weights.extend(weight.take());
+            } else if literal.unsigned_abs() as usize > variables {
+                return Err(ParseError::at(
+                    number,
+                    ParseErrorReason::VariableOutOfRange(literal),
+                ));
+            } else {
+                clause.push(literal);
+            }
+        }
+    }
+
+    let (variables, expected, weighted, top) =
+        header.ok_or_else(|| ParseError::missing(ParseErrorReason::MissingProblemLine))?;
+    if !clause.is_empty() || weight.is_some() {
+        return Err(ParseError::missing(ParseErrorReason::UnterminatedClause));
+    }
+    if clauses.len() != expected {
+        return Err(ParseError::missing(ParseErrorReason::ClauseCountMismatch {
+            expected,
+            found: clauses.len(),
+        }));
+    }
+    Ok(CnfInstance {
+        variables,
+        clauses,
+        weights: if weighted { Some(weights) } else { None },
+        top,
+    })
+}
+
+// Parse "p cnf <variables> <clauses>" or "p wcnf <variables> <clauses> [<top>]",
+// returning the number of variables and clauses, whether the formula is weighted and the top weight
+fn parse_problem_line(line: &str) -> Option<(usize, usize, bool, Option<f64>)> {
+    let tokens = line.split_whitespace().collect::<Vec<_>>();
+    let weighted = match tokens.get(1) {
+        Some(&"cnf") => false,
+        Some(&"wcnf") => true,
+        _ => return None,
+    };
+    let top = match (weighted, tokens.len()) {
+        (_, 4) => None,
+        (true, 5) => Some(tokens[4].parse::<f64>().ok()?),
+        _ => return None,
+    };
+    let variables = tokens[2].parse::<usize>().ok()?;
+    let clauses = tokens[3].parse::<usize>().ok()?;
+    Some((variables, clauses, weighted, top))
+}
+
+// Parse an undirected graph, either in the DIMACS graph format consisting of a "p edge <vertices> <edges>" line
+// followed by "e <u> <v>" lines with 1-based vertices, or as an edge list of "<u> <v>" lines with 0-based vertices,
+// where the number of vertices is one more than the largest vertex. Lines starting with 'c' or '#' are comments.
+// The edge count of the problem line counts every edge line, while duplicate edges,
+// such as edges listed in both directions, are only included once in the graph
+pub fn parse_graph(input: &str) -> Result<Graph, ParseError> {
+    let mut header: Option<(usize, usize)> = None;
+    let mut edge_lines = 0;
+    let mut edge_list = false;
+    let mut edges = vec![];
+    let mut seen = HashSet::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('c') || line.starts_with('#') {
+            continue;
+        }
+        let invalid_edge =
+            || ParseError::at(number, ParseErrorReason::InvalidEdge(line.to_string()));
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        let edge = match tokens[..] {
+            ["p", "edge" | "col", vertices, edges] if header.is_none() && !edge_list => {
+                let (Ok(vertices), Ok(edges)) = (vertices.parse::<usize>(), edges.parse::<usize>())
+                else {
+                    return Err(ParseError::at(
+                        number,
+                        ParseErrorReason::InvalidProblemLine(line.to_string()),
+                    ));
+                };
+                if vertices > MAX_VERTICES {
+                    return Err(ParseError::at(
+                        number,
+                        ParseErrorReason::TooManyVertices(vertices),
+                    ));
+                }
+                header = Some((vertices, edges));
+                continue;
+            }
+            ["p", ..] if header.is_some() => {
+                return Err(ParseError::at(
+                    number,
+                    ParseErrorReason::DuplicateProblemLine,
+                ))
+            }
+            ["p", ..] => {
+                return Err(ParseError::at(
+                    number,
+                    ParseErrorReason::InvalidProblemLine(line.to_string()),
+                ))
+            }
+            // Vertex descriptors, such as vertex weights, are not used
+            ["n", ..] if header.is_some() => continue,
+            ["e", u, v] => {
+                let (vertices, _) = header
+                    .ok_or_else(|| ParseError::at(number, ParseErrorReason::MissingProblemLine))?;
+                let vertex = |token: &str| match token.parse::<usize>() {
+                    Ok(vertex) if (1..=vertices).contains(&vertex) => Ok(vertex - 1),
+                    Ok(vertex) => Err(ParseError::at(
+                        number,
+                        ParseErrorReason::VertexOutOfRange(vertex),
+                    )),
+                    Err(_) => Err(invalid_edge()),
+                };
+                (vertex(u)?, vertex(v)?)
+            }
+            [u, v] if header.is_none() => {
+                edge_list = true;
+                match (u.parse::<usize>(), v.parse::<usize>()) {
+                    (Ok(u), Ok(v)) if u.max(v) >= MAX_VERTICES => {
+                        return Err(ParseError::at(
+                            number,
+                            ParseErrorReason::TooManyVertices(u.max(v) + 1),
+                        ))
+                    }
+                    (Ok(u), Ok(v)) => (u, v),
+                    _ => return Err(invalid_edge()),
+                }
+            }
+            _ => return Err(invalid_edge()),
+        };
+        edge_lines += 1;
+        if seen.insert((edge.0.min(edge.1), edge.0.max(edge.1))) {
+            edges.push(edge);
+        }
+    }
+
+    let vertices = match header {
+        Some((_, expected)) if expected != edge_lines => {
+            return Err(ParseError::missing(ParseErrorReason::EdgeCountMismatch {
+                expected,
+                found: edge_lines,
+            }))
+        }
+        Some((vertices, _)) => vertices,
+        None if edge_list => edges.iter().map(|&(u, v)| u.max(v) + 1).max().unwrap_or(0),
+        None => return Err(ParseError::missing(ParseErrorReason::MissingProblemLine)),
+    };
+    Ok(Graph::new(vertices, edges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cnf() {
+        let input = "c example formula\nc\np cnf 3 2\n1 -3 0\n2 3\n-1 0\n%\n0\n";
+        let instance = parse_cnf(input).unwrap();
+        assert_eq!(instance.variables, 3);
+        assert_eq!(instance.clauses, vec![vec![1, -3], vec![2, 3, -1]]);
+        assert_eq!(instance.weights, None);
+        assert_eq!(instance.top, None);
+    }
+
+    #[test]
+    fn test_parse_wcnf() {
+        let input = "p wcnf 2 3 10\n10 1 2 0\n3 -1 0\n2.5 -2 0\n";
+        let instance = parse_cnf(input).unwrap();
+        assert_eq!(instance.clauses, vec![vec![1, 2], vec![-1], vec![-2]]);
+        assert_eq!(instance.weights, Some(vec![10.0, 3.0, 2.5]));
+        assert_eq!(instance.top, Some(10.0));
+    }
+
+    #[test]
+    fn test_parse_graph() {
+        let input = "c example graph\np edge 4 4\ne 1 2\ne 2 3\ne 3 2\ne 4 1\n";
+        let graph = parse_graph(input).unwrap();
+        assert_eq!(graph.num_vertices(), 4);
+        assert_eq!(graph.edges(), &vec![(0, 1), (1, 2), (3, 0)]);
+
+        let input = "# edge list\n0 1\n1 2\n\n2 5\n";
+        let graph = parse_graph(input).unwrap();
+        assert_eq!(graph.num_vertices(), 6);
+        assert_eq!(graph.edges(), &vec![(0, 1), (1, 2), (2, 5)]);
+
+        // (input, expected line, expected reason)
+        let testcases = vec![
+            ("", None, ParseErrorReason::MissingProblemLine),
+            ("e 1 2\n", Some(1), ParseErrorReason::MissingProblemLine),
+            (
+                "p edge x 1\n",
+                Some(1),
+                ParseErrorReason::InvalidProblemLine("p edge x 1".to_string()),
+            ),
+            (
+                "p edge 2 1\np edge 2 1\n",
+                Some(2),
+                ParseErrorReason::DuplicateProblemLine,
+            ),
+            (
+                "p edge 2 1\ne 1 3\n",
+                Some(2),
+                ParseErrorReason::VertexOutOfRange(3),
+            ),
+            (
+                "p edge 2 1\ne 0 1\n",
+                Some(2),
+                ParseErrorReason::VertexOutOfRange(0),
+            ),
+            (
+                "p edge 2 1\n1 2\n",
+                Some(2),
+                ParseErrorReason::InvalidEdge("1 2".to_string()),
+            ),
+            (
+                "0 1\n1 x\n",
+                Some(2),
+                ParseErrorReason::InvalidEdge("1 x".to_string()),
+            ),
+            (
+                "p edge 3 3\ne 1 2\ne 2 3\n",
+                None,
+                ParseErrorReason::EdgeCountMismatch {
+                    expected: 3,
+                    found: 2,
+                },
+            ),
+            (
+                "p edge 4000000000 0\n",
+                Some(1),
+                ParseErrorReason::TooManyVertices(4000000000),
+            ),
+            (
+                "0 1\n0 4000000000\n",
+                Some(2),
+                ParseErrorReason::TooManyVertices(4000000001),
+            ),
+        ];
+        for t in testcases {
+            let error = parse_graph(t.0).unwrap_err();
+            assert_eq!(error.line, t.1, "{:?}", t.0);
+            assert_eq!(error.reason, t.2, "{:?}", t.0);
+        }
+    }
+
+    #[test]
+    fn test_parse_cnf_errors() {
+        // (input, expected line, expected reason)
+        let testcases = vec![
+            ("1 2 0\n", Some(1), ParseErrorReason::MissingProblemLine),
+            ("", None, ParseErrorReason::MissingProblemLine),
+            (
+                "p sat 2 1\n1 2 0\n",
+                Some(1),
+                ParseErrorReason::InvalidProblemLine("p sat 2 1".to_string()),
+            ),
+            (
+                "p cnf 2 1\np cnf 2 1\n",
+                Some(2),
+                ParseErrorReason::DuplicateProblemLine,
+            ),
+            (
+                "p cnf 2 1\n1 x 0\n",
+                Some(2),
+                ParseErrorReason::InvalidLiteral("x".to_string()),
+            ),
+            (
+                "p cnf 2 1\n1 -3 0\n",
+                Some(2),
+                ParseErrorReason::VariableOutOfRange(-3),
+            ),
+            (
+                "p wcnf 2 1\n0 1 0\n",
+                Some(2),
+                ParseErrorReason::InvalidWeight("0".to_string()),
+            ),
+            (
+                "p cnf 2 2\n1 2\n",
+                None,
+                ParseErrorReason::UnterminatedClause,
+            ),
+            (
+                "p cnf 2 2\n1 2 0\n",
+                None,
+                ParseErrorReason::ClauseCountMismatch {
+                    expected: 2,
+                    found: 1,
+                },
+            ),
+        ];
+        for t in testcases {
+            let error = parse_cnf(t.0).unwrap_err();
+            assert_eq!(error.line, t.1, "{:?}", t.0);
+            assert_eq!(error.reason, t.2, "{:?}", t.0);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+use super::dimacs::{self, ParseError};
+
+// Undirected graph given by its number of vertices and list of edges between 0-based vertices,
+// used by the graph problems on bitstrings where bit i corresponds to vertex i
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    vertices: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+impl Graph {
+    pub fn new(vertices: usize, edges: Vec<(usize, usize)>) -> Self {
+        assert!(
+            edges.iter().all(|&(u, v)| u < vertices && v < vertices),
+            "edge endpoints must be vertices of the graph"
+        );
+        Graph { vertices, edges }
+    }
+
+    // Parse a graph from a DIMACS graph file (.col) or an edge list
+    pub fn from_dimacs(input: &str) -> Result<Self, ParseError> {
+        dimacs::parse_graph(input)
+    }
+
+    // Erdős–Rényi random graph G(n, p), including each of the n(n-1)/2 edges independently with probability p.
+    // Uses a seeded RNG, such that the same seed gives the same graph
+    pub fn random_gnp(vertices: usize, p: f64, seed: u64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "edge probability must be in [0, 1]"
+        );
+        let mut rng = Pcg64::seed_from_u64(seed);
+        let mut edges = vec![];
+        for u in 0..vertices {
+            for v in (u + 1)..vertices {
+                if rng.random_bool(p) {
+                    edges.push((u, v));
+                }
+            }
+        }
+        Graph::new(vertices, edges)
+    }
+
+    // Complete bipartite graph K_(left, right), where the first left vertices are connected to all other vertices.
+    // With left < right the smaller side is the minimum vertex cover, while the larger side is a local optimum.
+    // For left = εn the (1+1) EA gets stuck on the larger side with constant probability,
+    // giving a (1-ε)/ε approximation (Friedrich et al.; Oliveto, He and Yao)
+    pub fn complete_bipartite(left: usize, right: usize) -> Self {
+        let edges = (0..left)
+            .flat_map(|u| (left..left + right).map(move |v| (u, v)))
+            .collect();
+        Graph::new(left + right, edges)
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.vertices
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn edges(&self) -> &Vec<(usize, usize)> {
+        &self.edges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_gnp() {
+        let a = Graph::random_gnp(50, 0.1, 3);
+        assert_eq!(a, Graph::random_gnp(50, 0.1, 3));
+        assert!(a.edges().iter().all(|&(u, v)| u < v && v < 50));
+        assert_eq!(Graph::random_gnp(10, 0.0, 1).num_edges(), 0);
+        assert_eq!(Graph::random_gnp(10, 1.0, 1).num_edges(), 45);
+    }
+
+    #[test]
+    fn test_complete_bipartite() {
+        let graph = Graph::complete_bipartite(2, 3);
+        assert_eq!(graph.num_vertices(), 5);
+        assert_eq!(
+            graph.edges(),
+            &vec![(0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4)]
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use super::FitnessFunction;
+use crate::search_space::{Bitstring, SearchSpace};
+
+// Implementation of the Jump_k pseudo-boolean function
+// Jump_k(x) = k + |x| if |x| <= n - k or x = 1^n, and n - |x| otherwise,
+// where |x| is the number of ones. The optimum 1^n is separated from the local optima
+// with n - k ones by a gap of k - 1 points of lower fitness
+pub struct Jump {
+    k: usize,
+}
+
+impl Jump {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "gap size k must be positive");
+        Jump { k }
+    }
+}
+
+impl FitnessFunction<Bitstring> for Jump {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let n = instance.size();
+        let ones = instance.count_ones();
+        if ones + self.k <= n || ones == n {
+            (self.k + ones) as f64
+        } else {
+            (n - ones) as f64
+        }
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some((size + self.k) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jump() {
+        // (bitstring, k, fitness)
+        let testcases = vec![
+            ("0000000000", 3, 3.0),
+            ("1111111000", 3, 10.0),
+            ("1111111100", 3, 2.0),
+            ("1111111110", 3, 1.0),
+            ("1111111111", 3, 13.0),
+            ("1111111110", 1, 10.0),
+        ];
+
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            let got = Jump::new(t.1).evaluate(&b);
+            assert_eq!(
+                got, t.2,
+                "expected fitness of {} but got {} on \"{}\" with k = {}",
+                t.2, got, t.0, t.1
+            );
+        }
+        assert_eq!(Jump::new(3).optimal_fitness(10), Some(13.0));
+    }
+}
@@ -0,0 +1,424 @@
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+use super::FitnessFunction;
+use crate::search_space::Bitstring;
+
+// The optimum is only computed when the dynamic programming table has at most this many entries
+const MAX_OPTIMUM_ENTRIES: usize = 1 << 26;
+
+// Instance of the 0/1 knapsack problem, selecting items maximizing the total value
+// subject to their total weight being at most the capacity
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnapsackInstance {
+    pub values: Vec<f64>,
+    pub weights: Vec<f64>,
+    pub capacity: f64,
+}
+
+// Correlation between the values and weights of generated instances, following Pisinger,
+// where weights are drawn uniformly from [1, R] and values are
+// uncorrelated: drawn uniformly from [1, R],
+// weakly correlated: drawn uniformly from [w - R/10, w + R/10] (at least 1),
+// strongly correlated: w + R/10
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Correlation {
+    Uncorrelated,
+    WeaklyCorrelated,
+    StronglyCorrelated,
+}
+
+// How solutions exceeding the capacity are evaluated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstraintHandling {
+    // The value is reduced by (V + 1) times the excess weight, where V is the total value of all items.
+    // For integer weights every infeasible solution is worse than every feasible solution,
+    // while less excess weight is preferred among infeasible solutions
+    Penalty,
+    // Items are greedily removed by increasing value/weight ratio until the solution is feasible,
+    // and the value of the repaired solution is used. The solution itself is not changed
+    Repair,
+    // Infeasible solutions get fitness -1, below every feasible solution
+    Death,
+}
+
+// Error returned when parsing a knapsack instance fails,
+// with the 1-based line number where the problem was found if it is tied to a line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub reason: ParseErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorReason {
+    MissingHeader,
+    InvalidHeader,
+    InvalidItem,
+    ItemCountMismatch { expected: usize, found: usize },
+}
+
+impl std::fmt::Display for ParseErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorReason::MissingHeader => write!(f, "missing header 'items capacity'"),
+            ParseErrorReason::InvalidHeader => {
+                write!(f, "invalid header, expected 'items capacity'")
+            }
+            ParseErrorReason::InvalidItem => write!(
+                f,
+                "invalid item, expected 'value weight' with non-negative value and positive weight"
+            ),
+            ParseErrorReason::ItemCountMismatch { expected, found } => write!(
+                f,
+                "item count mismatch, expected {} items but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.reason),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl KnapsackInstance {
+    pub fn new(values: Vec<f64>, weights: Vec<f64>, capacity: f64) -> Self {
+        assert_eq!(values.len(), weights.len(), "expected a weight per value");
+        KnapsackInstance {
+            values,
+            weights,
+            capacity,
+        }
+    }
+
+    // Generate an instance with weights in [1, range] using a seeded RNG,
+    // such that the same seed gives the same instance. The capacity is half the total weight
+    pub fn generate(size: usize, range: u64, correlation: Correlation, seed: u64) -> Self {
+        assert!(range > 0, "range must be positive");
+        let mut rng = Pcg64::seed_from_u64(seed);
+        let spread = range / 10;
+        let mut values = Vec::with_capacity(size);
+        let mut weights = Vec::with_capacity(size);
+        for _ in 0..size {
+            let weight = rng.random_range(1..=range);
+            let value = match correlation {
+                Correlation::Uncorrelated => rng.random_range(1..=range),
+                Correlation::WeaklyCorrelated => rng
+                    .random_range(weight.saturating_sub(spread)..=weight + spread)
+                    .max(1),
+                Correlation::StronglyCorrelated => weight + spread,
+            };
+            values.push(value as f64);
+            weights.push(weight as f64);
+        }
+        let capacity = (weights.iter().sum::<f64>() / 2.0).floor();
+        KnapsackInstance::new(values, weights, capacity)
+    }
+
+    // Parse an instance given as a header line "items capacity" followed by a "value weight" line per item
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !l.is_empty());
+
+        let (number, header) = lines.next().ok_or(ParseError {
+            line: None,
+            reason: ParseErrorReason::MissingHeader,
+        })?;
+        let invalid_header = ParseError {
+            line: Some(number),
+            reason: ParseErrorReason::InvalidHeader,
+        };
+        let (items, capacity) = match parse_pair(header) {
+            Some((items, capacity)) if items.fract() == 0.0 => (items as usize, capacity),
+            _ => return Err(invalid_header),
+        };
+
+        let mut values = vec![];
+        let mut weights = vec![];
+        for (number, line) in lines {
+            match parse_pair(line) {
+                Some((value, weight)) if weight > 0.0 => {
+                    values.push(value);
+                    weights.push(weight);
+                }
+                _ => {
+                    return Err(ParseError {
+                        line: Some(number),
+                        reason: ParseErrorReason::InvalidItem,
+                    })
+                }
+            }
+        }
+        if values.len() != items {
+            return Err(ParseError {
+                line: None,
+                reason: ParseErrorReason::ItemCountMismatch {
+                    expected: items,
+                    found: values.len(),
+                },
+            });
+        }
+        Ok(KnapsackInstance::new(values, weights, capacity))
+    }
+
+    // Write the instance in the format read by parse
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", self.values.len(), self.capacity);
+        for (value, weight) in self.values.iter().zip(&self.weights) {
+            text.push_str(&format!("{} {}\n", value, weight));
+        }
+        text
+    }
+
+    pub fn num_items(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn value(&self, instance: &Bitstring) -> f64 {
+        self.selected(instance).map(|i| self.values[i]).sum()
+    }
+
+    pub fn weight(&self, instance: &Bitstring) -> f64 {
+        self.selected(instance).map(|i| self.weights[i]).sum()
+    }
+
+    pub fn is_feasible(&self, instance: &Bitstring) -> bool {
+        self.weight(instance) <= self.capacity
+    }
+
+    fn selected<'a>(&'a self, instance: &'a Bitstring) -> impl Iterator<Item = usize> + 'a {
+        (0..self.values.len()).filter(|&i| instance.get(i))
+    }
+
+    // Optimal value using dynamic programming over the capacity, which requires integer weights.
+    // Returns None for non-integer weights or if the instance is too large to be solved exactly
+    pub fn optimum_value(&self) -> Option<f64> {
+        let integral = |x: f64| x.fract() == 0.0;
+        if !integral(self.capacity) || !self.weights.iter().all(|&w| integral(w)) {
+            return None;
+        }
+        let capacity = self.capacity.max(0.0) as usize;
+        if (capacity + 1).checked_mul(self.values.len())? > MAX_OPTIMUM_ENTRIES {
+            return None;
+        }
+        // best[c] is the best value of the items considered so far with weight at most c
+        let mut best = vec![0.0; capacity + 1];
+        for (&value, &weight) in self.values.iter().zip(&self.weights) {
+            let weight = weight as usize;
+            for c in (weight..=capacity).rev() {
+                best[c] = f64::max(best[c], best[c - weight] + value);
+            }
+        }
+        Some(best[capacity])
+    }
+}
+
+fn parse_pair(line: &str) -> Option<(f64, f64)> {
+    let numbers = line
+        .split_whitespace()
+        .map(|t| t.parse::<f64>().ok().filter(|x| x.is_finite() && *x >= 0.0))
+        .collect::<Option<Vec<_>>>()?;
+    match numbers[..] {
+        [a, b] => Some((a, b)),
+        _ => None,
+    }
+}
+
+// Implementation of the 0/1 knapsack problem, where bit i selects item i,
+// using the given strategy to evaluate solutions exceeding the capacity
+pub struct Knapsack {
+    instance: KnapsackInstance,
+    constraint_handling: ConstraintHandling,
+    penalty_factor: f64,
+    // Items ordered by increasing value/weight ratio, the order in which items are removed by repair
+    repair_order: Vec<usize>,
+}
+
+impl Knapsack {
+    pub fn new(instance: KnapsackInstance, constraint_handling: ConstraintHandling) -> Self {
+        let penalty_factor = instance.values.iter().sum::<f64>() + 1.0;
+        let mut repair_order = (0..instance.num_items()).collect::<Vec<_>>();
+        let ratio = |i: usize| instance.values[i] / instance.weights[i];
+        repair_order.sort_by(|&a, &b| ratio(a).total_cmp(&ratio(b)));
+        Knapsack {
+            instance,
+            constraint_handling,
+            penalty_factor,
+            repair_order,
+        }
+    }
+
+    pub fn instance(&self) -> &KnapsackInstance {
+        &self.instance
+    }
+
+    // Remove items by increasing value/weight ratio until the solution is feasible
+    pub fn repair(&self, instance: &Bitstring) -> Bitstring {
+        let mut result = instance.clone();
+        let mut weight = self.instance.weight(instance);
+        for &i in &self.repair_order {
+            if weight <= self.instance.capacity {
+                break;
+            }
+            if result.get(i) {
+                result.flip(i);
+                weight -= self.instance.weights[i];
+            }
+        }
+        result
+    }
+}
+
+impl FitnessFunction<Bitstring> for Knapsack {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let value = self.instance.value(instance);
+        let excess = self.instance.weight(instance) - self.instance.capacity;
+        if excess <= 0.0 {
+            return value;
+        }
+        match self.constraint_handling {
+            ConstraintHandling::Penalty => value - self.penalty_factor * excess,
+            ConstraintHandling::Repair => self.instance.value(&self.repair(instance)),
+            ConstraintHandling::Death => -1.0,
+        }
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    fn optimal_fitness(&self, _size: usize) -> Option<f64> {
+        self.instance.optimum_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> KnapsackInstance {
+        // (value, weight): (10, 5), (4, 4), (7, 3), (1, 2) with capacity 8
+        KnapsackInstance::parse("4 8\n10 5\n4 4\n7 3\n1 2\n").unwrap()
+    }
+
+    #[test]
+    fn test_knapsack_constraint_handling() {
+        let instance = example();
+        // Item ratios 2, 1, 7/3, 1/2, so repair removes items 3, 1, 0, 2 in that order
+        // (selection, value, weight, penalty, repair, death)
+        let testcases = vec![
+            ("0000", 0.0, 0.0, 0.0, 0.0, 0.0),
+            ("1010", 17.0, 8.0, 17.0, 17.0, 17.0),
+            ("1100", 14.0, 9.0, 14.0 - 23.0, 10.0, -1.0),
+            ("1111", 22.0, 14.0, 22.0 - 23.0 * 6.0, 17.0, -1.0),
+            ("0111", 12.0, 9.0, 12.0 - 23.0, 11.0, -1.0),
+        ];
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            assert_eq!(instance.value(&b), t.1, "{}", t.0);
+            assert_eq!(instance.weight(&b), t.2, "{}", t.0);
+            let strategies = [
+                (ConstraintHandling::Penalty, t.3),
+                (ConstraintHandling::Repair, t.4),
+                (ConstraintHandling::Death, t.5),
+            ];
+            for (strategy, expected) in strategies {
+                let knapsack = Knapsack::new(instance.clone(), strategy);
+                assert_eq!(knapsack.evaluate(&b), expected, "{} {:?}", t.0, strategy);
+            }
+        }
+    }
+
+    #[test]
+    fn test_knapsack_repair() {
+        let knapsack = Knapsack::new(example(), ConstraintHandling::Repair);
+        let b = Bitstring::from_bitstring("1111").unwrap();
+        let repaired = knapsack.repair(&b);
+        assert_eq!(repaired, Bitstring::from_bitstring("1010").unwrap());
+        assert!(knapsack.instance().is_feasible(&repaired));
+        // The solution itself is not changed by evaluation
+        knapsack.evaluate(&b);
+        assert_eq!(b.count_ones(), 4);
+    }
+
+    #[test]
+    fn test_knapsack_optimum() {
+        let instance = example();
+        assert_eq!(instance.optimum_value(), Some(17.0));
+        // The dynamic programming optimum must match exhaustive search
+        for correlation in [
+            Correlation::Uncorrelated,
+            Correlation::WeaklyCorrelated,
+            Correlation::StronglyCorrelated,
+        ] {
+            let instance = KnapsackInstance::generate(12, 100, correlation, 3);
+            let best = (0..1 << 12)
+                .map(|x| Bitstring::new((0..12).map(|i| x >> i & 1 == 1).collect()))
+                .filter(|b| instance.is_feasible(b))
+                .map(|b| instance.value(&b))
+                .fold(0.0, f64::max);
+            assert_eq!(instance.optimum_value(), Some(best), "{:?}", correlation);
+        }
+        // Non-integer weights are not solved exactly
+        let instance = KnapsackInstance::new(vec![1.0], vec![0.5], 1.0);
+        assert_eq!(instance.optimum_value(), None);
+    }
+
+    #[test]
+    fn test_knapsack_generate() {
+        let a = KnapsackInstance::generate(50, 1000, Correlation::WeaklyCorrelated, 7);
+        let b = KnapsackInstance::generate(50, 1000, Correlation::WeaklyCorrelated, 7);
+        assert_eq!(a, b);
+        for (&value, &weight) in a.values.iter().zip(&a.weights) {
+            assert!((1.0..=1000.0).contains(&weight));
+            assert!(value >= 1.0 && (value - weight).abs() <= 100.0);
+        }
+        let strong = KnapsackInstance::generate(50, 1000, Correlation::StronglyCorrelated, 7);
+        for (&value, &weight) in strong.values.iter().zip(&strong.weights) {
+            assert_eq!(value, weight + 100.0);
+        }
+        assert_eq!(
+            strong.capacity,
+            (strong.weights.iter().sum::<f64>() / 2.0).floor()
+        );
+        assert!(!strong.is_feasible(&Bitstring::new(vec![true; 50])));
+    }
+
+    #[test]
+    fn test_knapsack_parse() {
+        let instance = KnapsackInstance::generate(20, 100, Correlation::Uncorrelated, 1);
+        assert_eq!(KnapsackInstance::parse(&instance.to_text()), Ok(instance));
+
+        // (input, expected line, expected reason)
+        let testcases = vec![
+            ("\n\n", None, ParseErrorReason::MissingHeader),
+            ("2\n1 1\n", Some(1), ParseErrorReason::InvalidHeader),
+            ("1.5 10\n1 1\n", Some(1), ParseErrorReason::InvalidHeader),
+            ("1 10\n1\n", Some(2), ParseErrorReason::InvalidItem),
+            ("1 10\n1 0\n", Some(2), ParseErrorReason::InvalidItem),
+            ("1 10\n-1 2\n", Some(2), ParseErrorReason::InvalidItem),
+            (
+                "2 10\n1 2\n",
+                None,
+                ParseErrorReason::ItemCountMismatch {
+                    expected: 2,
+                    found: 1,
+                },
+            ),
+        ];
+        for t in testcases {
+            let error = KnapsackInstance::parse(t.0).unwrap_err();
+            assert_eq!(error.line, t.1, "{:?}", t.0);
+            assert_eq!(error.reason, t.2, "{:?}", t.0);
+        }
+    }
+}
@@ -6,19 +6,14 @@ pub struct LeadingOnes;
 
 impl FitnessFunction<Bitstring> for LeadingOnes {
     fn evaluate(&self, instance: &Bitstring) -> f64 {
-        let mut leading_ones = 0;
-        for &b in instance.bits() {
-            if b {
-                leading_ones += 1;
-            } else {
-                break;
-            }
-        }
-        leading_ones as f64
+        instance.leading_ones() as f64
     }
     fn is_maximizing(&self) -> bool {
         true
     }
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some(size as f64)
+    }
 }
 
 #[cfg(test)]
@@ -35,6 +30,17 @@ mod tests {
             ("1111100000", 5.0),
             ("0000011111", 0.0),
         ];
+        // Bitstrings spanning multiple 64-bit words
+        let long = vec![
+            ("1".repeat(64), 64.0),
+            ("1".repeat(130), 130.0),
+            (format!("{}0{}", "1".repeat(70), "1".repeat(30)), 70.0),
+            (format!("{}0", "1".repeat(128)), 128.0),
+        ];
+        for t in &long {
+            let b = Bitstring::from_bitstring(&t.0).unwrap();
+            assert_eq!(LeadingOnes::evaluate(&LeadingOnes, &b), t.1);
+        }
 
         for t in testcases {
             let b = Bitstring::from_bitstring(t.0).unwrap();
@@ -0,0 +1,86 @@
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+use super::FitnessFunction;
+use crate::search_space::Bitstring;
+
+// Implementation of a linear pseudo-boolean function f(x) = sum_i w_i * x_i with the given weights
+pub struct Linear {
+    weights: Vec<f64>,
+}
+
+impl Linear {
+    pub fn new(weights: Vec<f64>) -> Self {
+        Linear { weights }
+    }
+
+    // Linear function with integer weights drawn uniformly from [1, max_weight],
+    // using a seeded RNG such that the same seed gives the same function
+    pub fn new_random(size: usize, max_weight: u64, seed: u64) -> Self {
+        assert!(max_weight > 0, "max weight must be positive");
+        let mut rng = Pcg64::seed_from_u64(seed);
+        let weights = (0..size)
+            .map(|_| rng.random_range(1..=max_weight) as f64)
+            .collect();
+        Linear { weights }
+    }
+
+    pub fn weights(&self) -> &Vec<f64> {
+        &self.weights
+    }
+}
+
+impl FitnessFunction<Bitstring> for Linear {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        self.weights
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| instance.get(i))
+            .map(|(_, w)| w)
+            .sum()
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    // Setting exactly the bits with positive weight to one is optimal
+    fn optimal_fitness(&self, _size: usize) -> Option<f64> {
+        Some(self.weights.iter().filter(|&&w| w > 0.0).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear() {
+        let linear = Linear::new(vec![1.0, 2.0, 3.0, 4.0, -5.0]);
+        // (bitstring, fitness)
+        let testcases = vec![
+            ("00000", 0.0),
+            ("10000", 1.0),
+            ("01010", 6.0),
+            ("11110", 10.0),
+            ("11111", 5.0),
+        ];
+
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            let got = linear.evaluate(&b);
+            assert_eq!(
+                got, t.1,
+                "expected fitness of {} but got {} on \"{}\"",
+                t.1, got, t.0
+            );
+        }
+        assert_eq!(linear.optimal_fitness(5), Some(10.0));
+    }
+
+    #[test]
+    fn test_linear_random_weights() {
+        let a = Linear::new_random(100, 10, 42);
+        let b = Linear::new_random(100, 10, 42);
+        assert_eq!(a.weights(), b.weights());
+        assert!(a.weights().iter().all(|&w| (1.0..=10.0).contains(&w)));
+    }
+}
@@ -0,0 +1,60 @@
+use super::{graph::Graph, FitnessFunction};
+use crate::search_space::Bitstring;
+
+// Implementation of the MAX-CUT problem, where bit i assigns vertex i to one of two sides,
+// maximizing the number of edges between the two sides
+pub struct MaxCut {
+    graph: Graph,
+}
+
+impl MaxCut {
+    pub fn new(graph: Graph) -> Self {
+        MaxCut { graph }
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+}
+
+impl FitnessFunction<Bitstring> for MaxCut {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        self.graph
+            .edges()
+            .iter()
+            .filter(|&&(u, v)| instance.get(u) != instance.get(v))
+            .count() as f64
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_cut() {
+        // Triangle 0 - 1 - 2 with the pendant edge 2 - 3
+        let graph = Graph::new(4, vec![(0, 1), (1, 2), (0, 2), (2, 3)]);
+        let max_cut = MaxCut::new(graph);
+        // (assignment, cut edges)
+        let testcases = vec![
+            ("0000", 0.0),
+            ("1111", 0.0),
+            ("0010", 3.0),
+            ("1001", 3.0),
+            ("0110", 3.0),
+            ("1010", 3.0),
+        ];
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            assert_eq!(max_cut.evaluate(&b), t.1, "{}", t.0);
+        }
+        // Complete bipartite graphs are fully cut by their sides
+        let max_cut = MaxCut::new(Graph::complete_bipartite(3, 4));
+        let b = Bitstring::from_bitstring("1110000").unwrap();
+        assert_eq!(max_cut.evaluate(&b), 12.0);
+    }
+}
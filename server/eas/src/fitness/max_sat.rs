@@ -0,0 +1,201 @@
+use super::{
+    dimacs::{self, ParseError},
+    FitnessFunction,
+};
+use crate::search_space::Bitstring;
+
+// Implementation of the (weighted) MAX-SAT problem, maximizing the total weight of satisfied clauses,
+// where bit i of the bitstring is the assignment of variable i + 1.
+// Unweighted formulas give every clause weight 1, such that the fitness is the number of satisfied clauses.
+// Clauses with a weight of at least top are hard clauses, which must be satisfied.
+// Every violated hard clause subtracts more than the total weight of the soft clauses,
+// such that satisfying a hard clause is always better than any assignment of the soft clauses
+pub struct MaxSat {
+    variables: usize,
+    // Clauses as (variable, negated) literals with 0-based variables
+    clauses: Vec<Vec<(usize, bool)>>,
+    weights: Vec<f64>,
+    hard: Vec<bool>,
+    hard_penalty: f64,
+}
+
+impl MaxSat {
+    // Create a formula from clauses of non-zero DIMACS literals, where -v is the negation of variable v.
+    // Without a top weight all clauses are soft clauses
+    pub fn new(
+        variables: usize,
+        clauses: Vec<Vec<i64>>,
+        weights: Option<Vec<f64>>,
+        top: Option<f64>,
+    ) -> Self {
+        let weights = weights.unwrap_or_else(|| vec![1.0; clauses.len()]);
+        assert_eq!(weights.len(), clauses.len(), "expected a weight per clause");
+        let hard = weights
+            .iter()
+            .map(|&w| top.is_some_and(|top| w >= top))
+            .collect::<Vec<_>>();
+        let soft_weight = weights
+            .iter()
+            .zip(&hard)
+            .filter(|(_, &hard)| !hard)
+            .map(|(w, _)| w)
+            .sum::<f64>();
+        let clauses = clauses
+            .into_iter()
+            .map(|clause| {
+                clause
+                    .into_iter()
+                    .map(|literal| {
+                        assert!(
+                            literal != 0 && literal.unsigned_abs() as usize <= variables,
+                            "invalid literal {}",
+                            literal
+                        );
+                        (literal.unsigned_abs() as usize - 1, literal < 0)
+                    })
+                    .collect()
+            })
+            .collect();
+        MaxSat {
+            variables,
+            clauses,
+            weights,
+            hard,
+            hard_penalty: soft_weight + 1.0,
+        }
+    }
+
+    // Create a formula from a DIMACS CNF or WCNF file
+    pub fn from_dimacs(input: &str) -> Result<Self, ParseError> {
+        let instance = dimacs::parse_cnf(input)?;
+        Ok(Self::new(
+            instance.variables,
+            instance.clauses,
+            instance.weights,
+            instance.top,
+        ))
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.variables
+    }
+
+    pub fn num_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    pub fn num_hard_clauses(&self) -> usize {
+        self.hard.iter().filter(|&&hard| hard).count()
+    }
+
+    // Number of clauses satisfied by the assignment, regardless of their weights
+    pub fn satisfied_clauses(&self, instance: &Bitstring) -> usize {
+        self.clauses
+            .iter()
+            .filter(|clause| Self::is_satisfied(clause, instance))
+            .count()
+    }
+
+    fn is_satisfied(clause: &[(usize, bool)], instance: &Bitstring) -> bool {
+        clause
+            .iter()
+            .any(|&(variable, negated)| instance.get(variable) != negated)
+    }
+}
+
+impl FitnessFunction<Bitstring> for MaxSat {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        self.clauses
+            .iter()
+            .zip(self.weights.iter().zip(&self.hard))
+            .map(
+                |(clause, (&w, &hard))| match (Self::is_satisfied(clause, instance), hard) {
+                    (true, false) => w,
+                    (false, true) => -self.hard_penalty,
+                    _ => 0.0,
+                },
+            )
+            .sum()
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+
+    // The number of satisfied clauses is reported separately,
+    // as the fitness of weighted formulas is the satisfied weight instead
+    fn solution_properties(
+        &self,
+        instance: &Bitstring,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let mut properties = serde_json::Map::new();
+        properties.insert(
+            "satisfied_clauses".to_string(),
+            self.satisfied_clauses(instance).into(),
+        );
+        properties
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_sat() {
+        // (x1 or not x3) and (x2 or x3 or not x1) and (not x1) and (not x2)
+        let formula = MaxSat::from_dimacs("p cnf 3 4\n1 -3 0\n2 3 -1 0\n-1 0\n-2 0\n").unwrap();
+        assert_eq!(formula.num_variables(), 3);
+        assert_eq!(formula.num_clauses(), 4);
+        // (assignment, satisfied clauses)
+        let testcases = vec![("000", 4), ("111", 2), ("100", 2), ("001", 3), ("110", 2)];
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            assert_eq!(formula.satisfied_clauses(&b), t.1, "{}", t.0);
+            assert_eq!(formula.evaluate(&b), t.1 as f64, "{}", t.0);
+        }
+    }
+
+    #[test]
+    fn test_weighted_max_sat() {
+        let formula = MaxSat::from_dimacs("p wcnf 2 3\n10 1 2 0\n3 -1 0\n2.5 -2 0\n").unwrap();
+        assert_eq!(formula.num_hard_clauses(), 0);
+        // (assignment, satisfied clauses, fitness)
+        let testcases = vec![
+            ("00", 2, 5.5),
+            ("10", 2, 12.5),
+            ("01", 2, 13.0),
+            ("11", 1, 10.0),
+        ];
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            assert_eq!(formula.satisfied_clauses(&b), t.1, "{}", t.0);
+            assert_eq!(formula.evaluate(&b), t.2, "{}", t.0);
+            assert_eq!(
+                formula.solution_properties(&b)["satisfied_clauses"],
+                t.1,
+                "{}",
+                t.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_hard_clauses() {
+        // (x1 or x2) and (not x1) are hard, the soft clauses have a total weight of 5.5
+        let formula =
+            MaxSat::from_dimacs("p wcnf 2 4 10\n10 1 2 0\n12 -1 0\n3 -2 0\n2.5 1 0\n").unwrap();
+        assert_eq!(formula.num_hard_clauses(), 2);
+        // (assignment, satisfied clauses, fitness)
+        let testcases = vec![
+            ("00", 2, 3.0 - 6.5),
+            ("10", 3, 5.5 - 6.5),
+            ("01", 2, 0.0),
+            ("11", 2, 2.5 - 6.5),
+        ];
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            assert_eq!(formula.satisfied_clauses(&b), t.1, "{}", t.0);
+            assert_eq!(formula.evaluate(&b), t.2, "{}", t.0);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use rand::{seq::index, Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+use super::FitnessFunction;
+use crate::search_space::{Bitstring, SearchSpace};
+
+// How the K bits interacting with each bit of an NK-landscape are chosen
+// Adjacent uses the K bits following bit i (cyclically), while Random uses K distinct random other bits
+#[derive(Debug, Clone, Copy)]
+pub enum Neighborhood {
+    Adjacent,
+    Random,
+}
+
+// The optimum is only computed when the number of steps of the exact algorithm is at most this limit
+const MAX_OPTIMUM_STEPS: usize = 1 << 24;
+
+// Implementation of the NK-landscape pseudo-boolean function
+// f(x) = 1/N * sum_i f_i(x_i, x_{n_i1}, ..., x_{n_iK}), where each bit i interacts with K other bits,
+// and each f_i is a table of 2^(K+1) values drawn uniformly from [0, 1).
+// K tunes the ruggedness, from the linear K = 0 to the uncorrelated random landscape K = N - 1
+pub struct NKLandscape {
+    n: usize,
+    k: usize,
+    neighborhood: Neighborhood,
+    neighbors: Vec<Vec<usize>>,
+    tables: Vec<Vec<f64>>,
+}
+
+impl NKLandscape {
+    // Create a landscape from a seeded RNG, such that the same seed gives the same landscape
+    pub fn new(n: usize, k: usize, neighborhood: Neighborhood, seed: u64) -> Self {
+        assert!(k < n, "K must be less than N");
+        let mut rng = Pcg64::seed_from_u64(seed);
+        let neighbors = (0..n)
+            .map(|i| match neighborhood {
+                Neighborhood::Adjacent => (1..=k).map(|t| (i + t) % n).collect(),
+                Neighborhood::Random => index::sample(&mut rng, n - 1, k)
+                    .into_iter()
+                    .map(|j| if j < i { j } else { j + 1 })
+                    .collect(),
+            })
+            .collect::<Vec<Vec<usize>>>();
+        let tables = (0..n)
+            .map(|_| (0..1 << (k + 1)).map(|_| rng.random::<f64>()).collect())
+            .collect();
+        NKLandscape {
+            n,
+            k,
+            neighborhood,
+            neighbors,
+            tables,
+        }
+    }
+
+    pub fn neighbors(&self) -> &Vec<Vec<usize>> {
+        &self.neighbors
+    }
+
+    // Index into the table of bit i, with x_i as the least significant bit followed by its neighbors
+    fn table_index(&self, instance: &Bitstring, i: usize) -> usize {
+        self.neighbors[i]
+            .iter()
+            .enumerate()
+            .fold(instance.get(i) as usize, |index, (t, &j)| {
+                index | (instance.get(j) as usize) << (t + 1)
+            })
+    }
+
+    // Find an optimal bitstring, using dynamic programming for adjacent neighborhoods
+    // and exhaustive search for random neighborhoods.
+    // Returns None if the instance is too large to be solved exactly
+    pub fn optimum(&self) -> Option<Bitstring> {
+        let steps = match self.neighborhood {
+            Neighborhood::Adjacent => 1_usize.checked_shl(2 * self.k as u32 + 1),
+            Neighborhood::Random => 1_usize.checked_shl(self.n as u32),
+        }
+        .and_then(|states| states.checked_mul(self.n))?;
+        if steps > MAX_OPTIMUM_STEPS {
+            return None;
+        }
+        Some(match self.neighborhood {
+            Neighborhood::Adjacent => self.optimum_adjacent(),
+            Neighborhood::Random => self.optimum_exhaustive(),
+        })
+    }
+
+    // Fix the first K bits, after which the bits are assigned in order while keeping the best value
+    // of each assignment of the last K bits. The window of bit i is complete once bit i + K is assigned,
+    // and the windows wrapping around the end are added using the fixed first K bits
+    fn optimum_adjacent(&self) -> Bitstring {
+        let (n, k) = (self.n, self.k);
+        let states = 1 << k;
+        let window_mask = (1 << (k + 1)) - 1;
+        let mut best = (f64::NEG_INFINITY, vec![]);
+
+        for prefix in 0..states {
+            // value[s] is the best sum of complete windows where bit t of s is x_(j-K+1+t)
+            let mut value = vec![f64::NEG_INFINITY; states];
+            value[prefix] = 0.0;
+            // previous[j][s] is the state before assigning bit j, and the value assigned to it
+            let mut previous = vec![vec![(0, false); states]; n];
+            for (j, previous_j) in previous.iter_mut().enumerate().skip(k) {
+                let mut next = vec![f64::NEG_INFINITY; states];
+                for (s, &v) in value.iter().enumerate() {
+                    if v == f64::NEG_INFINITY {
+                        continue;
+                    }
+                    for b in 0..2 {
+                        let window = s | b << k;
+                        let new_state = window >> 1;
+                        let new_value = v + self.tables[j - k][window];
+                        if new_value > next[new_state] {
+                            next[new_state] = new_value;
+                            previous_j[new_state] = (s, b == 1);
+                        }
+                    }
+                }
+                value = next;
+            }
+
+            for (s, &v) in value.iter().enumerate() {
+                if v == f64::NEG_INFINITY {
+                    continue;
+                }
+                // Bit u of the sequence is x_(N-K+u) followed by the first K bits
+                let sequence = s | prefix << k;
+                let total = v
+                    + (0..k)
+                        .map(|m| self.tables[n - k + m][(sequence >> m) & window_mask])
+                        .sum::<f64>();
+                if total > best.0 {
+                    // Backtrack the bits assigned after the prefix
+                    let mut bits = vec![false; n];
+                    let mut state = s;
+                    for (bit, previous_j) in bits.iter_mut().zip(&previous).skip(k).rev() {
+                        let (previous_state, assigned) = previous_j[state];
+                        *bit = assigned;
+                        state = previous_state;
+                    }
+                    for (t, bit) in bits.iter_mut().take(k).enumerate() {
+                        *bit = prefix >> t & 1 == 1;
+                    }
+                    best = (total, bits);
+                }
+            }
+        }
+        Bitstring::new(best.1)
+    }
+
+    fn optimum_exhaustive(&self) -> Bitstring {
+        let mut best = (f64::NEG_INFINITY, Bitstring::new(vec![false; self.n]));
+        for x in 0..1_usize << self.n {
+            let candidate = Bitstring::new((0..self.n).map(|i| x >> i & 1 == 1).collect());
+            let fitness = self.evaluate(&candidate);
+            if fitness > best.0 {
+                best = (fitness, candidate);
+            }
+        }
+        best.1
+    }
+}
+
+impl FitnessFunction<Bitstring> for NKLandscape {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let total = (0..instance.size())
+            .map(|i| self.tables[i][self.table_index(instance, i)])
+            .sum::<f64>();
+        total / self.n as f64
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    // Evaluated on the optimal bitstring, such that it matches the fitness of the optimum exactly
+    fn optimal_fitness(&self, _size: usize) -> Option<f64> {
+        self.optimum().map(|x| self.evaluate(&x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn optimum_exhaustive(landscape: &NKLandscape) -> f64 {
+        landscape.evaluate(&landscape.optimum_exhaustive())
+    }
+
+    #[test]
+    fn test_nk_landscape_seeded() {
+        let a = NKLandscape::new(20, 3, Neighborhood::Random, 7);
+        let b = NKLandscape::new(20, 3, Neighborhood::Random, 7);
+        let x = Bitstring::from_bitstring("10110011100011110000").unwrap();
+        assert_eq!(a.evaluate(&x), b.evaluate(&x));
+        assert_eq!(a.neighbors(), b.neighbors());
+        for (i, neighbors) in a.neighbors().iter().enumerate() {
+            assert_eq!(neighbors.len(), 3);
+            assert!(!neighbors.contains(&i));
+            assert!(neighbors.iter().all(|&j| j < 20));
+        }
+        let fitness = a.evaluate(&x);
+        assert!((0.0..1.0).contains(&fitness));
+    }
+
+    #[test]
+    fn test_nk_landscape_adjacent_neighbors() {
+        let landscape = NKLandscape::new(5, 2, Neighborhood::Adjacent, 1);
+        assert_eq!(landscape.neighbors()[0], vec![1, 2]);
+        assert_eq!(landscape.neighbors()[4], vec![0, 1]);
+    }
+
+    #[test]
+    fn test_nk_landscape_optimum() {
+        // The dynamic programming optimum must match exhaustive search
+        for (n, k, seed) in [(10, 0, 1), (10, 1, 2), (10, 2, 3), (12, 4, 4), (5, 4, 5)] {
+            let landscape = NKLandscape::new(n, k, Neighborhood::Adjacent, seed);
+            let optimum = landscape.optimal_fitness(n).unwrap();
+            assert!(
+                (optimum - optimum_exhaustive(&landscape)).abs() < 1e-12,
+                "wrong optimum for N = {}, K = {}",
+                n,
+                k
+            );
+        }
+        let landscape = NKLandscape::new(12, 3, Neighborhood::Random, 6);
+        assert_eq!(
+            landscape.optimal_fitness(12),
+            Some(optimum_exhaustive(&landscape))
+        );
+        // Too large to be solved exactly
+        let landscape = NKLandscape::new(100, 3, Neighborhood::Random, 6);
+        assert_eq!(landscape.optimal_fitness(100), None);
+    }
+}
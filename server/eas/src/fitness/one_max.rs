@@ -6,14 +6,14 @@ pub struct OneMax;
 
 impl FitnessFunction<Bitstring> for OneMax {
     fn evaluate(&self, instance: &Bitstring) -> f64 {
-        instance
-            .bits()
-            .iter()
-            .fold(0_f64, |acc, &b| if b { acc + 1_f64 } else { acc })
+        instance.count_ones() as f64
     }
     fn is_maximizing(&self) -> bool {
         true
     }
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some(size as f64)
+    }
 }
 
 #[cfg(test)]
@@ -30,6 +30,16 @@ mod tests {
             ("1111100000", 5.0),
             ("0000011111", 5.0),
         ];
+        // Bitstrings spanning multiple 64-bit words
+        let long = vec![
+            ("1".repeat(64), 64.0),
+            ("10".repeat(65), 65.0),
+            (format!("{}0{}", "1".repeat(70), "1".repeat(30)), 100.0),
+        ];
+        for t in &long {
+            let b = Bitstring::from_bitstring(&t.0).unwrap();
+            assert_eq!(OneMax::evaluate(&OneMax, &b), t.1);
+        }
 
         for t in testcases {
             let b = Bitstring::from_bitstring(t.0).unwrap();
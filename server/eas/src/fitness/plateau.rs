@@ -0,0 +1,62 @@
+use super::FitnessFunction;
+use crate::search_space::{Bitstring, SearchSpace};
+
+// Implementation of the Plateau_k pseudo-boolean function
+// Plateau_k(x) = |x| if |x| <= n - k or x = 1^n, and n - k otherwise,
+// such that all points with more than n - k ones but not the optimum form a plateau of equal fitness
+pub struct Plateau {
+    k: usize,
+}
+
+impl Plateau {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "plateau size k must be positive");
+        Plateau { k }
+    }
+}
+
+impl FitnessFunction<Bitstring> for Plateau {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let n = instance.size();
+        let ones = instance.count_ones();
+        if ones + self.k <= n || ones == n {
+            ones as f64
+        } else {
+            (n - self.k) as f64
+        }
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some(size as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plateau() {
+        // (bitstring, k, fitness)
+        let testcases = vec![
+            ("0000000000", 3, 0.0),
+            ("1111111000", 3, 7.0),
+            ("1111111100", 3, 7.0),
+            ("1111111110", 3, 7.0),
+            ("1111111111", 3, 10.0),
+            ("1111111110", 1, 9.0),
+        ];
+
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            let got = Plateau::new(t.1).evaluate(&b);
+            assert_eq!(
+                got, t.2,
+                "expected fitness of {} but got {} on \"{}\" with k = {}",
+                t.2, got, t.0, t.1
+            );
+        }
+    }
+}
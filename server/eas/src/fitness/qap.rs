@@ -0,0 +1,138 @@
+use super::{
+    qaplib::{self, ParseError},
+    DeltaEvaluation, FitnessFunction,
+};
+use crate::{
+    mutation::{Move, PermutationMove},
+    search_space::Permutation,
+};
+
+// Implementation of the Quadratic Assignment Problem, assigning n facilities to n locations,
+// where position i of the permutation is the location of facility i.
+// Minimizes sum_i sum_j a_ij * b_p(i)p(j), with flows a between facilities and distances b between locations
+pub struct QAP {
+    flows: Vec<Vec<f64>>,
+    distances: Vec<Vec<f64>>,
+}
+
+impl FitnessFunction<Permutation> for QAP {
+    fn evaluate(&self, instance: &Permutation) -> f64 {
+        let p = instance.permutation();
+        let (a, b) = (&self.flows, &self.distances);
+        (0..p.len())
+            .map(|i| (0..p.len()).map(|j| a[i][j] * b[p[i]][p[j]]).sum::<f64>())
+            .sum()
+    }
+
+    // QAP is a minimization problem
+    fn is_maximizing(&self) -> bool {
+        false
+    }
+}
+
+// Delta evaluation of moves, where a swap of the locations of facilities r and s
+// only changes the terms involving r or s, giving an O(n) delta for general (asymmetric) matrices.
+// The other moves reassign every facility of the changed segments, so they are evaluated in full
+impl DeltaEvaluation<Permutation, PermutationMove> for QAP {
+    fn delta(&self, instance: &Permutation, m: &PermutationMove) -> f64 {
+        let p = instance.permutation();
+        let (a, b) = (&self.flows, &self.distances);
+        match *m {
+            PermutationMove::Swap { i: r, j: s } => {
+                let (pr, ps) = (p[r], p[s]);
+                let mut delta = (a[r][r] - a[s][s]) * (b[ps][ps] - b[pr][pr])
+                    + (a[r][s] - a[s][r]) * (b[ps][pr] - b[pr][ps]);
+                for k in (0..p.len()).filter(|&k| k != r && k != s) {
+                    let pk = p[k];
+                    delta += (a[k][r] - a[k][s]) * (b[pk][ps] - b[pk][pr])
+                        + (a[r][k] - a[s][k]) * (b[ps][pk] - b[pr][pk]);
+                }
+                delta
+            }
+            PermutationMove::TwoOpt { .. }
+            | PermutationMove::ThreeOpt { .. }
+            | PermutationMove::Scramble { .. } => {
+                let mut moved = instance.clone();
+                m.apply(&mut moved);
+                self.evaluate(&moved) - self.evaluate(instance)
+            }
+        }
+    }
+}
+
+impl QAP {
+    pub fn new(flows: Vec<Vec<f64>>, distances: Vec<Vec<f64>>) -> Self {
+        let n = flows.len();
+        assert!(
+            distances.len() == n && flows.iter().chain(&distances).all(|row| row.len() == n),
+            "flow and distance matrices must be square matrices of the same size"
+        );
+        QAP { flows, distances }
+    }
+
+    // Parse an instance in the QAPLIB format
+    pub fn from_qaplib(input: &str) -> Result<QAP, ParseError> {
+        qaplib::parse(input).map(|instance| QAP::new(instance.flows, instance.distances))
+    }
+
+    pub fn size(&self) -> usize {
+        self.flows.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asymmetric(n: usize) -> QAP {
+        let matrix = |c: usize, m: usize| {
+            (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| ((c * i + 3 * j + i * j) % m) as f64)
+                        .collect()
+                })
+                .collect()
+        };
+        QAP::new(matrix(7, 13), matrix(5, 11))
+    }
+
+    #[test]
+    fn test_qap() {
+        let qap = QAP::from_qaplib("3\n0 1 2\n1 0 3\n2 3 0\n0 5 2\n5 0 4\n2 4 0\n").unwrap();
+        assert_eq!(qap.size(), 3);
+        // (permutation, fitness), where each flow is counted in both directions
+        let testcases = vec![
+            (vec![0, 1, 2], 2.0 * (5.0 + 2.0 * 2.0 + 3.0 * 4.0)),
+            (vec![1, 2, 0], 2.0 * (4.0 + 2.0 * 5.0 + 3.0 * 2.0)),
+            (vec![2, 0, 1], 2.0 * (2.0 + 2.0 * 4.0 + 3.0 * 5.0)),
+        ];
+        for t in testcases {
+            let p = Permutation::new(t.0.clone());
+            assert_eq!(qap.evaluate(&p), t.1, "{:?}", t.0);
+        }
+        let solution = qaplib::parse_solution("3 40\n2 3 1\n").unwrap();
+        assert_eq!(
+            qap.evaluate(&Permutation::new(solution.permutation)),
+            solution.value
+        );
+    }
+
+    // Test that the delta evaluation of moves agrees with the full evaluation on an asymmetric instance
+    #[test]
+    fn test_qap_delta() {
+        let qap = asymmetric(12);
+        let p = Permutation::new((0..12).map(|v| (v * 5) % 12).collect());
+        for (i, j) in [(0, 1), (0, 11), (3, 7), (10, 11), (2, 9)] {
+            for m in [
+                PermutationMove::Swap { i, j },
+                PermutationMove::TwoOpt { i, j },
+            ] {
+                let mut moved = p.clone();
+                m.apply(&mut moved);
+                let expected = qap.evaluate(&moved) - qap.evaluate(&p);
+                assert_eq!(qap.delta(&p, &m), expected, "{:?}", m);
+            }
+        }
+    }
+}
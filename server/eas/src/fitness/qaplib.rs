@@ -0,0 +1,232 @@
+// Parser for Quadratic Assignment Problem instances from the QAPLIB library (.dat files),
+// consisting of the size n followed by the n x n flow matrix A and the n x n distance matrix B.
+// Numbers are separated by any whitespace, such that rows of the matrices may span multiple lines.
+// Also reads the solution files (.sln) consisting of the size, the objective value and the permutation
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaplibInstance {
+    pub size: usize,
+    pub flows: Vec<Vec<f64>>,
+    pub distances: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaplibSolution {
+    pub size: usize,
+    pub value: f64,
+    // 0-based location of each facility
+    pub permutation: Vec<usize>,
+}
+
+// Error returned when parsing a QAPLIB file fails,
+// with the 1-based line number where the problem was found if it is tied to a line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub reason: ParseErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorReason {
+    MissingSize,
+    InvalidSize(String),
+    InvalidEntry(String),
+    InvalidPermutation,
+    DimensionMismatch { expected: usize, found: usize },
+}
+
+impl std::fmt::Display for ParseErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorReason::MissingSize => write!(f, "missing instance size"),
+            ParseErrorReason::InvalidSize(size) => {
+                write!(
+                    f,
+                    "invalid instance size '{}', expected a positive integer",
+                    size
+                )
+            }
+            ParseErrorReason::InvalidEntry(entry) => write!(f, "invalid entry '{}'", entry),
+            ParseErrorReason::InvalidPermutation => {
+                write!(f, "solution is not a permutation of 1 to n")
+            }
+            ParseErrorReason::DimensionMismatch { expected, found } => write!(
+                f,
+                "dimension mismatch, expected {} entries but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.reason),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Whitespace separated tokens of the input paired with their 1-based line number
+fn numbered_tokens(input: &str) -> Vec<(usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(i, l)| l.split_whitespace().map(move |t| (i + 1, t)))
+        .collect()
+}
+
+// Parse the leading size and the following numbers, checking that exactly `entries(size)` numbers follow.
+// Sizes where the number of entries overflows are invalid
+fn parse_numbers(
+    input: &str,
+    entries: impl Fn(usize) -> Option<usize>,
+) -> Result<(usize, Vec<(usize, f64)>), ParseError> {
+    let tokens = numbered_tokens(input);
+    let (&(line, size), rest) = tokens.split_first().ok_or(ParseError {
+        line: None,
+        reason: ParseErrorReason::MissingSize,
+    })?;
+    let (size, expected) = size
+        .parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .and_then(|n| Some((n, entries(n)?)))
+        .ok_or_else(|| ParseError {
+            line: Some(line),
+            reason: ParseErrorReason::InvalidSize(size.to_string()),
+        })?;
+    let numbers = rest
+        .iter()
+        .map(|&(line, token)| match token.parse::<f64>() {
+            Ok(x) if x.is_finite() => Ok((line, x)),
+            _ => Err(ParseError {
+                line: Some(line),
+                reason: ParseErrorReason::InvalidEntry(token.to_string()),
+            }),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if numbers.len() != expected {
+        return Err(ParseError {
+            line: None,
+            reason: ParseErrorReason::DimensionMismatch {
+                expected,
+                found: numbers.len(),
+            },
+        });
+    }
+    Ok((size, numbers))
+}
+
+pub fn parse(input: &str) -> Result<QaplibInstance, ParseError> {
+    let (size, numbers) = parse_numbers(input, |n| n.checked_mul(n)?.checked_mul(2))?;
+    let mut matrices = numbers
+        .chunks(size)
+        .map(|row| row.iter().map(|&(_, x)| x).collect::<Vec<_>>());
+    let flows = matrices.by_ref().take(size).collect();
+    let distances = matrices.collect();
+    Ok(QaplibInstance {
+        size,
+        flows,
+        distances,
+    })
+}
+
+// Parse a solution file, where the permutation gives the 1-based location of each facility
+pub fn parse_solution(input: &str) -> Result<QaplibSolution, ParseError> {
+    let (size, numbers) = parse_numbers(input, |n| n.checked_add(1))?;
+    let value = numbers[0].1;
+    let mut seen = vec![false; size];
+    let permutation = numbers[1..]
+        .iter()
+        .map(|&(line, x)| {
+            let location = x as usize;
+            if x.fract() != 0.0 || location == 0 || location > size || seen[location - 1] {
+                return Err(ParseError {
+                    line: Some(line),
+                    reason: ParseErrorReason::InvalidPermutation,
+                });
+            }
+            seen[location - 1] = true;
+            Ok(location - 1)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(QaplibSolution {
+        size,
+        value,
+        permutation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "3\n\n0 1 2\n1 0 3\n2 3 0\n\n0 5\n  2 5 0 4\n2 4 0\n";
+        let instance = parse(input).unwrap();
+        assert_eq!(instance.size, 3);
+        assert_eq!(
+            instance.flows,
+            vec![
+                vec![0.0, 1.0, 2.0],
+                vec![1.0, 0.0, 3.0],
+                vec![2.0, 3.0, 0.0]
+            ]
+        );
+        assert_eq!(
+            instance.distances,
+            vec![
+                vec![0.0, 5.0, 2.0],
+                vec![5.0, 0.0, 4.0],
+                vec![2.0, 4.0, 0.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_solution() {
+        let solution = parse_solution("3 26\n2 3 1\n").unwrap();
+        assert_eq!(solution.size, 3);
+        assert_eq!(solution.value, 26.0);
+        assert_eq!(solution.permutation, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // (input, expected line, expected reason)
+        let testcases = vec![
+            ("\n", None, ParseErrorReason::MissingSize),
+            (
+                "0\n",
+                Some(1),
+                ParseErrorReason::InvalidSize("0".to_string()),
+            ),
+            (
+                "2\n0 1\n1 x\n0 1 1 0\n",
+                Some(3),
+                ParseErrorReason::InvalidEntry("x".to_string()),
+            ),
+            (
+                "2\n0 1\n1 0\n0 1 1\n",
+                None,
+                ParseErrorReason::DimensionMismatch {
+                    expected: 8,
+                    found: 7,
+                },
+            ),
+        ];
+        for t in testcases {
+            let error = parse(t.0).unwrap_err();
+            assert_eq!(error.line, t.1, "{:?}", t.0);
+            assert_eq!(error.reason, t.2, "{:?}", t.0);
+        }
+        let error = parse_solution("3 26\n2 2 1\n").unwrap_err();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.reason, ParseErrorReason::InvalidPermutation);
+    }
+}
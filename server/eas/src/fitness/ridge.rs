@@ -0,0 +1,53 @@
+use super::FitnessFunction;
+use crate::search_space::{Bitstring, SearchSpace};
+
+// Implementation of the Ridge pseudo-boolean function
+// Ridge(x) = n + i if x = 1^i 0^(n-i), and n - |x| otherwise,
+// such that the fitness leads to 0^n, from where a path of increasing fitness leads to the optimum 1^n
+pub struct Ridge;
+
+impl FitnessFunction<Bitstring> for Ridge {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let n = instance.size();
+        let ones = instance.count_ones();
+        // x is on the ridge if all ones are leading ones
+        if instance.leading_ones() == ones {
+            (n + ones) as f64
+        } else {
+            (n - ones) as f64
+        }
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some((2 * size) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ridge() {
+        // (bitstring, fitness)
+        let testcases = vec![
+            ("0000000000", 10.0),
+            ("1110000000", 13.0),
+            ("1111111111", 20.0),
+            ("0100000000", 9.0),
+            ("1101000000", 7.0),
+        ];
+
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            let got = Ridge.evaluate(&b);
+            assert_eq!(
+                got, t.1,
+                "expected fitness of {} but got {} on \"{}\"",
+                t.1, got, t.0
+            );
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use super::FitnessFunction;
+use crate::search_space::{Bitstring, SearchSpace};
+
+// Implementation of the Royal Road pseudo-boolean function with blocks of size k
+// The bitstring is split into n / k consecutive blocks, and each block consisting only of ones
+// contributes k to the fitness. Requires the block size to divide the bitstring size
+pub struct RoyalRoad {
+    block_size: usize,
+}
+
+impl RoyalRoad {
+    pub fn new(block_size: usize) -> Self {
+        assert!(block_size > 0, "block size must be positive");
+        RoyalRoad { block_size }
+    }
+}
+
+impl FitnessFunction<Bitstring> for RoyalRoad {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let blocks = instance.size() / self.block_size;
+        let complete = (0..blocks)
+            .filter(|b| {
+                let start = b * self.block_size;
+                (start..start + self.block_size).all(|i| instance.get(i))
+            })
+            .count();
+        (complete * self.block_size) as f64
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some((size / self.block_size * self.block_size) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_royal_road() {
+        // (bitstring, block size, fitness)
+        let testcases = vec![
+            ("000000000", 3, 0.0),
+            ("111000000", 3, 3.0),
+            ("110110110", 3, 0.0),
+            ("111000111", 3, 6.0),
+            ("111111111", 3, 9.0),
+            ("101101", 1, 4.0),
+        ];
+
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            let got = RoyalRoad::new(t.1).evaluate(&b);
+            assert_eq!(
+                got, t.2,
+                "expected fitness of {} but got {} on \"{}\" with block size {}",
+                t.2, got, t.0, t.1
+            );
+        }
+    }
+}
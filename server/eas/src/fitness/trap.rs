@@ -0,0 +1,50 @@
+use super::FitnessFunction;
+use crate::search_space::{Bitstring, SearchSpace};
+
+// Implementation of the deceptive Trap pseudo-boolean function
+// Trap(x) = |x| + (n + 1) * [x = 0^n], behaving like OneMax everywhere except at the optimum 0^n,
+// such that the fitness leads away from the optimum towards the local optimum 1^n
+pub struct Trap;
+
+impl FitnessFunction<Bitstring> for Trap {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let ones = instance.count_ones();
+        if ones == 0 {
+            (instance.size() + 1) as f64
+        } else {
+            ones as f64
+        }
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some((size + 1) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trap() {
+        // (bitstring, fitness)
+        let testcases = vec![
+            ("0000000000", 11.0),
+            ("1000000000", 1.0),
+            ("1111100000", 5.0),
+            ("1111111111", 10.0),
+        ];
+
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            let got = Trap.evaluate(&b);
+            assert_eq!(
+                got, t.1,
+                "expected fitness of {} but got {} on \"{}\"",
+                t.1, got, t.0
+            );
+        }
+    }
+}
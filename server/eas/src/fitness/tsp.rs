@@ -1,11 +1,15 @@
-use super::FitnessFunction;
-use crate::search_space::Permutation;
+use super::{
+    tsplib::{self, ParseError, ParseErrorReason},
+    DeltaEvaluation, FitnessFunction,
+};
+use crate::{mutation::PermutationMove, search_space::Permutation};
 
 // Implementation of the Travelling Salesman Problem
 // Implementation is generic and supports non-symmetric distances
 pub struct TSP {
     distances: Vec<Vec<f64>>,
     vertices: u64,
+    symmetric: bool,
 }
 
 impl FitnessFunction<Permutation> for TSP {
@@ -27,12 +31,133 @@ impl FitnessFunction<Permutation> for TSP {
     }
 }
 
+// Delta evaluation of moves, where a 2-opt move only replaces two edges.
+// For asymmetric instances the reversed segment is traversed in the opposite direction,
+// so its edges are included in the fitness change
+impl DeltaEvaluation<Permutation, PermutationMove> for TSP {
+    fn delta(&self, instance: &Permutation, m: &PermutationMove) -> f64 {
+        let p = instance.permutation();
+        let d = &self.distances;
+        match *m {
+            PermutationMove::TwoOpt { i, j } => {
+                let next = (j + 1) % p.len();
+                let mut delta =
+                    d[p[i]][p[j]] + d[p[i + 1]][p[next]] - d[p[i]][p[i + 1]] - d[p[j]][p[next]];
+                if !self.symmetric {
+                    delta += self.reversal_delta(p, i + 1, j + 1);
+                }
+                delta
+            }
+            // Only the edges leaving positions i - 1, i, j - 1 and j change
+            PermutationMove::Swap { i, j } => {
+                let n = p.len();
+                let starts = vec![(i + n - 1) % n, i, (j + n - 1) % n, j];
+                self.changed_edges(p, starts, |k| match k {
+                    k if k == i => j,
+                    k if k == j => i,
+                    k => k,
+                })
+            }
+            // Only the edges at the three cuts change, together with the edges within reversed segments
+            // for asymmetric instances
+            PermutationMove::ThreeOpt {
+                a,
+                b,
+                c,
+                exchange,
+                reverse_first,
+                reverse_second,
+            } => {
+                let n = p.len();
+                // First and last vertex of each segment after the reversals
+                let ends = |start: usize, end: usize, reverse: bool| {
+                    if reverse {
+                        (p[end - 1], p[start])
+                    } else {
+                        (p[start], p[end - 1])
+                    }
+                };
+                let first = ends(a, b, reverse_first);
+                let second = ends(b, c, reverse_second);
+                let (x, y) = if exchange {
+                    (second, first)
+                } else {
+                    (first, second)
+                };
+                let mut delta = d[x.1][y.0] - d[p[b - 1]][p[b]];
+                if a == 0 && c == n {
+                    // The segments make up the whole tour, such that the edges at the first and last cut coincide
+                    delta += d[y.1][x.0] - d[p[n - 1]][p[0]];
+                } else {
+                    let (previous, next) = (p[(a + n - 1) % n], p[c % n]);
+                    delta +=
+                        d[previous][x.0] + d[y.1][next] - d[previous][p[a]] - d[p[c - 1]][next];
+                }
+                if !self.symmetric {
+                    if reverse_first {
+                        delta += self.reversal_delta(p, a, b);
+                    }
+                    if reverse_second {
+                        delta += self.reversal_delta(p, b, c);
+                    }
+                }
+                delta
+            }
+            // The edges leaving positions start - 1 to the end of the scrambled segment change
+            PermutationMove::Scramble { start, ref order } => {
+                let n = p.len();
+                let mut starts = vec![(start + n - 1) % n];
+                starts.extend(start..start + order.len());
+                self.changed_edges(p, starts, |k| match k.checked_sub(start) {
+                    Some(o) if o < order.len() => start + order[o],
+                    _ => k,
+                })
+            }
+        }
+    }
+}
+
+impl TSP {
+    // Fitness change of traversing the edges within the segment [start, end) in the opposite direction
+    fn reversal_delta(&self, p: &[usize], start: usize, end: usize) -> f64 {
+        let d = &self.distances;
+        (start..end - 1)
+            .map(|k| d[p[k + 1]][p[k]] - d[p[k]][p[k + 1]])
+            .sum()
+    }
+
+    // Fitness change of replacing the edges leaving the given positions, for moves keeping
+    // the vertices at all other positions, where source(k) is the position of the vertex moved to position k.
+    // The positions are deduplicated, as the edges of a move may coincide for adjacent positions
+    fn changed_edges(
+        &self,
+        p: &[usize],
+        mut starts: Vec<usize>,
+        source: impl Fn(usize) -> usize,
+    ) -> f64 {
+        let n = p.len();
+        let d = &self.distances;
+        starts.sort_unstable();
+        starts.dedup();
+        starts
+            .into_iter()
+            .map(|k| {
+                let next = (k + 1) % n;
+                d[p[source(k)]][p[source(next)]] - d[p[k]][p[next]]
+            })
+            .sum()
+    }
+}
+
 impl TSP {
     pub fn new(distances: Vec<Vec<f64>>) -> Self {
         let vertices = distances.len() as u64;
+        let symmetric =
+            (0..distances.len()).all(|i| (0..i).all(|j| distances[i][j] == distances[j][i]));
         TSP {
             distances,
             vertices,
+            symmetric,
         }
     }
 
@@ -48,37 +173,57 @@ impl TSP {
         self.distances.clone()
     }
 
+    pub fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    // Parse a symmetric or asymmetric TSP instance in any of the TSPLIB formats
+    pub fn from_tsplib(input: &str) -> Result<TSP, ParseError> {
+        tsplib::parse(input).map(|instance| TSP::new(instance.distances))
+    }
+
+    // Parse a tour in the TSPLIB TOUR format, checking that it visits every city of this instance
+    pub fn tour_from_tsplib(&self, input: &str) -> Result<Permutation, ParseError> {
+        let tour = tsplib::parse_tour(input)?;
+        if tour.permutation().len() != self.num_cities() {
+            return Err(ParseError::missing(ParseErrorReason::DimensionMismatch {
+                expected: self.num_cities(),
+                found: tour.permutation().len(),
+            }));
+        }
+        Ok(tour)
+    }
+
+    // Write a tour in the TSPLIB TOUR format, with the tour length as comment
+    pub fn tour_to_tsplib(&self, name: &str, tour: &Permutation) -> String {
+        let comment = format!("Length = {}", self.evaluate(tour));
+        tsplib::write_tour(name, Some(&comment), tour)
+    }
+
     // Parse a TSP instance from the EUC2D format from the TSPLIB library
     // Only parses the "NODE_COORD_SECTION ... EOF" section of the file
-    pub fn from_euc2d(input: &str) -> Option<TSP> {
-        let (_metadata, data) = match input.split_once("NODE_COORD_SECTION\n") {
-            None => return None,
-            Some((l, r)) => (l, r),
-        };
-
-        let coords_str = match data.split_once("\nEOF") {
-            None => return None,
-            Some((l, _)) => l,
-        };
-
-        let coords = coords_str
-            .lines()
-            .map(|l| {
-                let mut parts = l.split_whitespace();
-                let (index, x, y) = match (parts.next(), parts.next(), parts.next()) {
-                    (Some(a), Some(b), Some(c)) => (a, b, c),
-                    _ => return None,
-                };
-                let (index, x, y) =
-                    match (index.parse::<usize>(), x.parse::<f64>(), y.parse::<f64>()) {
-                        (Ok(a), Ok(b), Ok(c)) => (a, b, c),
-                        _ => return None,
-                    };
-                Some((index, x, y))
-            })
-            .collect::<Option<Vec<_>>>();
+    pub fn from_euc2d(input: &str) -> Result<TSP, ParseError> {
+        let mut lines = tsplib::numbered_lines(input)?
+            .into_iter()
+            .skip_while(|(_, l)| *l != "NODE_COORD_SECTION");
+        if lines.next().is_none() {
+            return Err(ParseError::missing(ParseErrorReason::MissingSection(
+                "NODE_COORD_SECTION",
+            )));
+        }
 
-        let coords = coords?;
+        let mut coords = Vec::new();
+        let mut found_eof = false;
+        for (number, line) in lines {
+            if line == "EOF" {
+                found_eof = true;
+                break;
+            }
+            coords.push(tsplib::parse_coord_line(number, line)?);
+        }
+        if !found_eof {
+            return Err(ParseError::missing(ParseErrorReason::MissingSection("EOF")));
+        }
 
         let mut distances = vec![vec![0.0; coords.len()]; coords.len()];
 
@@ -90,46 +235,30 @@ impl TSP {
                 distances[i][j] = d.round();
             }
         }
-        Some(TSP::new(distances))
+        Ok(TSP::new(distances))
     }
 }
 
-#[cfg(test)]
-// Function used to parse `.opt.tour` files given by TSPLIB used to verify the optimal tour
-fn parse_tour(input: &str) -> Option<Vec<usize>> {
-    let (_metadata, remaning) = match input.split_once("TOUR_SECTION\n") {
-        None => return None,
-        Some((l, r)) => (l, r),
-    };
-
-    let data = match remaning.split_once("\n-1\nEOF") {
-        None => return None,
-        Some((l, _)) => l,
-    };
-
-    data.lines()
-        .map(|l| l.parse::<usize>().ok().map(|v| v - 1))
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mutation::Move;
 
     // Test being able to parse the berlin52 instance provided from TSPLIB
     #[test]
     fn test_parse_berlin52() {
         let berlin52 = include_str!("./berlin52.tsp");
         let tsp = TSP::from_euc2d(&berlin52);
-        assert!(tsp.is_some());
+        assert!(tsp.is_ok());
     }
 
     // Test veryfing the fitness of the optimal tour of berlin52 provided by TSPLIB
     #[test]
     fn test_berlin52_optimal_tour() {
         let tsp = TSP::from_euc2d(include_str!("./berlin52.tsp")).unwrap();
-        let optimal_tour = parse_tour(include_str!("./berlin52.opt.tour")).unwrap();
-        let optimal_permutation = Permutation::new(optimal_tour);
+        let optimal_permutation = tsp
+            .tour_from_tsplib(include_str!("./berlin52.opt.tour"))
+            .unwrap();
 
         let optimal_fitness = tsp.evaluate(&optimal_permutation);
         assert_eq!(optimal_fitness, 7542.0);
@@ -140,7 +269,106 @@ mod tests {
     fn test_parse_bier127() {
         let berlin52 = include_str!("./bier127.tsp");
         let tsp = TSP::from_euc2d(&berlin52);
-        assert!(tsp.is_some());
+        assert!(tsp.is_ok());
+    }
+
+    // Test that the TSPLIB parser agrees with the EUC2D parser
+    #[test]
+    fn test_from_tsplib_bier127() {
+        let bier127 = include_str!("./bier127.tsp");
+        let tsp = TSP::from_tsplib(bier127).unwrap();
+        assert_eq!(
+            tsp.distances(),
+            TSP::from_euc2d(bier127).unwrap().distances()
+        );
+    }
+
+    // Test that a written tour can be read back, and is rejected by an instance of a different size
+    #[test]
+    fn test_tour_roundtrip() {
+        let tsp = TSP::from_tsplib(include_str!("./bier127.tsp")).unwrap();
+        let tour = Permutation::new((0..127).rev().collect());
+        let output = tsp.tour_to_tsplib("bier127.tour", &tour);
+        assert_eq!(tsp.tour_from_tsplib(&output).unwrap(), tour);
+
+        let berlin52 = TSP::from_tsplib(include_str!("./berlin52.tsp")).unwrap();
+        assert!(berlin52.tour_from_tsplib(&output).is_err());
+    }
+
+    // Test that tours with duplicate or out-of-range nodes are rejected, even if the length matches
+    #[test]
+    fn test_invalid_tours() {
+        let tsp = TSP::new(vec![vec![0.0; 3]; 3]);
+        let tour = |nodes: &str| format!("TYPE : TOUR\nTOUR_SECTION\n{}\n-1\nEOF\n", nodes);
+        assert!(tsp.tour_from_tsplib(&tour("3 1 2")).is_ok());
+        for nodes in ["1 2 2", "1 2 4", "0 1 2", "1 2"] {
+            assert!(tsp.tour_from_tsplib(&tour(nodes)).is_err(), "{}", nodes);
+        }
+        let tour = "TYPE : TOUR\nDIMENSION : 4\nTOUR_SECTION\n1 2 4\n-1\nEOF\n";
+        assert!(tsp.tour_from_tsplib(tour).is_err());
+    }
+
+    // Moves of every kind with delta evaluation on a tour of 52 cities, including adjacent positions,
+    // positions at both ends of the tour, and all reconnections of 3-opt
+    fn delta_moves() -> Vec<PermutationMove> {
+        let pairs = [(0, 1), (0, 51), (3, 17), (20, 50), (50, 51), (1, 3)];
+        let mut moves = pairs
+            .iter()
+            .flat_map(|&(i, j)| {
+                [
+                    PermutationMove::TwoOpt { i, j },
+                    PermutationMove::Swap { i, j },
+                ]
+            })
+            .collect::<Vec<_>>();
+        moves.push(PermutationMove::Scramble {
+            start: 0,
+            order: vec![2, 0, 1],
+        });
+        moves.push(PermutationMove::Scramble {
+            start: 47,
+            order: vec![4, 3, 0, 2, 1],
+        });
+        for (a, b, c) in [
+            (0, 1, 2),
+            (0, 10, 52),
+            (3, 17, 40),
+            (20, 21, 51),
+            (49, 50, 52),
+        ] {
+            for flags in 0..8 {
+                moves.push(PermutationMove::ThreeOpt {
+                    a,
+                    b,
+                    c,
+                    exchange: flags & 1 != 0,
+                    reverse_first: flags & 2 != 0,
+                    reverse_second: flags & 4 != 0,
+                });
+            }
+        }
+        moves
+    }
+
+    // Test that the delta evaluation of every move agrees with the full evaluation,
+    // for both a symmetric and an asymmetric instance
+    #[test]
+    fn test_delta_evaluation() {
+        let symmetric = TSP::from_tsplib(include_str!("./berlin52.tsp")).unwrap();
+        let asymmetric = TSP::new(
+            (0..52)
+                .map(|i| (0..52).map(|j| ((7 * i + 3 * j) % 31) as f64).collect())
+                .collect(),
+        );
+        let tour = Permutation::new((0..52).map(|v| (v * 9) % 52).collect());
+        for tsp in [symmetric, asymmetric] {
+            for m in delta_moves() {
+                let mut moved = tour.clone();
+                m.apply(&mut moved);
+                let expected = tsp.evaluate(&moved) - tsp.evaluate(&tour);
+                assert_eq!(tsp.delta(&tour, &m), expected, "{:?}", m);
+            }
+        }
     }
 
     // Test veryfing the fitness of the optimal tour of bier127 found during testing
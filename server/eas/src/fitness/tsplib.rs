@@ -0,0 +1,838 @@
+// Parser for symmetric (TSP) and asymmetric (ATSP) instances from the TSPLIB library
+// Distances are computed following the TSPLIB 95 documentation (Reinelt),
+// either from node coordinates or given explicitly in the EDGE_WEIGHT_SECTION.
+// Also reads and writes tours in the TSPLIB TOUR format, such as the published .opt.tour files
+use crate::search_space::Permutation;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProblemType {
+    TSP,
+    ATSP,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeWeightType {
+    Explicit,
+    Euc2d,
+    Ceil2d,
+    Att,
+    Geo,
+    Man2d,
+    Max2d,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeWeightFormat {
+    FullMatrix,
+    UpperRow,
+    LowerRow,
+    UpperDiagRow,
+    LowerDiagRow,
+    UpperCol,
+    LowerCol,
+    UpperDiagCol,
+    LowerDiagCol,
+}
+
+#[derive(Debug, Clone)]
+pub struct TsplibInstance {
+    pub name: String,
+    pub problem_type: ProblemType,
+    pub edge_weight_type: EdgeWeightType,
+    pub distances: Vec<Vec<f64>>,
+}
+
+// Error returned when parsing a TSPLIB file fails,
+// with the 1-based line number where the problem was found if it is tied to a line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub reason: ParseErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorReason {
+    CrlfLineEndings,
+    MissingSection(&'static str),
+    MissingSpecification(&'static str),
+    InvalidSpecification { key: String, value: String },
+    UnknownKeyword(String),
+    InvalidCoordinate,
+    InvalidEdgeWeight,
+    InvalidNode,
+    IndexOutOfRange(usize),
+    DuplicateIndex(usize),
+    DimensionMismatch { expected: usize, found: usize },
+}
+
+impl ParseError {
+    pub(super) fn at(line: usize, reason: ParseErrorReason) -> Self {
+        ParseError {
+            line: Some(line),
+            reason,
+        }
+    }
+
+    pub(super) fn missing(reason: ParseErrorReason) -> Self {
+        ParseError { line: None, reason }
+    }
+}
+
+impl std::fmt::Display for ParseErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorReason::CrlfLineEndings => {
+                write!(f, "CRLF line endings are not supported, use LF")
+            }
+            ParseErrorReason::MissingSection(section) => write!(f, "missing section {}", section),
+            ParseErrorReason::MissingSpecification(key) => {
+                write!(f, "missing specification {}", key)
+            }
+            ParseErrorReason::InvalidSpecification { key, value } => {
+                write!(f, "invalid or unsupported value '{}' for {}", value, key)
+            }
+            ParseErrorReason::UnknownKeyword(keyword) => write!(f, "unknown keyword {}", keyword),
+            ParseErrorReason::InvalidCoordinate => {
+                write!(f, "invalid coordinate, expected 'index x y'")
+            }
+            ParseErrorReason::InvalidEdgeWeight => write!(f, "invalid edge weight"),
+            ParseErrorReason::InvalidNode => write!(f, "invalid node index in tour"),
+            ParseErrorReason::IndexOutOfRange(index) => {
+                write!(f, "node index {} is outside of the dimension", index)
+            }
+            ParseErrorReason::DuplicateIndex(index) => write!(f, "duplicate node index {}", index),
+            ParseErrorReason::DimensionMismatch { expected, found } => write!(
+                f,
+                "dimension mismatch, expected {} entries but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.reason),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Lines of the input paired with their 1-based line number.
+// Fails on the first line ending with a carriage return
+pub(super) fn numbered_lines(input: &str) -> Result<Vec<(usize, &str)>, ParseError> {
+    input
+        .split('\n')
+        .enumerate()
+        .map(|(i, l)| {
+            if l.ends_with('\r') {
+                Err(ParseError::at(i + 1, ParseErrorReason::CrlfLineEndings))
+            } else {
+                Ok((i + 1, l))
+            }
+        })
+        .collect()
+}
+
+// Parse a TSPLIB instance consisting of "KEY : VALUE" specification lines followed by data sections.
+// Sections not needed for the distances (DISPLAY_DATA_SECTION, FIXED_EDGES_SECTION) are skipped
+pub fn parse(input: &str) -> Result<TsplibInstance, ParseError> {
+    let mut name = String::new();
+    let mut problem_type = None;
+    let mut dimension = None;
+    let mut edge_weight_type = None;
+    let mut edge_weight_format = None;
+    let mut coords = None;
+    let mut weights = None;
+
+    let missing_specification =
+        |key| ParseError::missing(ParseErrorReason::MissingSpecification(key));
+    let invalid = |number: usize, key: &str, value: &str| {
+        ParseError::at(
+            number,
+            ParseErrorReason::InvalidSpecification {
+                key: key.to_string(),
+                value: value.to_string(),
+            },
+        )
+    };
+
+    let mut lines = numbered_lines(input)?.into_iter().peekable();
+    while let Some((number, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (key, value) = match line.split_once(':') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => (line, ""),
+        };
+        match key {
+            "NAME" => name = value.to_string(),
+            "TYPE" => {
+                problem_type = Some(match value {
+                    "TSP" => ProblemType::TSP,
+                    "ATSP" => ProblemType::ATSP,
+                    _ => return Err(invalid(number, key, value)),
+                })
+            }
+            // A tour needs at least two cities
+            "DIMENSION" => {
+                dimension = Some(
+                    value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|&dimension| dimension >= 2)
+                        .ok_or_else(|| invalid(number, key, value))?,
+                )
+            }
+            "EDGE_WEIGHT_TYPE" => {
+                edge_weight_type =
+                    Some(parse_edge_weight_type(value).ok_or_else(|| invalid(number, key, value))?)
+            }
+            "EDGE_WEIGHT_FORMAT" => {
+                edge_weight_format = Some(
+                    parse_edge_weight_format(value).ok_or_else(|| invalid(number, key, value))?,
+                )
+            }
+            "COMMENT" | "NODE_COORD_TYPE" | "DISPLAY_DATA_TYPE" | "EDGE_DATA_FORMAT" => {}
+            "NODE_COORD_SECTION" => {
+                let dimension = dimension.ok_or(missing_specification("DIMENSION"))?;
+                coords = Some(parse_coords(&mut lines, number, dimension)?);
+            }
+            "EDGE_WEIGHT_SECTION" => {
+                let format =
+                    edge_weight_format.ok_or(missing_specification("EDGE_WEIGHT_FORMAT"))?;
+                let dimension = dimension.ok_or(missing_specification("DIMENSION"))?;
+                let count = weight_count(format, dimension);
+                weights = Some(parse_numbers(&mut lines, number, count)?);
+            }
+            "DISPLAY_DATA_SECTION" => {
+                let dimension = dimension.ok_or(missing_specification("DIMENSION"))?;
+                parse_coords(&mut lines, number, dimension)?;
+            }
+            "FIXED_EDGES_SECTION" => {
+                if !lines.by_ref().any(|(_, l)| l.trim() == "-1") {
+                    return Err(ParseError::missing(ParseErrorReason::MissingSection(
+                        "FIXED_EDGES_SECTION terminator -1",
+                    )));
+                }
+            }
+            "EOF" => break,
+            _ => {
+                return Err(ParseError::at(
+                    number,
+                    ParseErrorReason::UnknownKeyword(key.to_string()),
+                ))
+            }
+        }
+    }
+
+    let problem_type = problem_type.ok_or(missing_specification("TYPE"))?;
+    let dimension = dimension.ok_or(missing_specification("DIMENSION"))?;
+    let edge_weight_type = edge_weight_type.ok_or(missing_specification("EDGE_WEIGHT_TYPE"))?;
+    let distances = match edge_weight_type {
+        EdgeWeightType::Explicit => {
+            let format = edge_weight_format.ok_or(missing_specification("EDGE_WEIGHT_FORMAT"))?;
+            let weights = weights.ok_or(ParseError::missing(ParseErrorReason::MissingSection(
+                "EDGE_WEIGHT_SECTION",
+            )))?;
+            explicit_distances(format, dimension, &weights)
+        }
+        _ => {
+            let coords = coords.ok_or(ParseError::missing(ParseErrorReason::MissingSection(
+                "NODE_COORD_SECTION",
+            )))?;
+            (0..dimension)
+                .map(|i| {
+                    (0..dimension)
+                        .map(|j| distance(edge_weight_type, coords[i], coords[j]))
+                        .collect()
+                })
+                .collect()
+        }
+    };
+
+    Ok(TsplibInstance {
+        name,
+        problem_type,
+        edge_weight_type,
+        distances,
+    })
+}
+
+fn parse_edge_weight_type(value: &str) -> Option<EdgeWeightType> {
+    Some(match value {
+        "EXPLICIT" => EdgeWeightType::Explicit,
+        "EUC_2D" => EdgeWeightType::Euc2d,
+        "CEIL_2D" => EdgeWeightType::Ceil2d,
+        "ATT" => EdgeWeightType::Att,
+        "GEO" => EdgeWeightType::Geo,
+        "MAN_2D" => EdgeWeightType::Man2d,
+        "MAX_2D" => EdgeWeightType::Max2d,
+        _ => return None,
+    })
+}
+
+fn parse_edge_weight_format(value: &str) -> Option<EdgeWeightFormat> {
+    Some(match value {
+        "FULL_MATRIX" => EdgeWeightFormat::FullMatrix,
+        "UPPER_ROW" => EdgeWeightFormat::UpperRow,
+        "LOWER_ROW" => EdgeWeightFormat::LowerRow,
+        "UPPER_DIAG_ROW" => EdgeWeightFormat::UpperDiagRow,
+        "LOWER_DIAG_ROW" => EdgeWeightFormat::LowerDiagRow,
+        "UPPER_COL" => EdgeWeightFormat::UpperCol,
+        "LOWER_COL" => EdgeWeightFormat::LowerCol,
+        "UPPER_DIAG_COL" => EdgeWeightFormat::UpperDiagCol,
+        "LOWER_DIAG_COL" => EdgeWeightFormat::LowerDiagCol,
+        _ => return None,
+    })
+}
+
+// Parse a single "index x y" coordinate line, returning the 1-based index and the coordinates
+pub(super) fn parse_coord_line(number: usize, line: &str) -> Result<(usize, f64, f64), ParseError> {
+    let mut parts = line.split_whitespace();
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(index), Some(x), Some(y), None) => {
+            match (index.parse::<usize>(), x.parse::<f64>(), y.parse::<f64>()) {
+                (Ok(index), Ok(x), Ok(y)) => Ok((index, x, y)),
+                _ => Err(ParseError::at(number, ParseErrorReason::InvalidCoordinate)),
+            }
+        }
+        _ => Err(ParseError::at(number, ParseErrorReason::InvalidCoordinate)),
+    }
+}
+
+// Parse "index x y" lines for each of the nodes, placing the coordinates by their 1-based index.
+// The section ends at the first line that is not a coordinate, which must be after exactly dimension nodes
+fn parse_coords<'a>(
+    lines: &mut std::iter::Peekable<impl Iterator<Item = (usize, &'a str)>>,
+    section: usize,
+    dimension: usize,
+) -> Result<Vec<(f64, f64)>, ParseError> {
+    let mut coords = vec![None; dimension];
+    let mut found = 0;
+    let mut last = section;
+    while let Some(&(number, line)) = lines.peek() {
+        let line = line.trim();
+        if line.is_empty() {
+            lines.next();
+            continue;
+        }
+        // Keywords start with a letter, while coordinate lines start with the index
+        if line.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            break;
+        }
+        lines.next();
+        let (index, x, y) = parse_coord_line(number, line)?;
+        if index == 0 || index > dimension {
+            return Err(ParseError::at(
+                number,
+                ParseErrorReason::IndexOutOfRange(index),
+            ));
+        }
+        if coords[index - 1].is_some() {
+            return Err(ParseError::at(
+                number,
+                ParseErrorReason::DuplicateIndex(index),
+            ));
+        }
+        coords[index - 1] = Some((x, y));
+        found += 1;
+        last = number;
+    }
+    if found != dimension {
+        return Err(ParseError::at(
+            last,
+            ParseErrorReason::DimensionMismatch {
+                expected: dimension,
+                found,
+            },
+        ));
+    }
+    Ok(coords.into_iter().map(|c| c.unwrap()).collect())
+}
+
+// Parse the given number of whitespace separated numbers, which may span any number of lines.
+// The section ends at the first line starting with a keyword or at the end of the input
+fn parse_numbers<'a>(
+    lines: &mut std::iter::Peekable<impl Iterator<Item = (usize, &'a str)>>,
+    section: usize,
+    count: usize,
+) -> Result<Vec<f64>, ParseError> {
+    let mut numbers = Vec::with_capacity(count);
+    let mut last = section;
+    while let Some(&(number, line)) = lines.peek() {
+        if line.trim().starts_with(|c: char| c.is_ascii_alphabetic()) {
+            break;
+        }
+        lines.next();
+        for token in line.split_whitespace() {
+            let weight = token
+                .parse::<f64>()
+                .map_err(|_| ParseError::at(number, ParseErrorReason::InvalidEdgeWeight))?;
+            numbers.push(weight);
+        }
+        if !line.trim().is_empty() {
+            last = number;
+        }
+    }
+    if numbers.len() != count {
+        return Err(ParseError::at(
+            last,
+            ParseErrorReason::DimensionMismatch {
+                expected: count,
+                found: numbers.len(),
+            },
+        ));
+    }
+    Ok(numbers)
+}
+
+// Parse a tour in the TSPLIB TOUR format, converting the 1-based node indices to a permutation of 0..n
+// The TOUR_SECTION lists the nodes in any number of lines, terminated by -1
+pub fn parse_tour(input: &str) -> Result<Permutation, ParseError> {
+    let mut dimension = None;
+    let mut nodes = None;
+
+    let mut lines = numbered_lines(input)?.into_iter();
+    while let Some((number, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (key, value) = match line.split_once(':') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => (line, ""),
+        };
+        let invalid = || {
+            ParseError::at(
+                number,
+                ParseErrorReason::InvalidSpecification {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                },
+            )
+        };
+        match key {
+            "NAME" | "COMMENT" => {}
+            "TYPE" if value == "TOUR" => {}
+            "TYPE" => return Err(invalid()),
+            "DIMENSION" => dimension = Some(value.parse::<usize>().map_err(|_| invalid())?),
+            "TOUR_SECTION" => nodes = Some(parse_tour_section(&mut lines, dimension)?),
+            "EOF" => break,
+            _ => {
+                return Err(ParseError::at(
+                    number,
+                    ParseErrorReason::UnknownKeyword(key.to_string()),
+                ))
+            }
+        }
+    }
+
+    let nodes = nodes.ok_or(ParseError::missing(ParseErrorReason::MissingSection(
+        "TOUR_SECTION",
+    )))?;
+    Ok(Permutation::new(nodes))
+}
+
+// Parse the nodes of the tour until the terminating -1, checking that they form a permutation.
+// Without a DIMENSION specification the number of listed nodes is used
+fn parse_tour_section<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    dimension: Option<usize>,
+) -> Result<Vec<usize>, ParseError> {
+    let mut nodes = Vec::new();
+    let mut terminator = None;
+    'section: for (number, line) in lines.by_ref() {
+        if line.trim().starts_with(|c: char| c.is_ascii_alphabetic()) {
+            break;
+        }
+        for token in line.split_whitespace() {
+            if token == "-1" {
+                terminator = Some(number);
+                break 'section;
+            }
+            let index = token
+                .parse::<usize>()
+                .map_err(|_| ParseError::at(number, ParseErrorReason::InvalidNode))?;
+            nodes.push((number, index));
+        }
+    }
+    let terminator = terminator.ok_or(ParseError::missing(ParseErrorReason::MissingSection(
+        "TOUR_SECTION terminator -1",
+    )))?;
+
+    let n = dimension.unwrap_or(nodes.len());
+    let mut seen = vec![false; n];
+    for &(number, index) in &nodes {
+        if index == 0 || index > n {
+            return Err(ParseError::at(
+                number,
+                ParseErrorReason::IndexOutOfRange(index),
+            ));
+        }
+        if seen[index - 1] {
+            return Err(ParseError::at(
+                number,
+                ParseErrorReason::DuplicateIndex(index),
+            ));
+        }
+        seen[index - 1] = true;
+    }
+    if nodes.len() != n {
+        return Err(ParseError::at(
+            terminator,
+            ParseErrorReason::DimensionMismatch {
+                expected: n,
+                found: nodes.len(),
+            },
+        ));
+    }
+    Ok(nodes.into_iter().map(|(_, index)| index - 1).collect())
+}
+
+// Write a tour in the TSPLIB TOUR format with 1-based node indices, which can be read by parse_tour
+pub fn write_tour(name: &str, comment: Option<&str>, tour: &Permutation) -> String {
+    let mut output = format!("NAME : {}\n", name);
+    if let Some(comment) = comment {
+        output += &format!("COMMENT : {}\n", comment);
+    }
+    output += &format!(
+        "TYPE : TOUR\nDIMENSION : {}\nTOUR_SECTION\n",
+        tour.permutation().len()
+    );
+    for v in tour.permutation() {
+        output += &format!("{}\n", v + 1);
+    }
+    output += "-1\nEOF\n";
+    output
+}
+
+// The column-wise formats list the same entries as the row-wise format of the opposite triangle
+fn row_format(format: EdgeWeightFormat) -> EdgeWeightFormat {
+    match format {
+        EdgeWeightFormat::UpperCol => EdgeWeightFormat::LowerRow,
+        EdgeWeightFormat::LowerCol => EdgeWeightFormat::UpperRow,
+        EdgeWeightFormat::UpperDiagCol => EdgeWeightFormat::LowerDiagRow,
+        EdgeWeightFormat::LowerDiagCol => EdgeWeightFormat::UpperDiagRow,
+        format => format,
+    }
+}
+
+// Entries (i, j) of the matrix in the order they are listed for the given format
+fn matrix_entries(format: EdgeWeightFormat, n: usize) -> Vec<(usize, usize)> {
+    let columns = |i: usize| match row_format(format) {
+        EdgeWeightFormat::UpperRow => (i + 1)..n,
+        EdgeWeightFormat::LowerRow => 0..i,
+        EdgeWeightFormat::UpperDiagRow => i..n,
+        EdgeWeightFormat::LowerDiagRow => 0..(i + 1),
+        _ => 0..n,
+    };
+    (0..n)
+        .flat_map(|i| columns(i).map(move |j| (i, j)))
+        .collect()
+}
+
+fn weight_count(format: EdgeWeightFormat, n: usize) -> usize {
+    matrix_entries(format, n).len()
+}
+
+fn explicit_distances(format: EdgeWeightFormat, n: usize, weights: &[f64]) -> Vec<Vec<f64>> {
+    let mut distances = vec![vec![0.0; n]; n];
+    for ((i, j), &w) in matrix_entries(format, n).into_iter().zip(weights) {
+        distances[i][j] = w;
+        // All formats other than the full matrix describe symmetric instances
+        if format != EdgeWeightFormat::FullMatrix {
+            distances[j][i] = w;
+        }
+    }
+    distances
+}
+
+// Nearest integer function nint(x) = (int) (x + 0.5) used by TSPLIB
+fn nint(x: f64) -> f64 {
+    (x + 0.5).floor()
+}
+
+fn distance(edge_weight_type: EdgeWeightType, (x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> f64 {
+    let (dx, dy) = (x1 - x2, y1 - y2);
+    match edge_weight_type {
+        EdgeWeightType::Euc2d => nint((dx * dx + dy * dy).sqrt()),
+        EdgeWeightType::Ceil2d => (dx * dx + dy * dy).sqrt().ceil(),
+        EdgeWeightType::Man2d => nint(dx.abs() + dy.abs()),
+        EdgeWeightType::Max2d => nint(dx.abs()).max(nint(dy.abs())),
+        // Pseudo-Euclidean distance
+        EdgeWeightType::Att => {
+            let r = ((dx * dx + dy * dy) / 10.0).sqrt();
+            let t = nint(r);
+            if t < r {
+                t + 1.0
+            } else {
+                t
+            }
+        }
+        // Geographical distance on an idealized sphere, with coordinates given as DDD.MM (degrees and minutes)
+        EdgeWeightType::Geo => {
+            if (x1, y1) == (x2, y2) {
+                return 0.0;
+            }
+            // TSPLIB specifies this approximation of pi to reproduce the published optima
+            #[allow(clippy::approx_constant)]
+            const PI: f64 = 3.141592;
+            const RRR: f64 = 6378.388;
+            let radians = |v: f64| {
+                let degrees = v.trunc();
+                PI * (degrees + 5.0 * (v - degrees) / 3.0) / 180.0
+            };
+            let (latitude1, longitude1) = (radians(x1), radians(y1));
+            let (latitude2, longitude2) = (radians(x2), radians(y2));
+            let q1 = (longitude1 - longitude2).cos();
+            let q2 = (latitude1 - latitude2).cos();
+            let q3 = (latitude1 + latitude2).cos();
+            (RRR * (0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)).acos() + 1.0).trunc()
+        }
+        EdgeWeightType::Explicit => unreachable!("explicit edge weights have no coordinates"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coordinate_instance(edge_weight_type: &str, coords: &str) -> String {
+        format!(
+            "NAME : test\nTYPE : TSP\nDIMENSION : 3\nEDGE_WEIGHT_TYPE : {}\nNODE_COORD_SECTION\n{}\nEOF\n",
+            edge_weight_type, coords
+        )
+    }
+
+    #[test]
+    fn test_parse_berlin52() {
+        let instance = parse(include_str!("./berlin52.tsp")).unwrap();
+        assert_eq!(instance.name, "berlin52");
+        assert_eq!(instance.problem_type, ProblemType::TSP);
+        assert_eq!(instance.edge_weight_type, EdgeWeightType::Euc2d);
+        assert_eq!(instance.distances.len(), 52);
+        // Nodes 1 (565, 575) and 2 (25, 185)
+        assert_eq!(instance.distances[0][1], 666.0);
+    }
+
+    #[test]
+    fn test_coordinate_distances() {
+        // (edge weight type, expected distances from node 1 to nodes 2 and 3)
+        let testcases = vec![
+            ("EUC_2D", [5.0, 3.0]),
+            ("CEIL_2D", [5.0, 3.0]),
+            ("MAN_2D", [7.0, 3.0]),
+            ("MAX_2D", [4.0, 3.0]),
+            ("ATT", [2.0, 1.0]),
+        ];
+        for t in testcases {
+            let input = coordinate_instance(t.0, "1 0 0\n2 3.0 4.0\n3 0.0 2.6");
+            let instance = parse(&input).unwrap();
+            assert_eq!(instance.distances[0][1..], t.1, "{}", t.0);
+        }
+    }
+
+    #[test]
+    fn test_geo_distance() {
+        // One degree of longitude along the equator
+        let input = coordinate_instance("GEO", "1 0.0 0.0\n2 0.0 1.0\n3 0.0 0.30");
+        let instance = parse(&input).unwrap();
+        assert_eq!(instance.distances[0][1], 112.0);
+        assert_eq!(instance.distances[1][0], 112.0);
+        assert_eq!(instance.distances[0][2], 56.0);
+        assert_eq!(instance.distances[0][0], 0.0);
+    }
+
+    #[test]
+    fn test_explicit_formats() {
+        // Symmetric matrix [[0, 1, 2], [1, 0, 3], [2, 3, 0]] listed in each of the formats
+        let testcases = vec![
+            ("FULL_MATRIX", "0 1 2\n1 0 3\n2 3 0"),
+            ("UPPER_ROW", "1 2\n3"),
+            ("LOWER_ROW", "1\n2 3"),
+            ("UPPER_DIAG_ROW", "0 1 2\n0 3\n0"),
+            ("LOWER_DIAG_ROW", "0\n1 0\n2 3 0"),
+            ("UPPER_COL", "1\n2 3"),
+            ("LOWER_COL", "1 2\n3"),
+            ("UPPER_DIAG_COL", "0\n1 0\n2 3 0"),
+            ("LOWER_DIAG_COL", "0 1 2 0\n3 0"),
+        ];
+        for t in testcases {
+            let input = format!(
+                "NAME: test\nTYPE: TSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: {}\nEDGE_WEIGHT_SECTION\n{}\nEOF\n",
+                t.0, t.1
+            );
+            let instance = parse(&input).unwrap();
+            assert_eq!(
+                instance.distances,
+                vec![
+                    vec![0.0, 1.0, 2.0],
+                    vec![1.0, 0.0, 3.0],
+                    vec![2.0, 3.0, 0.0]
+                ],
+                "{}",
+                t.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_atsp() {
+        let input = "NAME: test\nTYPE: ATSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: FULL_MATRIX\nEDGE_WEIGHT_SECTION\n9999 1 2\n4 9999 3\n5 6 9999\nEOF\n";
+        let instance = parse(input).unwrap();
+        assert_eq!(instance.problem_type, ProblemType::ATSP);
+        assert_eq!(instance.distances[0][1], 1.0);
+        assert_eq!(instance.distances[1][0], 4.0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let explicit = "TYPE: TSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: UPPER_ROW\nEDGE_WEIGHT_SECTION\n1 2 3 4\nEOF".to_string();
+        // (input, expected line, expected reason)
+        let testcases = vec![
+            (
+                coordinate_instance("EUC_3D", "1 0 0 0\n2 1 1 1\n3 2 2 2"),
+                Some(4),
+                ParseErrorReason::InvalidSpecification {
+                    key: "EDGE_WEIGHT_TYPE".to_string(),
+                    value: "EUC_3D".to_string(),
+                },
+            ),
+            (
+                coordinate_instance("EUC_2D", "1 0 0\n2 a 1\n3 2 2"),
+                Some(7),
+                ParseErrorReason::InvalidCoordinate,
+            ),
+            (
+                coordinate_instance("EUC_2D", "1 0 0\n2 1 1"),
+                Some(7),
+                ParseErrorReason::DimensionMismatch {
+                    expected: 3,
+                    found: 2,
+                },
+            ),
+            (
+                coordinate_instance("EUC_2D", "1 0 0\n2 1 1\n2 2 2"),
+                Some(8),
+                ParseErrorReason::DuplicateIndex(2),
+            ),
+            (
+                coordinate_instance("EUC_2D", "1 0 0\n2 1 1\n4 2 2"),
+                Some(8),
+                ParseErrorReason::IndexOutOfRange(4),
+            ),
+            (
+                explicit,
+                Some(6),
+                ParseErrorReason::DimensionMismatch {
+                    expected: 3,
+                    found: 4,
+                },
+            ),
+            (
+                "NAME: test\r\nTYPE: TSP\r\n".to_string(),
+                Some(1),
+                ParseErrorReason::CrlfLineEndings,
+            ),
+            (
+                "TYPE: TSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EUC_2D\nEOF\n".to_string(),
+                None,
+                ParseErrorReason::MissingSection("NODE_COORD_SECTION"),
+            ),
+            (
+                "TYPE: TSP\nDIMENSION: 0\nEDGE_WEIGHT_TYPE: EUC_2D\nNODE_COORD_SECTION\nEOF\n"
+                    .to_string(),
+                Some(2),
+                ParseErrorReason::InvalidSpecification {
+                    key: "DIMENSION".to_string(),
+                    value: "0".to_string(),
+                },
+            ),
+            (
+                "TYPE: TSP\nDIMENSION: 1\nEDGE_WEIGHT_TYPE: EUC_2D\nNODE_COORD_SECTION\n1 0 0\nEOF\n"
+                    .to_string(),
+                Some(2),
+                ParseErrorReason::InvalidSpecification {
+                    key: "DIMENSION".to_string(),
+                    value: "1".to_string(),
+                },
+            ),
+            (
+                "TYPE: TSP\nDIMENSION: 3\nNODE_COORDS\n".to_string(),
+                Some(3),
+                ParseErrorReason::UnknownKeyword("NODE_COORDS".to_string()),
+            ),
+        ];
+        for t in testcases {
+            let error = parse(&t.0).unwrap_err();
+            assert_eq!(error.line, t.1, "{}", t.0);
+            assert_eq!(error.reason, t.2, "{}", t.0);
+        }
+    }
+
+    #[test]
+    fn test_parse_berlin52_tour() {
+        let tour = parse_tour(include_str!("./berlin52.opt.tour")).unwrap();
+        assert_eq!(tour.permutation().len(), 52);
+        assert_eq!(tour.permutation()[..4], [0, 48, 31, 44]);
+    }
+
+    #[test]
+    fn test_write_tour() {
+        let tour = Permutation::new(vec![2, 0, 1]);
+        let output = write_tour("test.tour", Some("length 10"), &tour);
+        assert_eq!(
+            output,
+            "NAME : test.tour\nCOMMENT : length 10\nTYPE : TOUR\nDIMENSION : 3\nTOUR_SECTION\n3\n1\n2\n-1\nEOF\n"
+        );
+        assert_eq!(parse_tour(&output).unwrap(), tour);
+    }
+
+    #[test]
+    fn test_parse_tour_errors() {
+        let tour = |dimension: usize, nodes: &str| {
+            format!(
+                "NAME : test\nTYPE : TOUR\nDIMENSION : {}\nTOUR_SECTION\n{}\n-1\nEOF\n",
+                dimension, nodes
+            )
+        };
+        // (input, expected line, expected reason)
+        let testcases = vec![
+            (
+                tour(3, "1\n2\n2"),
+                Some(7),
+                ParseErrorReason::DuplicateIndex(2),
+            ),
+            (
+                tour(3, "1 2 4"),
+                Some(5),
+                ParseErrorReason::IndexOutOfRange(4),
+            ),
+            (
+                tour(3, "1\n2"),
+                Some(7),
+                ParseErrorReason::DimensionMismatch {
+                    expected: 3,
+                    found: 2,
+                },
+            ),
+            (tour(3, "1\n2.5\n3"), Some(6), ParseErrorReason::InvalidNode),
+            (
+                "TYPE : TOUR\nTOUR_SECTION\n1\n2\nEOF\n".to_string(),
+                None,
+                ParseErrorReason::MissingSection("TOUR_SECTION terminator -1"),
+            ),
+        ];
+        for t in testcases {
+            let error = parse_tour(&t.0).unwrap_err();
+            assert_eq!(error.line, t.1, "{}", t.0);
+            assert_eq!(error.reason, t.2, "{}", t.0);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use super::FitnessFunction;
+use crate::search_space::{Bitstring, SearchSpace};
+
+// Implementation of the TwoMax pseudo-boolean function
+// TwoMax(x) = max(|x|, n - |x|), with the two optima 0^n and 1^n
+pub struct TwoMax;
+
+impl FitnessFunction<Bitstring> for TwoMax {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let ones = instance.count_ones();
+        ones.max(instance.size() - ones) as f64
+    }
+    fn is_maximizing(&self) -> bool {
+        true
+    }
+    fn optimal_fitness(&self, size: usize) -> Option<f64> {
+        Some(size as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_max() {
+        // (bitstring, fitness)
+        let testcases = vec![
+            ("0000000000", 10.0),
+            ("1111111111", 10.0),
+            ("1111100000", 5.0),
+            ("1000000000", 9.0),
+            ("1111111011", 9.0),
+        ];
+
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            let got = TwoMax.evaluate(&b);
+            assert_eq!(
+                got, t.1,
+                "expected fitness of {} but got {} on \"{}\"",
+                t.1, got, t.0
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use super::{graph::Graph, FitnessFunction};
+use crate::search_space::Bitstring;
+
+// Implementation of the minimum vertex cover problem, where bit i selects vertex i.
+// Minimizes |x| + (n + 1) * u(x), where u(x) is the number of edges not covered by the selected vertices,
+// such that every cover is better than every non-cover, and smaller covers are better
+pub struct VertexCover {
+    graph: Graph,
+}
+
+impl VertexCover {
+    pub fn new(graph: Graph) -> Self {
+        VertexCover { graph }
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn uncovered_edges(&self, instance: &Bitstring) -> usize {
+        self.graph
+            .edges()
+            .iter()
+            .filter(|&&(u, v)| !instance.get(u) && !instance.get(v))
+            .count()
+    }
+
+    pub fn is_cover(&self, instance: &Bitstring) -> bool {
+        self.uncovered_edges(instance) == 0
+    }
+}
+
+impl FitnessFunction<Bitstring> for VertexCover {
+    fn evaluate(&self, instance: &Bitstring) -> f64 {
+        let penalty = self.graph.num_vertices() + 1;
+        (instance.count_ones() + penalty * self.uncovered_edges(instance)) as f64
+    }
+    fn is_maximizing(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vertex_cover() {
+        // Path 0 - 1 - 2 - 3 and the edge 1 - 3
+        let graph = Graph::new(4, vec![(0, 1), (1, 2), (2, 3), (1, 3)]);
+        let vertex_cover = VertexCover::new(graph);
+        // (selection, uncovered edges, fitness)
+        let testcases = vec![
+            ("0000", 4, 20.0),
+            ("0101", 0, 2.0),
+            ("1111", 0, 4.0),
+            ("1000", 3, 16.0),
+            ("0110", 0, 2.0),
+            ("0010", 2, 11.0),
+        ];
+        for t in testcases {
+            let b = Bitstring::from_bitstring(t.0).unwrap();
+            assert_eq!(vertex_cover.uncovered_edges(&b), t.1, "{}", t.0);
+            assert_eq!(vertex_cover.is_cover(&b), t.1 == 0, "{}", t.0);
+            assert_eq!(vertex_cover.evaluate(&b), t.2, "{}", t.0);
+        }
+    }
+}
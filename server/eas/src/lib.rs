@@ -1,5 +1,7 @@
 pub mod algorithms;
+pub mod crossover;
 pub mod fitness;
+pub mod local_search;
 pub mod mutation;
 pub mod search_space;
 
@@ -0,0 +1,348 @@
+use super::search_space::Permutation;
+
+// Local search on tours of a symmetric TSP instance, used as daemon action by the ACO algorithms.
+// Both operators use first improvement and only consider moves adding an edge
+// to one of the k nearest neighbors of a vertex
+
+// Enum representing the local search operators
+// TwoOptOrOpt applies 2-opt until a local optimum is reached, followed by Or-opt,
+// repeating until neither finds an improvement
+#[derive(Debug, Clone, Copy)]
+pub enum LocalSearchOperator {
+    TwoOpt,
+    OrOpt,
+    TwoOptOrOpt,
+}
+
+// Improvements smaller than this are ignored to avoid cycling due to rounding errors
+const EPSILON: f64 = 1e-9;
+
+// Maximum length of the segments moved by Or-opt
+const OR_OPT_MAX_SEGMENT: usize = 3;
+
+pub struct LocalSearch {
+    distances: Vec<Vec<f64>>,
+    neighbors: Vec<Vec<usize>>,
+    operator: LocalSearchOperator,
+}
+
+impl LocalSearch {
+    pub fn new(distances: Vec<Vec<f64>>, neighbors: usize, operator: LocalSearchOperator) -> Self {
+        let neighbors = nearest_neighbor_lists(&distances, neighbors);
+        LocalSearch {
+            distances,
+            neighbors,
+            operator,
+        }
+    }
+
+    // Apply the local search to a tour, returning a tour that is at most as long
+    pub fn apply(&self, tour: &Permutation) -> Permutation {
+        let mut tour = tour.permutation().clone();
+        if tour.len() < 5 {
+            return Permutation::new(tour);
+        }
+        match self.operator {
+            LocalSearchOperator::TwoOpt => {
+                self.two_opt(&mut tour);
+            }
+            LocalSearchOperator::OrOpt => {
+                self.or_opt(&mut tour);
+            }
+            LocalSearchOperator::TwoOptOrOpt => {
+                self.two_opt(&mut tour);
+                while self.or_opt(&mut tour) && self.two_opt(&mut tour) {}
+            }
+        }
+        Permutation::new(tour)
+    }
+
+    fn d(&self, a: usize, b: usize) -> f64 {
+        self.distances[a][b]
+    }
+
+    // First-improvement 2-opt using neighbor lists and don't-look bits.
+    // Returns whether the tour was improved
+    fn two_opt(&self, tour: &mut [usize]) -> bool {
+        let n = tour.len();
+        let mut position = positions(tour);
+        let mut dont_look = vec![false; n];
+        let mut improved_any = false;
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in 0..n {
+                let a = tour[i];
+                if dont_look[a] {
+                    continue;
+                }
+                let mut improved_a = false;
+                // Try moves replacing the edge to the successor and to the predecessor of a
+                for successor in [true, false] {
+                    let pos_a = position[a];
+                    let a_next = if successor {
+                        tour[(pos_a + 1) % n]
+                    } else {
+                        tour[(pos_a + n - 1) % n]
+                    };
+                    for &b in &self.neighbors[a] {
+                        // Neighbors are sorted by distance, so no further improving move exists
+                        if self.d(a, b) >= self.d(a, a_next) {
+                            break;
+                        }
+                        let pos_b = position[b];
+                        let b_next = if successor {
+                            tour[(pos_b + 1) % n]
+                        } else {
+                            tour[(pos_b + n - 1) % n]
+                        };
+                        if b == a_next || b_next == a {
+                            continue;
+                        }
+                        let delta = self.d(a, b) + self.d(a_next, b_next)
+                            - self.d(a, a_next)
+                            - self.d(b, b_next);
+                        if delta < -EPSILON {
+                            if successor {
+                                let pos_a_next = position[a_next];
+                                reverse(tour, &mut position, pos_a_next, pos_b);
+                            } else {
+                                let pos_b_next = position[b_next];
+                                reverse(tour, &mut position, pos_a, pos_b_next);
+                            }
+                            for v in [a, a_next, b, b_next] {
+                                dont_look[v] = false;
+                            }
+                            improved_a = true;
+                            break;
+                        }
+                    }
+                    if improved_a {
+                        break;
+                    }
+                }
+                if improved_a {
+                    improved = true;
+                    improved_any = true;
+                } else {
+                    dont_look[a] = true;
+                }
+            }
+        }
+        improved_any
+    }
+
+    // First-improvement Or-opt, moving segments of up to three vertices
+    // (possibly reversed) next to a neighbor of one of the segment endpoints.
+    // Returns whether the tour was improved
+    fn or_opt(&self, tour: &mut Vec<usize>) -> bool {
+        let n = tour.len();
+        let mut improved_any = false;
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for length in 1..=OR_OPT_MAX_SEGMENT {
+                let mut position = positions(tour);
+                for start in 0..n {
+                    if let Some((c, e, reverse_segment)) =
+                        self.or_opt_move(tour, &position, start, length)
+                    {
+                        move_segment(tour, start, length, c, e, reverse_segment);
+                        position = positions(tour);
+                        improved = true;
+                        improved_any = true;
+                    }
+                }
+            }
+        }
+        improved_any
+    }
+
+    // Find an improving move of the segment of the given length starting at position start,
+    // returning the adjacent vertices c and e to insert it between, and whether to reverse it
+    fn or_opt_move(
+        &self,
+        tour: &[usize],
+        position: &[usize],
+        start: usize,
+        length: usize,
+    ) -> Option<(usize, usize, bool)> {
+        let n = tour.len();
+        let first = tour[start];
+        let last = tour[(start + length - 1) % n];
+        let prev = tour[(start + n - 1) % n];
+        let next = tour[(start + length) % n];
+        let in_segment = |v: usize| (position[v] + n - start) % n < length;
+
+        let removal_gain = self.d(prev, first) + self.d(last, next) - self.d(prev, next);
+        if removal_gain <= EPSILON {
+            return None;
+        }
+
+        for end in [first, last] {
+            for &c in &self.neighbors[end] {
+                if in_segment(c) {
+                    continue;
+                }
+                // Insert between c and either its successor or predecessor
+                for e in [tour[(position[c] + 1) % n], tour[(position[c] + n - 1) % n]] {
+                    if in_segment(e) {
+                        continue;
+                    }
+                    let forward = self.d(c, first) + self.d(last, e) - self.d(c, e);
+                    let reversed = self.d(c, last) + self.d(first, e) - self.d(c, e);
+                    let (added, reverse_segment) = if forward <= reversed {
+                        (forward, false)
+                    } else {
+                        (reversed, true)
+                    };
+                    if added - removal_gain < -EPSILON {
+                        return Some((c, e, reverse_segment));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+// For each vertex, the k nearest other vertices sorted by increasing distance
+pub fn nearest_neighbor_lists(distances: &[Vec<f64>], k: usize) -> Vec<Vec<usize>> {
+    let n = distances.len();
+    (0..n)
+        .map(|i| {
+            let mut neighbors = (0..n).filter(|&j| j != i).collect::<Vec<_>>();
+            neighbors.sort_by(|&a, &b| distances[i][a].total_cmp(&distances[i][b]));
+            neighbors.truncate(k);
+            neighbors
+        })
+        .collect()
+}
+
+fn positions(tour: &[usize]) -> Vec<usize> {
+    let mut position = vec![0; tour.len()];
+    for (i, &v) in tour.iter().enumerate() {
+        position[v] = i;
+    }
+    position
+}
+
+// Reverse the cyclic segment of the tour going forward from position i to position j.
+// Reverses the complementary segment instead if it is shorter, resulting in the same tour
+fn reverse(tour: &mut [usize], position: &mut [usize], i: usize, j: usize) {
+    let n = tour.len();
+    let mut length = (j + n - i) % n + 1;
+    let (mut i, mut j) = (i, j);
+    if 2 * length > n {
+        (i, j) = ((j + 1) % n, (i + n - 1) % n);
+        length = n - length;
+    }
+    for _ in 0..length / 2 {
+        tour.swap(i, j);
+        position[tour[i]] = i;
+        position[tour[j]] = j;
+        i = (i + 1) % n;
+        j = (j + n - 1) % n;
+    }
+}
+
+// Move the cyclic segment of the given length starting at position start,
+// inserting it between the adjacent vertices c and e
+fn move_segment(
+    tour: &mut Vec<usize>,
+    start: usize,
+    length: usize,
+    c: usize,
+    e: usize,
+    reverse_segment: bool,
+) {
+    let n = tour.len();
+    let mut segment = (0..length)
+        .map(|k| tour[(start + k) % n])
+        .collect::<Vec<_>>();
+    let mut rest = (length..n)
+        .map(|k| tour[(start + k) % n])
+        .collect::<Vec<_>>();
+
+    let pos_c = rest.iter().position(|&v| v == c).unwrap();
+    // Orient the segment such that it is entered from c
+    if reverse_segment {
+        segment.reverse();
+    }
+    let insert_at = if rest[(pos_c + 1) % rest.len()] == e {
+        pos_c + 1
+    } else {
+        // e precedes c, so the segment is traversed from e to c
+        segment.reverse();
+        pos_c
+    };
+    rest.splice(insert_at..insert_at, segment);
+    *tour = rest;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fitness::{tsp::TSP, FitnessFunction};
+
+    fn is_permutation(tour: &[usize]) -> bool {
+        let mut seen = vec![false; tour.len()];
+        for &v in tour {
+            if v >= tour.len() || seen[v] {
+                return false;
+            }
+            seen[v] = true;
+        }
+        true
+    }
+
+    // Distances between points on a line, where the optimal tour has length 2 * (n - 1)
+    fn line_distances(n: usize) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|i| (0..n).map(|j| (i as f64 - j as f64).abs()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_two_opt_line() {
+        let distances = line_distances(8);
+        let tsp = TSP::new(distances.clone());
+        let tour = Permutation::new(vec![0, 5, 2, 7, 4, 1, 6, 3]);
+        let local_search = LocalSearch::new(distances, 7, LocalSearchOperator::TwoOpt);
+        let result = local_search.apply(&tour);
+        assert!(is_permutation(result.permutation()));
+        assert_eq!(tsp.evaluate(&result), 14.0);
+    }
+
+    #[test]
+    fn test_or_opt_moves_single_vertex() {
+        let distances = line_distances(8);
+        let tsp = TSP::new(distances.clone());
+        // Vertex 3 is placed on the way back, moving it between 2 and 4 is optimal
+        let tour = Permutation::new(vec![0, 1, 2, 4, 5, 6, 7, 3]);
+        let local_search = LocalSearch::new(distances, 7, LocalSearchOperator::OrOpt);
+        let result = local_search.apply(&tour);
+        assert!(is_permutation(result.permutation()));
+        assert_eq!(tsp.evaluate(&result), 14.0);
+    }
+
+    // Test that the local search never increases the tour length on berlin52
+    #[test]
+    fn test_local_search_berlin52() {
+        let tsp = TSP::from_euc2d(include_str!("./fitness/berlin52.tsp")).unwrap();
+        let tour = Permutation::new((0..tsp.num_cities()).collect());
+        let initial = tsp.evaluate(&tour);
+        for operator in [
+            LocalSearchOperator::TwoOpt,
+            LocalSearchOperator::OrOpt,
+            LocalSearchOperator::TwoOptOrOpt,
+        ] {
+            let local_search = LocalSearch::new(tsp.distances(), 10, operator);
+            let result = local_search.apply(&tour);
+            assert!(is_permutation(result.permutation()));
+            assert!(tsp.evaluate(&result) < initial);
+        }
+    }
+}
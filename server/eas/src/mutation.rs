@@ -7,15 +7,121 @@ pub trait Mutation<S: SearchSpace> {
     fn apply<R: MyRng>(&self, solution: &S, rng: &mut R) -> S;
 }
 
+// A local change of a search point, which can be applied and undone in place
+pub trait Move<S: SearchSpace> {
+    fn apply(&self, solution: &mut S);
+    fn undo(&self, solution: &mut S);
+}
+
+// Mutation operators that can sample the change as a move instead of constructing the offspring.
+// Together with a fitness function implementing DeltaEvaluation for the move,
+// this allows evaluating the offspring before deciding whether to apply the move
+pub trait MoveMutation<S: SearchSpace>: Mutation<S> {
+    type Move: Move<S>;
+    fn sample_move<R: MyRng>(&self, solution: &S, rng: &mut R) -> Self::Move;
+}
+
+// Moves on the permutation search space
+#[derive(Debug, Clone, PartialEq)]
+pub enum PermutationMove {
+    // Reverse the elements between positions i+1 and j, where i < j
+    TwoOpt {
+        i: usize,
+        j: usize,
+    },
+    // Swap the elements at positions i and j, where i < j
+    Swap {
+        i: usize,
+        j: usize,
+    },
+    // Replace the adjacent segments [a, b) and [b, c), where a < b < c,
+    // reversing the first and second segment if the flags are set and then exchanging them if exchange is set.
+    // Insertion, Or-opt and double-bridge moves are exchanges without reversal
+    ThreeOpt {
+        a: usize,
+        b: usize,
+        c: usize,
+        exchange: bool,
+        reverse_first: bool,
+        reverse_second: bool,
+    },
+    // Reorder the elements from position start,
+    // such that position start + k receives the element at position start + order[k]
+    Scramble {
+        start: usize,
+        order: Vec<usize>,
+    },
+}
+
+impl Move<Permutation> for PermutationMove {
+    fn apply(&self, solution: &mut Permutation) {
+        match *self {
+            PermutationMove::TwoOpt { i, j } => solution.reverse(i + 1, j),
+            PermutationMove::Swap { i, j } => solution.swap(i, j),
+            PermutationMove::ThreeOpt {
+                a,
+                b,
+                c,
+                exchange,
+                reverse_first,
+                reverse_second,
+            } => {
+                if reverse_first {
+                    solution.reverse(a, b - 1);
+                }
+                if reverse_second {
+                    solution.reverse(b, c - 1);
+                }
+                if exchange {
+                    solution.rotate_left(a, c - 1, b - a);
+                }
+            }
+            PermutationMove::Scramble { start, ref order } => solution.reorder(start, order),
+        }
+    }
+
+    fn undo(&self, solution: &mut Permutation) {
+        match *self {
+            // Reversing the segment or swapping the elements again restores the permutation
+            PermutationMove::TwoOpt { .. } | PermutationMove::Swap { .. } => self.apply(solution),
+            // Exchange the segments back before reversing them again
+            PermutationMove::ThreeOpt {
+                a,
+                b,
+                c,
+                exchange,
+                reverse_first,
+                reverse_second,
+            } => {
+                if exchange {
+                    solution.rotate_left(a, c - 1, c - b);
+                }
+                if reverse_first {
+                    solution.reverse(a, b - 1);
+                }
+                if reverse_second {
+                    solution.reverse(b, c - 1);
+                }
+            }
+            PermutationMove::Scramble { start, ref order } => {
+                let mut inverse = vec![0; order.len()];
+                for (k, &o) in order.iter().enumerate() {
+                    inverse[o] = k;
+                }
+                solution.reorder(start, &inverse);
+            }
+        }
+    }
+}
+
 // Simple bitflip mutation, flipping each bit with probability 1/n
 pub struct NaiveBitflip;
 
 impl Mutation<Bitstring> for NaiveBitflip {
     fn apply<R: MyRng>(&self, solution: &Bitstring, rng: &mut R) -> Bitstring {
         let bits = solution
-            .bits()
             .iter()
-            .map(|&b| {
+            .map(|b| {
                 let to_flip = rng.random_ratio(1, solution.size() as u32);
                 to_flip ^ b
             })
@@ -31,15 +137,106 @@ pub struct Bitflip;
 
 impl Mutation<Bitstring> for Bitflip {
     fn apply<R: MyRng>(&self, solution: &Bitstring, rng: &mut R) -> Bitstring {
-        let mut result = solution.clone();
-        let p = 1.0 / solution.size() as f64;
-        let mut i = 0;
+        flip_independently(solution, 1.0 / solution.size() as f64, rng).0
+    }
+}
+
+// Flip each bit independently with probability p,
+// using the geometric distribution to draw distance between bitflips from.
+// Returns the offspring and the number of flipped bits
+fn flip_independently<R: MyRng>(solution: &Bitstring, p: f64, rng: &mut R) -> (Bitstring, usize) {
+    let mut result = solution.clone();
+    let mut flips = 0;
+    let mut i = 0;
+    i += rng.sample_geometric(p) as usize;
+
+    while i < solution.size() {
+        result.flip(i);
+        flips += 1;
+        i += 1;
         i += rng.sample_geometric(p) as usize;
+    }
+    (result, flips)
+}
+
+// Standard bit mutation with shift, flipping a single random bit instead
+// if no bit was flipped, such that the offspring always differs from the parent
+// (Carvalho Pinto & Doerr, 2018)
+pub struct ShiftBitflip;
 
-        while i < solution.size() {
-            result.flip(i);
-            i += 1;
-            i += rng.sample_geometric(p) as usize;
+impl Mutation<Bitstring> for ShiftBitflip {
+    fn apply<R: MyRng>(&self, solution: &Bitstring, rng: &mut R) -> Bitstring {
+        let (mut result, flips) = flip_independently(solution, 1.0 / solution.size() as f64, rng);
+        if flips == 0 {
+            result.flip(rng.random_range(0..solution.size()));
+        }
+        result
+    }
+}
+
+// Standard bit mutation with resampling, repeating the mutation until at least one bit is flipped.
+// Unlike the shift variant, the number of flipped bits follows the binomial distribution conditioned on being positive
+pub struct ResamplingBitflip;
+
+impl Mutation<Bitstring> for ResamplingBitflip {
+    fn apply<R: MyRng>(&self, solution: &Bitstring, rng: &mut R) -> Bitstring {
+        loop {
+            let (result, flips) = flip_independently(solution, 1.0 / solution.size() as f64, rng);
+            if flips > 0 {
+                return result;
+            }
+        }
+    }
+}
+
+// Heavy-tailed mutation of the fast GA (Doerr et al., 2017), flipping each bit with probability alpha/n,
+// where the strength alpha is drawn from the power-law distribution with P(alpha = i) proportional to i^(-beta)
+// for i in 1..=n/2. Larger strengths are drawn with polynomial probability, helping to cross fitness valleys
+pub struct HeavyTailed {
+    // Cumulative unnormalized probabilities of the strengths 1..=n/2
+    cumulative: Vec<f64>,
+}
+
+impl HeavyTailed {
+    // Create the operator for bitstrings of the given size, with beta > 1 for a bounded expected strength
+    pub fn new(size: usize, beta: f64) -> Self {
+        let mut total = 0.0;
+        let cumulative = (1..=(size / 2).max(1))
+            .map(|i| {
+                total += (i as f64).powf(-beta);
+                total
+            })
+            .collect();
+        HeavyTailed { cumulative }
+    }
+
+    pub fn sample_strength<R: MyRng>(&self, rng: &mut R) -> usize {
+        let total = self.cumulative[self.cumulative.len() - 1];
+        let u = rng.random_range_float(0.0..total);
+        1 + self.cumulative.partition_point(|&c| c <= u)
+    }
+}
+
+impl Mutation<Bitstring> for HeavyTailed {
+    fn apply<R: MyRng>(&self, solution: &Bitstring, rng: &mut R) -> Bitstring {
+        let alpha = self.sample_strength(rng);
+        flip_independently(solution, alpha as f64 / solution.size() as f64, rng).0
+    }
+}
+
+// Mutation flipping exactly k distinct random bits, or all bits if k exceeds the size
+pub struct KBitflip(pub usize);
+
+impl Mutation<Bitstring> for KBitflip {
+    fn apply<R: MyRng>(&self, solution: &Bitstring, rng: &mut R) -> Bitstring {
+        let mut result = solution.clone();
+        let mut flipped = Vec::with_capacity(self.0);
+        while flipped.len() < self.0.min(solution.size()) {
+            let i = rng.random_range(0..solution.size());
+            if !flipped.contains(&i) {
+                result.flip(i);
+                flipped.push(i);
+            }
         }
         result
     }
@@ -58,36 +255,351 @@ impl Mutation<Bitstring> for SingleBitflip {
     }
 }
 
+// Mutation operators on the bitstring search space, allowing the operator to be chosen at runtime
+pub enum BitstringMutation {
+    NaiveBitflip,
+    Bitflip,
+    SingleBitflip,
+    ShiftBitflip,
+    ResamplingBitflip,
+    HeavyTailed(HeavyTailed),
+    KBitflip { k: usize },
+}
+
+impl Mutation<Bitstring> for BitstringMutation {
+    fn apply<R: MyRng>(&self, solution: &Bitstring, rng: &mut R) -> Bitstring {
+        match self {
+            BitstringMutation::NaiveBitflip => NaiveBitflip.apply(solution, rng),
+            BitstringMutation::Bitflip => Bitflip.apply(solution, rng),
+            BitstringMutation::SingleBitflip => SingleBitflip.apply(solution, rng),
+            BitstringMutation::ShiftBitflip => ShiftBitflip.apply(solution, rng),
+            BitstringMutation::ResamplingBitflip => ResamplingBitflip.apply(solution, rng),
+            BitstringMutation::HeavyTailed(mutation) => mutation.apply(solution, rng),
+            BitstringMutation::KBitflip { k } => KBitflip(*k).apply(solution, rng),
+        }
+    }
+}
+
 // 2-opt mutation using on the permutation search space
 // Used by both (1+1) EA and Simulated Annealing on TSP
 pub struct TwoOpt;
 
 impl Mutation<Permutation> for TwoOpt {
     fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
-        let previous = solution.permutation();
-        let a = rng.random_range(0..previous.len());
-        let mut b = a;
-        while b == a {
-            b = rng.random_range(0..previous.len());
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
+    }
+}
+
+impl MoveMutation<Permutation> for TwoOpt {
+    type Move = PermutationMove;
+
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        let (i, j) = sample_positions(solution, rng);
+        PermutationMove::TwoOpt { i, j }
+    }
+}
+
+// Swap mutation on the permutation search space, exchanging the elements at two random positions
+pub struct Swap;
+
+impl Mutation<Permutation> for Swap {
+    fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
+    }
+}
+
+impl MoveMutation<Permutation> for Swap {
+    type Move = PermutationMove;
+
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        let (i, j) = sample_positions(solution, rng);
+        PermutationMove::Swap { i, j }
+    }
+}
+
+// Insertion mutation on the permutation search space,
+// moving the element at a random position to another random position
+pub struct Insertion;
+
+impl Mutation<Permutation> for Insertion {
+    fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
+    }
+}
+
+impl MoveMutation<Permutation> for Insertion {
+    type Move = PermutationMove;
+
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        sample_segment_shift(solution, 1, rng)
+    }
+}
+
+// Maximum length of the segments moved by Or-opt
+const OR_OPT_MAX_LENGTH: usize = 3;
+
+// Or-opt mutation on the permutation search space,
+// moving a segment of 1 to 3 consecutive elements to another random position
+pub struct OrOpt;
+
+impl Mutation<Permutation> for OrOpt {
+    fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
+    }
+}
+
+impl MoveMutation<Permutation> for OrOpt {
+    type Move = PermutationMove;
+
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        let max_length = OR_OPT_MAX_LENGTH.min(solution.size() - 1);
+        let length = 1 + rng.random_range(0..max_length);
+        sample_segment_shift(solution, length, rng)
+    }
+}
+
+// Inversion mutation on the permutation search space,
+// reversing a random segment of between 2 and the given maximum number of elements.
+// Unlike 2-opt, which reverses segments of any length, this keeps the change local.
+// Requires a permutation of at least 2 elements, as given by PermutationMutation::min_size
+pub struct Inversion(pub usize);
+
+impl Mutation<Permutation> for Inversion {
+    fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
+    }
+}
+
+impl MoveMutation<Permutation> for Inversion {
+    type Move = PermutationMove;
+
+    // The segment starts at any position, including the first, so it is reversed as a reordering
+    // rather than a 2-opt move, which only reverses segments after its first position
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        let max_length = self.0.clamp(2, solution.size().max(2));
+        let length = 2 + rng.random_range(0..max_length - 1);
+        let start = rng.random_range(0..solution.size() - length + 1);
+        PermutationMove::Scramble {
+            start,
+            order: (0..length).rev().collect(),
+        }
+    }
+}
+
+// Scramble mutation on the permutation search space,
+// shuffling the elements between two random positions
+pub struct Scramble;
+
+impl Mutation<Permutation> for Scramble {
+    fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
+    }
+}
+
+impl MoveMutation<Permutation> for Scramble {
+    type Move = PermutationMove;
+
+    // The segment is shuffled using Fisher-Yates
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        let (start, end) = sample_positions(solution, rng);
+        let mut order = (0..=end - start).collect::<Vec<_>>();
+        for k in (1..order.len()).rev() {
+            let r = rng.random_range(0..k + 1);
+            order.swap(k, r);
+        }
+        PermutationMove::Scramble { start, order }
+    }
+}
+
+// Double-bridge mutation on the permutation search space,
+// cutting the permutation into four non-empty segments A B C D and reconnecting them as A C B D.
+// Commonly used as the kick of iterated local search for TSP, as it is hard to undo by 2-opt moves
+pub struct DoubleBridge;
+
+impl Mutation<Permutation> for DoubleBridge {
+    fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
+    }
+}
+
+impl MoveMutation<Permutation> for DoubleBridge {
+    type Move = PermutationMove;
+
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        let (a, b, c) = sample_cuts(1, solution, rng);
+        PermutationMove::ThreeOpt {
+            a,
+            b,
+            c,
+            exchange: true,
+            reverse_first: false,
+            reverse_second: false,
         }
-        let result = two_opt(previous, a, b);
-        Permutation::new(result)
     }
 }
 
-fn two_opt(previous: &Vec<usize>, a: usize, b: usize) -> Vec<usize> {
-    let (v1, v2) = if a > b { (b, a) } else { (a, b) };
-    let mut result = Vec::with_capacity(previous.len());
-    for v in &previous[0..=v1] {
-        result.push(*v);
+// Random 3-opt mutation on the permutation search space, removing three random edges
+// and reconnecting the segments in one of the four ways where none of the removed edges are restored.
+// With segments A B C D, these are A C B D, A C B' D, A C' B D and A B' C' D, where ' denotes reversal
+pub struct ThreeOpt;
+
+impl Mutation<Permutation> for ThreeOpt {
+    fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
     }
-    for i in ((v1 + 1)..=v2).rev() {
-        result.push(previous[i]);
+}
+
+impl MoveMutation<Permutation> for ThreeOpt {
+    type Move = PermutationMove;
+
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        let (a, b, c) = sample_cuts(0, solution, rng);
+        let (exchange, reverse_first, reverse_second) = match rng.random_range(0..4) {
+            0 => (true, false, false),
+            1 => (true, true, false),
+            2 => (true, false, true),
+            _ => (false, true, true),
+        };
+        PermutationMove::ThreeOpt {
+            a,
+            b,
+            c,
+            exchange,
+            reverse_first,
+            reverse_second,
+        }
     }
-    for v in &previous[(v2 + 1)..] {
-        result.push(*v);
+}
+
+// Mutation operators on the permutation search space, allowing the operator to be chosen at runtime
+#[derive(Debug, Clone, Copy)]
+pub enum PermutationMutation {
+    TwoOpt,
+    Swap,
+    Insertion,
+    OrOpt,
+    Inversion { max_length: usize },
+    Scramble,
+    DoubleBridge,
+    ThreeOpt,
+}
+
+impl PermutationMutation {
+    // Smallest permutation size on which the operator can sample a move
+    pub fn min_size(&self) -> usize {
+        match self {
+            PermutationMutation::TwoOpt
+            | PermutationMutation::Swap
+            | PermutationMutation::Insertion
+            | PermutationMutation::OrOpt
+            | PermutationMutation::Inversion { .. }
+            | PermutationMutation::Scramble => 2,
+            PermutationMutation::ThreeOpt => 3,
+            PermutationMutation::DoubleBridge => 4,
+        }
+    }
+}
+
+impl Mutation<Permutation> for PermutationMutation {
+    fn apply<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> Permutation {
+        let mut result = solution.clone();
+        self.sample_move(solution, rng).apply(&mut result);
+        result
+    }
+}
+
+impl MoveMutation<Permutation> for PermutationMutation {
+    type Move = PermutationMove;
+
+    fn sample_move<R: MyRng>(&self, solution: &Permutation, rng: &mut R) -> PermutationMove {
+        match *self {
+            PermutationMutation::TwoOpt => TwoOpt.sample_move(solution, rng),
+            PermutationMutation::Swap => Swap.sample_move(solution, rng),
+            PermutationMutation::Insertion => Insertion.sample_move(solution, rng),
+            PermutationMutation::OrOpt => OrOpt.sample_move(solution, rng),
+            PermutationMutation::Inversion { max_length } => {
+                Inversion(max_length).sample_move(solution, rng)
+            }
+            PermutationMutation::Scramble => Scramble.sample_move(solution, rng),
+            PermutationMutation::DoubleBridge => DoubleBridge.sample_move(solution, rng),
+            PermutationMutation::ThreeOpt => ThreeOpt.sample_move(solution, rng),
+        }
+    }
+}
+
+// Sample a move shifting a random segment of the given length to start at another random position,
+// as the exchange of the segment with the elements between it and its new position
+fn sample_segment_shift<R: MyRng>(
+    solution: &Permutation,
+    length: usize,
+    rng: &mut R,
+) -> PermutationMove {
+    let positions = solution.size() - length + 1;
+    let start = rng.random_range(0..positions);
+    let mut to = rng.random_range(0..positions - 1);
+    if to >= start {
+        to += 1;
+    }
+    let (a, b, c) = if to < start {
+        (to, start, start + length)
+    } else {
+        (start, start + length, to + length)
+    };
+    PermutationMove::ThreeOpt {
+        a,
+        b,
+        c,
+        exchange: true,
+        reverse_first: false,
+        reverse_second: false,
+    }
+}
+
+// Sample three distinct cut positions of at least the given minimum, returned in increasing order
+fn sample_cuts<R: MyRng>(
+    minimum: usize,
+    solution: &Permutation,
+    rng: &mut R,
+) -> (usize, usize, usize) {
+    let mut cuts = Vec::with_capacity(3);
+    while cuts.len() < 3 {
+        let cut = rng.random_range(minimum..solution.size());
+        if !cuts.contains(&cut) {
+            cuts.push(cut);
+        }
+    }
+    cuts.sort_unstable();
+    (cuts[0], cuts[1], cuts[2])
+}
+
+// Sample two distinct positions of the permutation, returned in increasing order
+fn sample_positions<R: MyRng>(solution: &Permutation, rng: &mut R) -> (usize, usize) {
+    let a = rng.random_range(0..solution.size());
+    let mut b = a;
+    while b == a {
+        b = rng.random_range(0..solution.size());
+    }
+    if a > b {
+        (b, a)
+    } else {
+        (a, b)
     }
-    result
 }
 
 // Test of the defined mutation operators
@@ -119,7 +631,7 @@ mod tests {
             let bitstring = Bitstring::from_bitstring(t.0).unwrap();
             let mut mock_rng = MockRng::new_ratio(bitstring_to_bools(t.1));
             let got = NaiveBitflip.apply(&bitstring, &mut mock_rng);
-            assert_eq!(*got.bits(), bitstring_to_bools(t.2))
+            assert_eq!(got.bits(), bitstring_to_bools(t.2))
         }
     }
 
@@ -137,7 +649,7 @@ mod tests {
             let bitstring = Bitstring::from_bitstring(t.0).unwrap();
             let mut mock_rng = MockRng::new_geometric(t.1);
             let got = Bitflip.apply(&bitstring, &mut mock_rng);
-            assert_eq!(*got.bits(), bitstring_to_bools(t.2))
+            assert_eq!(got.bits(), bitstring_to_bools(t.2))
         }
     }
 
@@ -154,7 +666,65 @@ mod tests {
             let bitstring = Bitstring::from_bitstring(t.0).unwrap();
             let mut mock_rng = MockRng::new_range(vec![t.1]);
             let got = SingleBitflip.apply(&bitstring, &mut mock_rng);
-            assert_eq!(*got.bits(), bitstring_to_bools(t.2))
+            assert_eq!(got.bits(), bitstring_to_bools(t.2))
+        }
+    }
+
+    #[test]
+    fn test_shift_bitflip() {
+        // (input, [flip distance(s)], [shifted bit], expected)
+        let testcases = vec![
+            ("0000000000", vec![10], vec![3], "0001000000"),
+            ("0000000000", vec![2, 10], vec![], "0010000000"),
+        ];
+
+        for t in testcases {
+            let bitstring = Bitstring::from_bitstring(t.0).unwrap();
+            let mut mock_rng = MockRng::new_geometric(t.1).with_range(t.2);
+            let got = ShiftBitflip.apply(&bitstring, &mut mock_rng);
+            assert_eq!(got.bits(), bitstring_to_bools(t.3))
+        }
+    }
+
+    #[test]
+    fn test_resampling_bitflip() {
+        let bitstring = Bitstring::from_bitstring("0000000000").unwrap();
+        let mut mock_rng = MockRng::new_geometric(vec![10, 10, 4, 10]);
+        let got = ResamplingBitflip.apply(&bitstring, &mut mock_rng);
+        assert_eq!(got.bits(), bitstring_to_bools("0000100000"))
+    }
+
+    #[test]
+    fn test_heavy_tailed() {
+        // Unnormalized probabilities 1, 2^(-1.5), 3^(-1.5) and 4^(-1.5) of the strengths 1 to 4,
+        // with cumulative values 1, 1.354, 1.546 and 1.671
+        let mutation = HeavyTailed::new(8, 1.5);
+        let mut mock_rng = MockRng::new_float(vec![0.5, 1.2, 1.5, 1.6]);
+        let strengths = (0..4)
+            .map(|_| mutation.sample_strength(&mut mock_rng))
+            .collect::<Vec<_>>();
+        assert_eq!(strengths, vec![1, 2, 3, 4]);
+
+        let bitstring = Bitstring::from_bitstring("00000000").unwrap();
+        let mut mock_rng = MockRng::new_float(vec![1.6]).with_geometric(vec![0, 3, 10]);
+        let got = mutation.apply(&bitstring, &mut mock_rng);
+        assert_eq!(got.bits(), bitstring_to_bools("10001000"))
+    }
+
+    #[test]
+    fn test_k_bitflip() {
+        // (input, k, [bit(s)], expected), where repeated bits are sampled again
+        let testcases = vec![
+            ("00000000", 3, vec![1, 5, 1, 6], "01000110"),
+            ("1111", 1, vec![2], "1101"),
+            ("101", 5, vec![0, 1, 2], "010"),
+        ];
+
+        for t in testcases {
+            let bitstring = Bitstring::from_bitstring(t.0).unwrap();
+            let mut mock_rng = MockRng::new_range(t.2);
+            let got = KBitflip(t.1).apply(&bitstring, &mut mock_rng);
+            assert_eq!(got.bits(), bitstring_to_bools(t.3))
         }
     }
 
@@ -165,4 +735,151 @@ mod tests {
         let result = TwoOpt.apply(&initial, &mut mock_rng);
         assert_eq!(*result.permutation(), vec![0, 1, 2, 3, 4, 5, 6, 7])
     }
+
+    #[test]
+    fn test_two_opt_move() {
+        let initial = Permutation::new(vec![0, 1, 4, 3, 2, 5, 6, 7]);
+        let mut mock_rng = MockRng::new_range(vec![4, 1]);
+        let m = TwoOpt.sample_move(&initial, &mut mock_rng);
+        assert_eq!(m, PermutationMove::TwoOpt { i: 1, j: 4 });
+
+        let mut result = initial.clone();
+        m.apply(&mut result);
+        assert_eq!(*result.permutation(), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        m.undo(&mut result);
+        assert_eq!(result, initial)
+    }
+
+    #[test]
+    fn test_swap() {
+        let initial = Permutation::new(vec![0, 1, 4, 3, 2, 5, 6, 7]);
+        let mut mock_rng = MockRng::new_range(vec![4, 4, 2]);
+        let m = Swap.sample_move(&initial, &mut mock_rng);
+        assert_eq!(m, PermutationMove::Swap { i: 2, j: 4 });
+
+        let mut result = initial.clone();
+        m.apply(&mut result);
+        assert_eq!(*result.permutation(), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        m.undo(&mut result);
+        assert_eq!(result, initial);
+
+        let mut mock_rng = MockRng::new_range(vec![7, 0]);
+        let result = Swap.apply(&initial, &mut mock_rng);
+        assert_eq!(*result.permutation(), vec![7, 1, 4, 3, 2, 5, 6, 0])
+    }
+
+    // Apply the move to the identity permutation and check the result, and that undoing it restores the identity
+    fn assert_move(m: &PermutationMove, expected: Vec<usize>) {
+        let initial = Permutation::new((0..8).collect());
+        let mut result = initial.clone();
+        m.apply(&mut result);
+        assert_eq!(*result.permutation(), expected, "{:?}", m);
+        m.undo(&mut result);
+        assert_eq!(result, initial, "{:?}", m)
+    }
+
+    #[test]
+    fn test_insertion() {
+        let initial = Permutation::new((0..8).collect());
+        // (random values, expected), where the element at the first position is moved to the second
+        let testcases = vec![
+            (vec![2, 5], vec![0, 1, 3, 4, 5, 6, 2, 7]),
+            (vec![6, 1], vec![0, 6, 1, 2, 3, 4, 5, 7]),
+            (vec![0, 6], vec![1, 2, 3, 4, 5, 6, 7, 0]),
+        ];
+        for t in testcases {
+            let mut mock_rng = MockRng::new_range(t.0);
+            assert_move(&Insertion.sample_move(&initial, &mut mock_rng), t.1);
+        }
+    }
+
+    #[test]
+    fn test_or_opt() {
+        let initial = Permutation::new((0..8).collect());
+        // (random values, expected), with the segment length minus one, its start and its new position
+        let testcases = vec![
+            (vec![2, 0, 4], vec![3, 4, 5, 6, 7, 0, 1, 2]),
+            (vec![1, 5, 2], vec![0, 1, 5, 6, 2, 3, 4, 7]),
+            (vec![0, 3, 3], vec![0, 1, 2, 4, 3, 5, 6, 7]),
+        ];
+        for t in testcases {
+            let mut mock_rng = MockRng::new_range(t.0);
+            assert_move(&OrOpt.sample_move(&initial, &mut mock_rng), t.1);
+        }
+    }
+
+    #[test]
+    fn test_inversion() {
+        let initial = Permutation::new((0..8).collect());
+        let mut mock_rng = MockRng::new_range(vec![1, 2]);
+        let m = Inversion(3).sample_move(&initial, &mut mock_rng);
+        assert_eq!(
+            m,
+            PermutationMove::Scramble {
+                start: 2,
+                order: vec![2, 1, 0]
+            }
+        );
+        assert_move(&m, vec![0, 1, 4, 3, 2, 5, 6, 7]);
+
+        // (max length, [length - 2, start], expected), where the length is bounded by the size of the permutation
+        let testcases = vec![
+            (3, vec![0, 0], vec![1, 0, 2, 3, 4, 5, 6, 7]),
+            (3, vec![0, 6], vec![0, 1, 2, 3, 4, 5, 7, 6]),
+            (100, vec![5, 0], vec![6, 5, 4, 3, 2, 1, 0, 7]),
+            (100, vec![6, 0], vec![7, 6, 5, 4, 3, 2, 1, 0]),
+        ];
+        for t in testcases {
+            let mut mock_rng = MockRng::new_range(t.1);
+            let mutation = PermutationMutation::Inversion { max_length: t.0 };
+            let result = mutation.apply(&initial, &mut mock_rng);
+            assert_eq!(*result.permutation(), t.2)
+        }
+
+        // The smallest permutation is reversed entirely
+        let mut mock_rng = MockRng::new_range(vec![0, 0]);
+        let result = Inversion(5).apply(&Permutation::new(vec![0, 1]), &mut mock_rng);
+        assert_eq!(*result.permutation(), vec![1, 0])
+    }
+
+    #[test]
+    fn test_scramble() {
+        let initial = Permutation::new((0..8).collect());
+        let mut mock_rng = MockRng::new_range(vec![5, 2, 0, 2, 0]);
+        let m = Scramble.sample_move(&initial, &mut mock_rng);
+        assert_eq!(
+            m,
+            PermutationMove::Scramble {
+                start: 2,
+                order: vec![1, 3, 2, 0]
+            }
+        );
+        assert_move(&m, vec![0, 1, 3, 5, 4, 2, 6, 7]);
+    }
+
+    #[test]
+    fn test_double_bridge() {
+        let initial = Permutation::new((0..8).collect());
+        // Repeated cuts are sampled again
+        let mut mock_rng = MockRng::new_range(vec![5, 2, 5, 7]);
+        let m = DoubleBridge.sample_move(&initial, &mut mock_rng);
+        assert_move(&m, vec![0, 1, 5, 6, 2, 3, 4, 7]);
+    }
+
+    #[test]
+    fn test_three_opt() {
+        let initial = Permutation::new((0..8).collect());
+        // (random values, expected), with the three cuts and the reconnection
+        let testcases = vec![
+            (vec![6, 0, 3, 0], vec![3, 4, 5, 0, 1, 2, 6, 7]),
+            (vec![6, 0, 3, 1], vec![3, 4, 5, 2, 1, 0, 6, 7]),
+            (vec![6, 0, 3, 2], vec![5, 4, 3, 0, 1, 2, 6, 7]),
+            (vec![6, 0, 3, 3], vec![2, 1, 0, 5, 4, 3, 6, 7]),
+            (vec![1, 7, 2, 1], vec![0, 2, 3, 4, 5, 6, 1, 7]),
+        ];
+        for t in testcases {
+            let mut mock_rng = MockRng::new_range(t.0);
+            assert_move(&ThreeOpt.sample_move(&initial, &mut mock_rng), t.1);
+        }
+    }
 }
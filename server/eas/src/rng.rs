@@ -75,6 +75,15 @@ pub struct MockRng {
 
     random_geometric_values: Vec<u64>,
     random_geometric_index: usize,
+
+    random_float_values: Vec<f64>,
+    random_float_index: usize,
+
+    random_bool_values: Vec<bool>,
+    random_bool_index: usize,
+
+    random_poisson_values: Vec<u64>,
+    random_poisson_index: usize,
 }
 
 #[cfg(test)]
@@ -102,15 +111,45 @@ impl MockRng {
             ..Default::default()
         }
     }
+
+    pub fn new_float(values: Vec<f64>) -> Self {
+        MockRng {
+            random_float_values: values,
+            random_float_index: 0,
+            ..Default::default()
+        }
+    }
+
+    // Set the values returned by random_range, for operators using several kinds of random numbers
+    pub fn with_range(mut self, values: Vec<usize>) -> Self {
+        self.random_range_values = values;
+        self.random_range_index = 0;
+        self
+    }
+
+    pub fn with_geometric(mut self, values: Vec<u64>) -> Self {
+        self.random_geometric_values = values;
+        self.random_geometric_index = 0;
+        self
+    }
+
+    // Set the values returned by both random and random_bool
+    pub fn with_bool(mut self, values: Vec<bool>) -> Self {
+        self.random_bool_values = values;
+        self.random_bool_index = 0;
+        self
+    }
 }
 
 impl MyRng for MockRng {
     fn random(&mut self) -> bool {
-        todo!()
+        self.random_bool(0.5)
     }
 
     fn random_bool(&mut self, _: f64) -> bool {
-        todo!()
+        let value = self.random_bool_values[self.random_bool_index];
+        self.random_bool_index += 1;
+        value
     }
 
     fn random_ratio(&mut self, _: u32, _: u32) -> bool {
@@ -132,14 +171,17 @@ impl MyRng for MockRng {
     }
 
     fn sample_poisson(&mut self) -> u64 {
-        todo!()
+        let value = self.random_poisson_values[self.random_poisson_index];
+        self.random_poisson_index += 1;
+        value
     }
 
-    fn shuffle_vec<T>(&mut self, _: &mut Vec<T>) {
-        todo!()
-    }
+    // Shuffling keeps the order, such that random permutations are the identity
+    fn shuffle_vec<T>(&mut self, _: &mut Vec<T>) {}
 
     fn random_range_float(&mut self, _: Range<f64>) -> f64 {
-        todo!()
+        let value = self.random_float_values[self.random_float_index];
+        self.random_float_index += 1;
+        value
     }
 }
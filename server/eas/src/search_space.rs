@@ -1,30 +1,90 @@
 use std::fmt::Debug;
 
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
 use crate::rng::MyRng;
 
 // Main search space trait used as solution candidate for algorithms
-pub trait SearchSpace: Debug + Clone {
+// Solutions are serializable, allowing the state of algorithms to be checkpointed
+pub trait SearchSpace: Debug + Clone + PartialEq + Serialize + DeserializeOwned {
     fn new_random<R: MyRng>(size: usize, rng: &mut R) -> Self;
     fn size(&self) -> usize;
     fn to_string(&self) -> String;
 }
 
-#[derive(Debug, Clone)]
+// Bitstring packed into 64-bit words, where bit i is stored at position i % 64 of word i / 64.
+// Unused bits of the last word are always zero, such that words can be compared and counted directly.
+// Serialized as a string of zeros and ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct Bitstring {
-    bits: Vec<bool>,
+    words: Vec<u64>,
+    len: usize,
 }
 
+const WORD_BITS: usize = u64::BITS as usize;
+
 impl Bitstring {
-    pub fn bits(&self) -> &Vec<bool> {
-        &self.bits
+    pub fn new(bits: Vec<bool>) -> Self {
+        let mut words = vec![0; bits.len().div_ceil(WORD_BITS)];
+        for (i, &b) in bits.iter().enumerate() {
+            if b {
+                words[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+            }
+        }
+        Bitstring {
+            words,
+            len: bits.len(),
+        }
     }
 
-    pub fn new(bits: Vec<bool>) -> Self {
-        Bitstring { bits }
+    pub fn get(&self, i: usize) -> bool {
+        assert!(
+            i < self.len,
+            "index {} out of range for bitstring of length {}",
+            i,
+            self.len
+        );
+        self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1
+    }
+
+    // Unpacked copy of the bits
+    pub fn bits(&self) -> Vec<bool> {
+        self.iter().collect()
+    }
+
+    // Iterate over the bits without unpacking them
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1)
     }
 
     pub fn flip(&mut self, i: usize) {
-        self.bits[i] = !self.bits[i];
+        assert!(
+            i < self.len,
+            "index {} out of range for bitstring of length {}",
+            i,
+            self.len
+        );
+        self.words[i / WORD_BITS] ^= 1 << (i % WORD_BITS);
+    }
+
+    // Number of ones using popcount on each word
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // Number of consecutive ones from the start of the bitstring,
+    // given by the trailing zeros of the complement of the first word that is not all ones
+    pub fn leading_ones(&self) -> usize {
+        let mut count = 0;
+        for word in &self.words {
+            let ones = (!word).trailing_zeros() as usize;
+            count += ones;
+            if ones < WORD_BITS {
+                break;
+            }
+        }
+        count.min(self.len)
     }
 
     pub fn from_bitstring(s: &str) -> Option<Self> {
@@ -37,7 +97,7 @@ impl Bitstring {
             })
             .collect::<Option<Vec<_>>>();
 
-        bitstring.map(|bits| Self { bits })
+        bitstring.map(Self::new)
     }
 }
 
@@ -47,22 +107,36 @@ impl SearchSpace for Bitstring {
         for bit in bits.iter_mut() {
             *bit = rng.random();
         }
-        Bitstring { bits }
+        Bitstring::new(bits)
     }
 
     fn size(&self) -> usize {
-        self.bits.len()
+        self.len
     }
 
     fn to_string(&self) -> String {
-        self.bits
-            .iter()
-            .map(|&b| if b { "1" } else { "0" })
-            .collect()
+        self.iter().map(|b| if b { "1" } else { "0" }).collect()
+    }
+}
+
+impl TryFrom<String> for Bitstring {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Bitstring::from_bitstring(&s)
+            .ok_or_else(|| "bitstring must only contain 0 and 1".to_string())
+    }
+}
+
+impl From<Bitstring> for String {
+    fn from(bitstring: Bitstring) -> Self {
+        bitstring.to_string()
     }
 }
 
-#[derive(Debug, Clone)]
+// Serialized as the list of elements, checking that it is a permutation when deserialized
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<usize>", into = "Vec<usize>")]
 pub struct Permutation {
     permutation: Vec<usize>,
 }
@@ -75,6 +149,51 @@ impl Permutation {
     pub fn new(permutation: Vec<usize>) -> Self {
         Permutation { permutation }
     }
+
+    // Reverse the elements between positions start and end (inclusive)
+    pub fn reverse(&mut self, start: usize, end: usize) {
+        self.permutation[start..=end].reverse();
+    }
+
+    // Swap the elements at positions i and j
+    pub fn swap(&mut self, i: usize, j: usize) {
+        self.permutation.swap(i, j);
+    }
+
+    // Rotate the elements between positions start and end (inclusive) k places to the left
+    pub fn rotate_left(&mut self, start: usize, end: usize, k: usize) {
+        self.permutation[start..=end].rotate_left(k);
+    }
+
+    // Reorder the elements from position start,
+    // such that position start + k receives the element at position start + order[k]
+    pub fn reorder(&mut self, start: usize, order: &[usize]) {
+        let segment = self.permutation[start..start + order.len()].to_vec();
+        for (k, &o) in order.iter().enumerate() {
+            self.permutation[start + k] = segment[o];
+        }
+    }
+}
+
+impl TryFrom<Vec<usize>> for Permutation {
+    type Error = String;
+
+    fn try_from(permutation: Vec<usize>) -> Result<Self, Self::Error> {
+        let mut seen = vec![false; permutation.len()];
+        for &v in &permutation {
+            if v >= permutation.len() || seen[v] {
+                return Err(format!("{} is out of range or repeated", v));
+            }
+            seen[v] = true;
+        }
+        Ok(Permutation { permutation })
+    }
+}
+
+impl From<Permutation> for Vec<usize> {
+    fn from(permutation: Permutation) -> Self {
+        permutation.permutation
+    }
 }
 
 impl SearchSpace for Permutation {
@@ -96,3 +215,94 @@ impl SearchSpace for Permutation {
             .join(", ")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bitstring of the given length with ones at the given positions
+    fn with_ones(len: usize, ones: &[usize]) -> Bitstring {
+        let mut bits = vec![false; len];
+        for &i in ones {
+            bits[i] = true;
+        }
+        Bitstring::new(bits)
+    }
+
+    #[test]
+    fn test_bitstring_word_boundary() {
+        // (length, ones, count_ones, leading_ones)
+        let testcases = vec![
+            (63, vec![], 0, 0),
+            (63, (0..63).collect(), 63, 63),
+            (63, vec![0, 1, 62], 3, 2),
+            (64, (0..64).collect(), 64, 64),
+            (64, vec![0, 63], 2, 1),
+            (65, (0..65).collect(), 65, 65),
+            (65, (0..64).collect(), 64, 64),
+            (65, vec![64], 1, 0),
+            (130, (0..70).chain([129]).collect(), 71, 70),
+        ];
+
+        for t in testcases {
+            let bitstring = with_ones(t.0, &t.1);
+            let expected = (0..t.0).map(|i| t.1.contains(&i)).collect::<Vec<_>>();
+            assert_eq!(bitstring.size(), t.0);
+            assert_eq!(bitstring.iter().collect::<Vec<_>>(), expected);
+            assert_eq!(bitstring.bits(), expected);
+            assert!((0..t.0).all(|i| bitstring.get(i) == expected[i]));
+            assert_eq!(bitstring.count_ones(), t.2);
+            assert_eq!(bitstring.leading_ones(), t.3);
+        }
+    }
+
+    #[test]
+    fn test_bitstring_flip() {
+        for len in [63, 64, 65] {
+            let mut bitstring = with_ones(len, &[]);
+            for i in [0, 31, len - 2, len - 1] {
+                bitstring.flip(i);
+                assert!(bitstring.get(i));
+            }
+            bitstring.flip(len - 1);
+            assert!(!bitstring.get(len - 1));
+            assert_eq!(bitstring, with_ones(len, &[0, 31, len - 2]));
+
+            // Flipping every bit fills the bitstring without touching the unused bits of the last word
+            let mut bitstring = with_ones(len, &[]);
+            for i in 0..len {
+                bitstring.flip(i);
+            }
+            assert_eq!(bitstring.count_ones(), len);
+            assert_eq!(bitstring.leading_ones(), len);
+            assert_eq!(bitstring, with_ones(len, &(0..len).collect::<Vec<_>>()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index 64 out of range for bitstring of length 64")]
+    fn test_bitstring_get_out_of_range() {
+        with_ones(64, &[]).get(64);
+    }
+
+    #[test]
+    fn test_bitstring_serde() {
+        for len in [0, 63, 64, 65] {
+            let bitstring = with_ones(len, &(0..len).step_by(3).collect::<Vec<_>>());
+            let json = serde_json::to_value(&bitstring).unwrap();
+            assert_eq!(json, serde_json::Value::String(bitstring.to_string()));
+            assert_eq!(
+                serde_json::from_value::<Bitstring>(json).unwrap(),
+                bitstring
+            );
+        }
+
+        let string = "1".repeat(64) + "0";
+        let bitstring = Bitstring::try_from(string.clone()).unwrap();
+        assert_eq!(bitstring.leading_ones(), 64);
+        assert_eq!(String::from(bitstring), string);
+
+        assert!(Bitstring::try_from("0120".to_string()).is_err());
+        assert!(serde_json::from_str::<Bitstring>("\"01x\"").is_err());
+    }
+}
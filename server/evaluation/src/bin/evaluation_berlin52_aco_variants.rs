@@ -0,0 +1,111 @@
+use std::time::{Duration, Instant};
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        ant_system::{AntSystem, AntSystemVariant},
+        mmas::{MMAStsp, PheromoneUpdateStrategy},
+    },
+    fitness::tsp::TSP,
+};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 20;
+const MAX_MS: u64 = 1000;
+const SEED: u64 = 5512093;
+
+// Compare MMAS against Ant Colony System, rank-based AS and elitist AS on berlin52,
+// using a fixed time budget per run
+fn main() {
+    let mut rng = Pcg64::seed_from_u64(SEED);
+
+    let mut fitness_mmas = Vec::with_capacity(REPETITIONS);
+    for _ in 0..REPETITIONS {
+        let tsp = TSP::from_euc2d(include_str!("./berlin52.tsp")).unwrap();
+        let start = Instant::now();
+        let size = tsp.num_cities();
+        let mut mmas = MMAStsp::new(
+            tsp.distances(),
+            tsp,
+            size,
+            25,
+            1.0,
+            5.0,
+            0.02,
+            PheromoneUpdateStrategy::GenerationBest,
+            true,
+            0.0,
+            0.0,
+            &mut rng,
+        );
+        while start.elapsed() < Duration::from_millis(MAX_MS) {
+            mmas.iterate(&mut rng);
+        }
+        fitness_mmas.push(mmas.current_fitness());
+    }
+    println!("MMAS:");
+    report(&fitness_mmas);
+
+    let variants = [
+        (
+            "Ant Colony System",
+            AntSystemVariant::AntColonySystem { q0: 0.9, xi: 0.1 },
+            10,
+            0.1,
+        ),
+        (
+            "Rank-based AS",
+            AntSystemVariant::RankBased { ranks: 6 },
+            52,
+            0.1,
+        ),
+        (
+            "Elitist AS",
+            AntSystemVariant::Elitist {
+                elitist_weight: 52.0,
+            },
+            52,
+            0.5,
+        ),
+    ];
+    for (name, variant, ants, evap_factor) in variants {
+        let mut fitness = Vec::with_capacity(REPETITIONS);
+        for _ in 0..REPETITIONS {
+            let tsp = TSP::from_euc2d(include_str!("./berlin52.tsp")).unwrap();
+            let start = Instant::now();
+            let size = tsp.num_cities();
+            let mut aco = AntSystem::new(
+                tsp.distances(),
+                tsp,
+                size,
+                ants,
+                1.0,
+                2.0,
+                evap_factor,
+                variant,
+                true,
+                &mut rng,
+            );
+            while start.elapsed() < Duration::from_millis(MAX_MS) {
+                aco.iterate(&mut rng);
+            }
+            fitness.push(aco.current_fitness());
+        }
+        println!("{}:", name);
+        report(&fitness);
+    }
+}
+
+fn report(fitness: &[f64]) {
+    let optimum = fitness.iter().filter(|&&x| x == 7542.0).count();
+    println!(
+        "Avg: {}",
+        fitness.iter().sum::<f64>() / fitness.len() as f64
+    );
+    println!("Optimum: {}", optimum);
+    for x in fitness {
+        print!("{}\\\\ ", x);
+    }
+    println!("\n");
+}
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        genetic_algorithm::{GeneticAlgorithm, Replacement, Selection},
+    },
+    crossover::{
+        Crossover, CycleCrossover, EdgeRecombination, OrderCrossover, PartiallyMappedCrossover,
+    },
+    fitness::tsp::TSP,
+    mutation::TwoOpt,
+    search_space::Permutation,
+};
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 20;
+const MAX_MS: u64 = 1000;
+const SEED: u64 = 7734120;
+
+// Compare the permutation crossover operators of the genetic algorithm on berlin52,
+// using a fixed time budget per run
+fn main() {
+    let mut rng = Pcg64::seed_from_u64(SEED);
+
+    println!("Order crossover (OX):");
+    report(&run(|| OrderCrossover, &mut rng));
+    println!("Partially mapped crossover (PMX):");
+    report(&run(|| PartiallyMappedCrossover, &mut rng));
+    println!("Cycle crossover (CX):");
+    report(&run(|| CycleCrossover, &mut rng));
+    println!("Edge recombination (ERX):");
+    report(&run(|| EdgeRecombination, &mut rng));
+}
+
+fn run<C, G, R>(crossover: G, rng: &mut R) -> Vec<f64>
+where
+    C: Crossover<Permutation>,
+    G: Fn() -> C,
+    R: Rng,
+{
+    let mut fitness = Vec::with_capacity(REPETITIONS);
+    for _ in 0..REPETITIONS {
+        let tsp = TSP::from_euc2d(include_str!("./berlin52.tsp")).unwrap();
+        let start = Instant::now();
+        let mut ga = GeneticAlgorithm::new(
+            tsp.num_cities(),
+            100,
+            0.9,
+            Selection::Tournament(2),
+            Replacement::Generational { elitism: 1 },
+            crossover(),
+            TwoOpt,
+            tsp,
+            rng,
+        );
+        while start.elapsed() < Duration::from_millis(MAX_MS) {
+            ga.iterate(rng);
+        }
+        fitness.push(ga.current_fitness());
+    }
+    fitness
+}
+
+fn report(fitness: &[f64]) {
+    let optimum = fitness.iter().filter(|&&x| x == 7542.0).count();
+    println!(
+        "Avg: {}",
+        fitness.iter().sum::<f64>() / fitness.len() as f64
+    );
+    println!("Optimum: {}", optimum);
+    for x in fitness {
+        print!("{}\\\\ ", x);
+    }
+    println!("\n");
+}
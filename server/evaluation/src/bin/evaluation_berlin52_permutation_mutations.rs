@@ -0,0 +1,67 @@
+use std::time::Instant;
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        one_plus_one_ea::OnePlusOneEA,
+        simulated_annealing::{CoolingSchedule, SimulatedAnnealing},
+    },
+    fitness::tsp::TSP,
+    mutation::PermutationMutation,
+};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 20;
+const ITERATIONS: u64 = 200_000;
+const SEED: u64 = 5252;
+
+// Compare the permutation mutation operators with (1+1) EA and SA on berlin52,
+// running each for a fixed number of iterations
+fn main() {
+    let tsp = || TSP::from_euc2d(include_str!("./berlin52.tsp")).unwrap();
+    let size = tsp().num_cities();
+    let mutations = [
+        ("2-opt", PermutationMutation::TwoOpt),
+        ("swap", PermutationMutation::Swap),
+        ("insertion", PermutationMutation::Insertion),
+        ("Or-opt", PermutationMutation::OrOpt),
+        (
+            "inversion (10)",
+            PermutationMutation::Inversion { max_length: 10 },
+        ),
+        ("scramble", PermutationMutation::Scramble),
+        ("double-bridge", PermutationMutation::DoubleBridge),
+        ("3-opt", PermutationMutation::ThreeOpt),
+    ];
+    println!(
+        "Iterations: {}, repetitions: {}, optimum: 7542",
+        ITERATIONS, REPETITIONS
+    );
+
+    let start = Instant::now();
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    println!("mutation, (1+1) EA average, SA average");
+    for (name, mutation) in mutations {
+        let mut ea = 0.0;
+        let mut sa = 0.0;
+        for _ in 0..REPETITIONS {
+            let mut one_plus_one = OnePlusOneEA::new(size, mutation, tsp(), &mut rng);
+            let cooling = CoolingSchedule::from_max_iterations_tsp(size as u64, ITERATIONS);
+            let mut annealing = SimulatedAnnealing::new(size, mutation, tsp(), cooling, &mut rng);
+            for _ in 0..ITERATIONS {
+                one_plus_one.iterate(&mut rng);
+                annealing.iterate(&mut rng);
+            }
+            ea += one_plus_one.current_fitness();
+            sa += annealing.current_fitness();
+        }
+        println!(
+            "{}, {:.1}, {:.1}",
+            name,
+            ea / REPETITIONS as f64,
+            sa / REPETITIONS as f64
+        );
+    }
+    println!("Time elapsed: {:?}", start.elapsed());
+}
@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        mmas::{MMAStsp, PheromoneUpdateStrategy},
+    },
+    fitness::{FitnessFunction, tsp::TSP},
+};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 10;
+const MAX_MS: u64 = 1000;
+const SEED: u64 = 7542;
+
+// Run MMAS on berlin52 until the length of the optimal tour from TSPLIB is reached or the time runs out,
+// and write the best tour found in the TSPLIB TOUR format, checking that it can be read back
+fn main() {
+    let tsp = TSP::from_euc2d(include_str!("./berlin52.tsp")).unwrap();
+    let optimal_tour = tsp
+        .tour_from_tsplib(include_str!("./berlin52.opt.tour"))
+        .unwrap();
+    let optimum = tsp.evaluate(&optimal_tour);
+    println!("Optimum: {}", optimum);
+
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let mut best = None;
+    let mut optimum_found = 0;
+    for _ in 0..REPETITIONS {
+        let start = Instant::now();
+        let size = tsp.num_cities();
+        let mut mmas = MMAStsp::new(
+            tsp.distances(),
+            TSP::new(tsp.distances()),
+            size,
+            25,
+            1.0,
+            5.0,
+            0.02,
+            PheromoneUpdateStrategy::GenerationBest,
+            true,
+            0.0,
+            0.0,
+            &mut rng,
+        );
+        while mmas.current_fitness() > optimum && start.elapsed() < Duration::from_millis(MAX_MS) {
+            mmas.iterate(&mut rng);
+        }
+        if mmas.current_fitness() == optimum {
+            optimum_found += 1;
+        }
+        println!(
+            "{} after {} iterations",
+            mmas.current_fitness(),
+            mmas.iterations()
+        );
+        if best
+            .as_ref()
+            .is_none_or(|(_, fitness)| mmas.current_fitness() < *fitness)
+        {
+            best = Some((mmas.state.current_solution.clone(), mmas.current_fitness()));
+        }
+    }
+    println!("Optimum found: {}\n", optimum_found);
+
+    let (tour, fitness) = best.unwrap();
+    let output = tsp.tour_to_tsplib("berlin52.best.tour", &tour);
+    let read_back = tsp.tour_from_tsplib(&output).unwrap();
+    assert_eq!(tsp.evaluate(&read_back), fitness);
+    print!("{}", output);
+}
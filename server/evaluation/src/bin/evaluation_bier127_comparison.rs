@@ -172,6 +172,43 @@ fn main() {
         print!("{}\\\\ ", x);
     }
     println!("\n");
+
+    let mut fitness_mmas_4 = Vec::with_capacity(REPETITIONS);
+    for _ in 0..REPETITIONS {
+        let tsp = TSP::from_euc2d(include_str!("./bier127.tsp")).unwrap();
+        let start = Instant::now();
+        let size = tsp.num_cities();
+        let mut mmas = MMAStsp::new(
+            tsp.distances(),
+            tsp,
+            size,
+            50,
+            1.0,
+            5.0,
+            0.02,
+            PheromoneUpdateStrategy::GenerationBest,
+            true,
+            0.0,
+            2693.0,
+            &mut rng,
+        )
+        .with_candidate_lists(20);
+        while start.elapsed() < Duration::from_millis(MAX_MS) {
+            mmas.iterate(&mut rng);
+        }
+        fitness_mmas_4.push(mmas.current_fitness());
+    }
+
+    let optimum_mmas_4 = fitness_mmas_4
+        .iter()
+        .fold(0, |acc, x| if *x == 118282.0 { acc + 1 } else { acc });
+    println!("MMAS Size borders + fitness deposit + candidate lists (k = 20):");
+    println!("Avg: {}", average(&fitness_mmas_4));
+    println!("Optimum: {}", optimum_mmas_4);
+    for x in fitness_mmas_4 {
+        print!("{}\\\\ ", x);
+    }
+    println!("\n");
 }
 
 fn average(numbers: &[f64]) -> f64 {
@@ -0,0 +1,91 @@
+use std::time::Instant;
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        mmas::{MMAStsp, PheromoneUpdateStrategy},
+        one_plus_one_ea::OnePlusOneEA,
+        simulated_annealing::{CoolingSchedule, SimulatedAnnealing},
+    },
+    fitness::tsp::TSP,
+    mutation::TwoOpt,
+};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 10;
+const MAX_EVALUATIONS: u64 = 250_000;
+const SEED: u64 = 1253132;
+
+// Compare (1+1) EA, SA and MMAS on bier127 using the same number of fitness evaluations,
+// where an MMAS iteration evaluates the tour of every ant
+fn main() {
+    let tsp = || TSP::from_euc2d(include_str!("./bier127.tsp")).unwrap();
+    let size = tsp().num_cities();
+    println!(
+        "Evaluations: {}, repetitions: {}, optimum: 118282",
+        MAX_EVALUATIONS, REPETITIONS
+    );
+
+    let start = Instant::now();
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let results = vec![
+        (
+            "(1+1) EA",
+            run(&mut rng, |rng| OnePlusOneEA::new(size, TwoOpt, tsp(), rng)),
+        ),
+        (
+            "SA",
+            run(&mut rng, |rng| {
+                let cooling = CoolingSchedule::new_default_tsp(size as u64, 20.0);
+                SimulatedAnnealing::new(size, TwoOpt, tsp(), cooling, rng)
+            }),
+        ),
+        (
+            "MMAS",
+            run(&mut rng, |rng| {
+                let tsp = tsp();
+                MMAStsp::new(
+                    tsp.distances(),
+                    tsp,
+                    size,
+                    50,
+                    1.0,
+                    5.0,
+                    0.02,
+                    PheromoneUpdateStrategy::GenerationBest,
+                    true,
+                    0.0,
+                    2693.0,
+                    rng,
+                )
+            }),
+        ),
+    ];
+    println!("Time elapsed: {:?}\n", start.elapsed());
+
+    println!("algorithm, average fitness, average iterations");
+    for (name, runs) in results {
+        let fitness = runs.iter().map(|&(f, _)| f).sum::<f64>() / REPETITIONS as f64;
+        let iterations = runs.iter().map(|&(_, i)| i).sum::<u64>() / REPETITIONS as u64;
+        println!("{}, {:.1}, {}", name, fitness, iterations);
+    }
+}
+
+// Run the algorithm until the evaluation budget is used,
+// returning the final fitness and number of iterations of each repetition
+fn run<A, C>(rng: &mut Pcg64, create: C) -> Vec<(f64, u64)>
+where
+    A: AlgorithmCore,
+    C: Fn(&mut Pcg64) -> A,
+{
+    (0..REPETITIONS)
+        .map(|_| {
+            let mut algorithm = create(rng);
+            while algorithm.evaluations() < MAX_EVALUATIONS {
+                algorithm.iterate(rng);
+            }
+            (algorithm.current_fitness(), algorithm.iterations())
+        })
+        .collect()
+}
@@ -0,0 +1,81 @@
+use std::time::Instant;
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        one_plus_one_ea::OnePlusOneEA,
+        simulated_annealing::{CoolingSchedule, SimulatedAnnealing},
+    },
+    fitness::{DeltaEvaluation, FitnessFunction, tsp::TSP},
+    mutation::{Move, MoveMutation, Mutation, TwoOpt},
+    search_space::{Permutation, SearchSpace},
+};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const ITERATIONS: u64 = 2_000_000;
+const SEED: u64 = 4242;
+
+// Compare the number of iterations per second of randomized local search with 2-opt on bier127
+// when evaluating each offspring fully and when using delta evaluation of the 2-opt move,
+// as well as the throughput of (1+1) EA and SA which use delta evaluation on TSP
+fn main() {
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let tsp = TSP::from_euc2d(include_str!("./bier127.tsp")).unwrap();
+    let size = tsp.num_cities();
+
+    // Full evaluation of each offspring
+    let start = Instant::now();
+    let mut current = Permutation::new_random(size, &mut rng);
+    let mut current_fitness = tsp.evaluate(&current);
+    for _ in 0..ITERATIONS {
+        let offspring = TwoOpt.apply(&current, &mut rng);
+        let fitness = tsp.evaluate(&offspring);
+        if fitness < current_fitness {
+            current = offspring;
+            current_fitness = fitness;
+        }
+    }
+    report("RLS, full evaluation", start, current_fitness);
+
+    // Delta evaluation, only applying improving moves
+    let start = Instant::now();
+    let mut current = Permutation::new_random(size, &mut rng);
+    let mut current_fitness = tsp.evaluate(&current);
+    for _ in 0..ITERATIONS {
+        let m = TwoOpt.sample_move(&current, &mut rng);
+        let delta = tsp.delta(&current, &m);
+        if delta < 0.0 {
+            m.apply(&mut current);
+            current_fitness += delta;
+        }
+    }
+    assert_eq!(current_fitness, tsp.evaluate(&current));
+    report("RLS, delta evaluation", start, current_fitness);
+
+    let start = Instant::now();
+    let mut one_plus_one = OnePlusOneEA::new(size, TwoOpt, tsp, &mut rng);
+    while one_plus_one.iterations() < ITERATIONS {
+        one_plus_one.iterate(&mut rng);
+    }
+    report("(1+1) EA", start, one_plus_one.current_fitness());
+
+    let tsp = TSP::from_euc2d(include_str!("./bier127.tsp")).unwrap();
+    let cooling = CoolingSchedule::from_max_iterations_tsp(size as u64, ITERATIONS);
+    let start = Instant::now();
+    let mut sa = SimulatedAnnealing::new(size, TwoOpt, tsp, cooling, &mut rng);
+    while sa.iterations() < ITERATIONS {
+        sa.iterate(&mut rng);
+    }
+    report("SA", start, sa.current_fitness());
+}
+
+fn report(name: &str, start: Instant, fitness: f64) {
+    let seconds = start.elapsed().as_secs_f64();
+    println!(
+        "{}: {:.0} iterations per second, final fitness {}",
+        name,
+        ITERATIONS as f64 / seconds,
+        fitness
+    );
+}
@@ -0,0 +1,63 @@
+use std::time::Instant;
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        mmas::{MMAStsp, PheromoneUpdateStrategy},
+    },
+    fitness::tsp::TSP,
+};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 10;
+const ITERATIONS: u64 = 500;
+const SEED: u64 = 6630141;
+
+// Measure the time per iteration and resulting fitness of MMAS on bier127
+// when constructing tours with and without nearest neighbor candidate lists
+fn main() {
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let candidate_sizes = [None, Some(40), Some(20), Some(10)];
+
+    println!("candidates, ms per iteration, average fitness");
+    for k in candidate_sizes {
+        let mut total_ms = 0.0;
+        let mut total_fitness = 0.0;
+        for _ in 0..REPETITIONS {
+            let tsp = TSP::from_euc2d(include_str!("./bier127.tsp")).unwrap();
+            let size = tsp.num_cities();
+            let mmas = MMAStsp::new(
+                tsp.distances(),
+                tsp,
+                size,
+                50,
+                1.0,
+                5.0,
+                0.02,
+                PheromoneUpdateStrategy::GenerationBest,
+                true,
+                0.0,
+                2693.0,
+                &mut rng,
+            );
+            let mut mmas = match k {
+                Some(k) => mmas.with_candidate_lists(k),
+                None => mmas,
+            };
+            let start = Instant::now();
+            while mmas.iterations() < ITERATIONS {
+                mmas.iterate(&mut rng);
+            }
+            total_ms += start.elapsed().as_secs_f64() * 1000.0;
+            total_fitness += mmas.current_fitness();
+        }
+        let name = k.map_or("all".to_string(), |k| k.to_string());
+        println!(
+            "{}, {:.3}, {:.1}",
+            name,
+            total_ms / (REPETITIONS as f64 * ITERATIONS as f64),
+            total_fitness / REPETITIONS as f64
+        );
+    }
+}
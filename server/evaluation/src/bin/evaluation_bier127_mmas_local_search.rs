@@ -0,0 +1,95 @@
+use std::time::Instant;
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        mmas::{LocalSearchScope, MMAStsp, PheromoneUpdateStrategy},
+    },
+    fitness::tsp::TSP,
+    local_search::{LocalSearch, LocalSearchOperator},
+};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 10;
+const ITERATIONS: u64 = 500;
+const SEED: u64 = 6630141;
+const OPTIMUM: f64 = 118282.0;
+
+// Measure the resulting fitness and number of runs reaching the optimum of MMAS on bier127
+// with and without local search applied as daemon action
+fn main() {
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let configurations = [
+        ("none", None),
+        (
+            "2-opt, iteration best",
+            Some((LocalSearchOperator::TwoOpt, LocalSearchScope::IterationBest)),
+        ),
+        (
+            "2-opt, all ants",
+            Some((LocalSearchOperator::TwoOpt, LocalSearchScope::AllAnts)),
+        ),
+        (
+            "2-opt + Or-opt, iteration best",
+            Some((
+                LocalSearchOperator::TwoOptOrOpt,
+                LocalSearchScope::IterationBest,
+            )),
+        ),
+        (
+            "2-opt + Or-opt, all ants",
+            Some((LocalSearchOperator::TwoOptOrOpt, LocalSearchScope::AllAnts)),
+        ),
+    ];
+
+    println!("local search, ms per iteration, average fitness, optimum reached");
+    for (name, configuration) in configurations {
+        let mut total_ms = 0.0;
+        let mut total_fitness = 0.0;
+        let mut optimum_reached = 0;
+        for _ in 0..REPETITIONS {
+            let tsp = TSP::from_euc2d(include_str!("./bier127.tsp")).unwrap();
+            let size = tsp.num_cities();
+            let distances = tsp.distances();
+            let mmas = MMAStsp::new(
+                distances.clone(),
+                tsp,
+                size,
+                25,
+                1.0,
+                2.0,
+                0.2,
+                PheromoneUpdateStrategy::GenerationBest,
+                true,
+                0.0,
+                2693.0,
+                &mut rng,
+            )
+            .with_candidate_lists(20);
+            let mut mmas = match configuration {
+                Some((operator, scope)) => {
+                    mmas.with_local_search(LocalSearch::new(distances, 10, operator), scope)
+                }
+                None => mmas,
+            };
+            let start = Instant::now();
+            while mmas.iterations() < ITERATIONS && mmas.current_fitness() > OPTIMUM {
+                mmas.iterate(&mut rng);
+            }
+            total_ms += start.elapsed().as_secs_f64() * 1000.0 / mmas.iterations() as f64;
+            total_fitness += mmas.current_fitness();
+            if mmas.current_fitness() <= OPTIMUM {
+                optimum_reached += 1;
+            }
+        }
+        println!(
+            "{}, {:.3}, {:.1}, {}/{}",
+            name,
+            total_ms / REPETITIONS as f64,
+            total_fitness / REPETITIONS as f64,
+            optimum_reached,
+            REPETITIONS
+        );
+    }
+}
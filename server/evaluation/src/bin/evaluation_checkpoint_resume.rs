@@ -0,0 +1,194 @@
+use std::fs;
+
+use eas::{
+    algorithms::{
+        AlgorithmCore,
+        ant_system::{AntSystem, AntSystemVariant},
+        eda::{CompactGA, PBIL},
+        genetic_algorithm::{GeneticAlgorithm, Replacement, Selection},
+        mmas::{MMASbs, MMASqap, MMAStsp, PheromoneUpdateStrategy},
+        mu_plus_lambda_ea::{DuplicateHandling, MuPlusLambdaEA, TieBreaking},
+        one_comma_lambda_ea::{OffspringPopulation, OneCommaLambdaEA},
+        one_plus_lambda_lambda_ga::OnePlusLambdaLambdaGA,
+        one_plus_one_ea::OnePlusOneEA,
+        simulated_annealing::{CoolingSchedule, SimulatedAnnealing},
+    },
+    crossover::OrderCrossover,
+    fitness::{one_max::OneMax, qap::QAP, tsp::TSP},
+    mutation::{Bitflip, SingleBitflip, Swap, TwoOpt},
+};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+use serde_json::json;
+
+const SEED: u64 = 1234;
+const OTHER_SEED: u64 = 5678;
+
+// Check that saving a checkpoint to disk and restoring it continues identically to an uninterrupted run.
+// Each algorithm is run for `before + after` iterations, and compared with a run that is checkpointed
+// after `before` iterations and resumed from the checkpoint on a newly created algorithm
+fn main() {
+    let tsp = || TSP::from_euc2d(include_str!("./bier127.tsp")).unwrap();
+    // Distances between the cities of bier127 with flows along a cycle
+    let qap = || {
+        let flows = (0..127)
+            .map(|i| {
+                (0..127)
+                    .map(|j| if j == (i + 1) % 127 { 1.0 } else { 0.0 })
+                    .collect()
+            })
+            .collect();
+        QAP::new(flows, tsp().distances())
+    };
+
+    check_resume("(1+1) EA, bier127", 20_000, 20_000, |rng| {
+        let tsp = tsp();
+        OnePlusOneEA::new(tsp.num_cities(), TwoOpt, tsp, rng)
+    });
+    check_resume("(mu+lambda) EA, OneMax", 500, 500, |rng| {
+        MuPlusLambdaEA::new(
+            500,
+            10,
+            10,
+            TieBreaking::Random,
+            DuplicateHandling::Reject,
+            Bitflip,
+            OneMax,
+            rng,
+        )
+    });
+    check_resume("(1+(l,l)) GA, OneMax", 500, 500, |rng| {
+        OnePlusLambdaLambdaGA::new(500, OffspringPopulation::new_one_fifth(), OneMax, rng)
+    });
+    check_resume("(1,lambda) EA, OneMax", 2_000, 2_000, |rng| {
+        OneCommaLambdaEA::new(
+            500,
+            OffspringPopulation::new_one_fifth(),
+            Bitflip,
+            OneMax,
+            rng,
+        )
+    });
+    check_resume("SA, bier127", 100_000, 100_000, |rng| {
+        let tsp = tsp();
+        let cooling = CoolingSchedule::new_default_tsp(tsp.num_cities() as u64, 20.0);
+        SimulatedAnnealing::new(tsp.num_cities(), TwoOpt, tsp, cooling, rng)
+    });
+    check_resume("SA, OneMax", 10_000, 10_000, |rng| {
+        let cooling = CoolingSchedule::new_default_bitstring(500, 1.0);
+        SimulatedAnnealing::new(500, SingleBitflip, OneMax, cooling, rng)
+    });
+    check_resume("GA, bier127", 200, 200, |rng| {
+        let tsp = tsp();
+        GeneticAlgorithm::new(
+            tsp.num_cities(),
+            50,
+            0.8,
+            Selection::Tournament(2),
+            Replacement::Generational { elitism: 1 },
+            OrderCrossover,
+            TwoOpt,
+            tsp,
+            rng,
+        )
+    });
+    check_resume("cGA, OneMax", 5_000, 5_000, |rng| {
+        CompactGA::new(OneMax, 500, 50.0, rng)
+    });
+    check_resume("PBIL, OneMax", 200, 200, |rng| {
+        PBIL::new(OneMax, 500, 10, 50, 0.1, rng)
+    });
+    check_resume("MMASbs, OneMax", 500, 500, |rng| {
+        MMASbs::new(OneMax, 500, 1, 1.0, 0.1, rng)
+    });
+    check_resume("MMAStsp, bier127", 50, 50, |rng| {
+        let tsp = tsp();
+        let size = tsp.num_cities();
+        MMAStsp::new(
+            tsp.distances(),
+            tsp,
+            size,
+            25,
+            1.0,
+            2.0,
+            0.2,
+            PheromoneUpdateStrategy::GenerationBest,
+            false,
+            0.05,
+            0.0,
+            rng,
+        )
+        .with_candidate_lists(20)
+    });
+    check_resume("MMASqap, QAP", 50, 50, |rng| {
+        MMASqap::new(qap(), 127, 10, 1.0, 0.2, rng)
+    });
+    check_resume("(1+1) EA, QAP", 20_000, 20_000, |rng| {
+        OnePlusOneEA::new(127, Swap, qap(), rng)
+    });
+    check_resume("ACS, bier127", 50, 50, |rng| {
+        let tsp = tsp();
+        let size = tsp.num_cities();
+        AntSystem::new(
+            tsp.distances(),
+            tsp,
+            size,
+            10,
+            1.0,
+            2.0,
+            0.1,
+            AntSystemVariant::AntColonySystem { q0: 0.9, xi: 0.1 },
+            true,
+            rng,
+        )
+    });
+}
+
+fn check_resume<A, C>(name: &str, before: u64, after: u64, create: C)
+where
+    A: AlgorithmCore,
+    C: Fn(&mut Pcg64) -> A,
+{
+    // Uninterrupted run
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let mut uninterrupted = create(&mut rng);
+    for _ in 0..(before + after) {
+        uninterrupted.iterate(&mut rng);
+    }
+
+    // Run that is checkpointed to disk
+    let path = std::env::temp_dir().join("eas_checkpoint.json");
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let mut interrupted = create(&mut rng);
+    for _ in 0..before {
+        interrupted.iterate(&mut rng);
+    }
+    let checkpoint = json!({
+        "algorithm": interrupted.checkpoint(),
+        // The RNG state contains 128-bit integers, which are stored as a string
+        "rng": serde_json::to_string(&rng).unwrap(),
+    });
+    fs::write(&path, serde_json::to_string(&checkpoint).unwrap()).unwrap();
+    let size = fs::metadata(&path).unwrap().len();
+
+    // Resume from the checkpoint on an algorithm created with a different initial state
+    let checkpoint: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    let mut rng: Pcg64 = serde_json::from_str(checkpoint["rng"].as_str().unwrap()).unwrap();
+    let mut resumed = create(&mut Pcg64::seed_from_u64(OTHER_SEED));
+    resumed.restore(&checkpoint["algorithm"]).unwrap();
+    for _ in 0..after {
+        resumed.iterate(&mut rng);
+    }
+    fs::remove_file(&path).unwrap();
+
+    let identical = resumed.checkpoint() == uninterrupted.checkpoint();
+    println!(
+        "{:<24} checkpoint: {:>8} bytes, uninterrupted: {:>12.2}, resumed: {:>12.2}, identical state: {}",
+        name,
+        size,
+        uninterrupted.current_fitness(),
+        resumed.current_fitness(),
+        identical
+    );
+}
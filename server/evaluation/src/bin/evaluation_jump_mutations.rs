@@ -0,0 +1,66 @@
+use std::time::Instant;
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::one_plus_one_ea::OnePlusOneEA;
+use eas::fitness::FitnessFunction;
+use eas::fitness::jump::Jump;
+use eas::mutation::{BitstringMutation, HeavyTailed};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 20;
+const MAX_ITERATIONS: u64 = 10_000_000;
+const SEED: u64 = 4242;
+
+// Creates the mutation operator for a given problem size
+type CreateMutation = fn(usize) -> BitstringMutation;
+
+// Compare the bitstring mutation operators with the (1+1) EA on Jump_k,
+// reporting the average number of iterations until the optimum is found
+fn main() {
+    let n = 30;
+    let start = Instant::now();
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    println!(
+        "n: {}, repetitions: {}, max iterations: {}",
+        n, REPETITIONS, MAX_ITERATIONS
+    );
+    println!("k, mutation, successful runs, average iterations of successful runs");
+    for k in [2, 3, 4] {
+        let mutations: Vec<(&str, CreateMutation)> = vec![
+            ("standard", |_| BitstringMutation::Bitflip),
+            ("shift", |_| BitstringMutation::ShiftBitflip),
+            ("resampling", |_| BitstringMutation::ResamplingBitflip),
+            ("heavy-tailed (1.5)", |n| {
+                BitstringMutation::HeavyTailed(HeavyTailed::new(n, 1.5))
+            }),
+            ("heavy-tailed (3.0)", |n| {
+                BitstringMutation::HeavyTailed(HeavyTailed::new(n, 3.0))
+            }),
+        ];
+        for (name, mutation) in mutations {
+            let optimum = Jump::new(k).optimal_fitness(n).unwrap();
+            let mut successful = 0;
+            let mut iterations = 0;
+            for _ in 0..REPETITIONS {
+                let mut ea = OnePlusOneEA::new(n, mutation(n), Jump::new(k), &mut rng);
+                while ea.current_fitness() < optimum && ea.iterations() < MAX_ITERATIONS {
+                    ea.iterate(&mut rng);
+                }
+                if ea.current_fitness() == optimum {
+                    successful += 1;
+                    iterations += ea.iterations();
+                }
+            }
+            println!(
+                "{}, {}, {}/{}, {:.0}",
+                k,
+                name,
+                successful,
+                REPETITIONS,
+                iterations as f64 / successful.max(1) as f64
+            );
+        }
+    }
+    println!("Time elapsed: {:?}", start.elapsed());
+}
@@ -0,0 +1,73 @@
+use std::time::Instant;
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::one_plus_one_ea::OnePlusOneEA;
+use eas::algorithms::simulated_annealing::{CoolingSchedule, SimulatedAnnealing};
+use eas::fitness::knapsack::{ConstraintHandling, Correlation, Knapsack, KnapsackInstance};
+use eas::mutation::{Bitflip, SingleBitflip};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 20;
+const ITERATIONS: u64 = 20_000;
+const SIZE: usize = 100;
+const RANGE: u64 = 1000;
+const SEED: u64 = 31415;
+
+// Compare how the constraint handling strategies of the knapsack problem interact with
+// standard bit mutation ((1+1) EA) and single bitflips (RLS) on generated instances.
+// Reports the number of runs that ended feasible and the average ratio of the optimal value reached
+fn main() {
+    let start = Instant::now();
+    println!(
+        "Items: {}, range: {}, iterations: {}, repetitions: {}",
+        SIZE, RANGE, ITERATIONS, REPETITIONS
+    );
+    println!("instance, strategy, algorithm, feasible runs, average ratio of optimum");
+
+    for correlation in [
+        Correlation::Uncorrelated,
+        Correlation::WeaklyCorrelated,
+        Correlation::StronglyCorrelated,
+    ] {
+        let instance = KnapsackInstance::generate(SIZE, RANGE, correlation, SEED);
+        let optimum = instance.optimum_value().unwrap();
+        for strategy in [
+            ConstraintHandling::Penalty,
+            ConstraintHandling::Repair,
+            ConstraintHandling::Death,
+        ] {
+            let knapsack = || Knapsack::new(instance.clone(), strategy);
+            let mut rng = Pcg64::seed_from_u64(SEED);
+            let mut results = vec![];
+            for _ in 0..REPETITIONS {
+                let ea = OnePlusOneEA::new(SIZE, Bitflip, knapsack(), &mut rng);
+                results.push(("(1+1) EA", run(ea, &mut rng)));
+                let c = CoolingSchedule::new_static(0.0);
+                let rls = SimulatedAnnealing::new(SIZE, SingleBitflip, knapsack(), c, &mut rng);
+                results.push(("RLS", run(rls, &mut rng)));
+            }
+            for name in ["(1+1) EA", "RLS"] {
+                let runs = results.iter().filter(|(n, _)| *n == name).map(|(_, r)| r);
+                let feasible = runs.clone().filter(|r| r.is_some()).count();
+                let ratio =
+                    runs.map(|r| r.unwrap_or(0.0) / optimum).sum::<f64>() / REPETITIONS as f64;
+                println!(
+                    "{:?}, {:?}, {}, {}/{}, {:.4}",
+                    correlation, strategy, name, feasible, REPETITIONS, ratio
+                );
+            }
+        }
+    }
+    println!("Time elapsed: {:?}", start.elapsed());
+}
+
+// Run the algorithm for a fixed number of iterations, returning the value of the final solution
+// if it is feasible. Infeasible solutions have negative fitness with both penalty and death penalty,
+// while repair always yields the value of the repaired feasible solution
+fn run<A: AlgorithmCore>(mut algorithm: A, rng: &mut Pcg64) -> Option<f64> {
+    for _ in 0..ITERATIONS {
+        algorithm.iterate(rng);
+    }
+    Some(algorithm.current_fitness()).filter(|&fitness| fitness >= 0.0)
+}
@@ -0,0 +1,106 @@
+use std::{env, fs, time::Instant};
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::mmas::MMASbs;
+use eas::algorithms::one_plus_one_ea::OnePlusOneEA;
+use eas::algorithms::simulated_annealing::{CoolingSchedule, SimulatedAnnealing};
+use eas::fitness::max_sat::MaxSat;
+use eas::mutation::{Bitflip, SingleBitflip};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 20;
+const MAX_ITERATIONS: u64 = 200_000;
+const SEED: u64 = 424242;
+
+// Compare (1+1) EA, RLS, SA and MMAS on a satisfiable MAX-SAT instance,
+// such as the uniform random 3-SAT instances (uf20, uf250, ...) from SATLIB.
+// Usage: evaluation_maxsat_1p1_sa_mmas [path to .cnf file], defaulting to a random 3-SAT instance with 50 variables
+fn main() {
+    let input = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(path).expect("failed to read cnf file"),
+        None => include_str!("./random3sat50.cnf").to_string(),
+    };
+    let formula = || MaxSat::from_dimacs(&input).unwrap();
+    let variables = formula().num_variables();
+    let clauses = formula().num_clauses();
+    println!("Variables: {}, clauses: {}", variables, clauses);
+    println!(
+        "Repetitions: {}, max iterations: {}",
+        REPETITIONS, MAX_ITERATIONS
+    );
+
+    let start = Instant::now();
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let results = vec![
+        (
+            "(1+1) EA",
+            run(clauses, || {
+                OnePlusOneEA::new(variables, Bitflip, formula(), &mut rng)
+            }),
+        ),
+        (
+            "RLS",
+            run(clauses, || {
+                let c = CoolingSchedule::new_static(0.0);
+                SimulatedAnnealing::new(variables, SingleBitflip, formula(), c, &mut rng)
+            }),
+        ),
+        (
+            "SA",
+            run(clauses, || {
+                let c = CoolingSchedule::new_default_bitstring(variables as u64, 1.0);
+                SimulatedAnnealing::new(variables, SingleBitflip, formula(), c, &mut rng)
+            }),
+        ),
+        (
+            "MMAS",
+            run(clauses, || {
+                MMASbs::new(formula(), variables, 1, 1.0, 0.1, &mut rng)
+            }),
+        ),
+    ];
+    println!("Time elapsed: {:?}\n", start.elapsed());
+
+    println!(
+        "algorithm, satisfied runs, average satisfied clauses, average iterations of satisfied runs"
+    );
+    for (name, (satisfied_runs, average_satisfied, average_iterations)) in results {
+        println!(
+            "{}, {}/{}, {:.2}, {:.2}",
+            name, satisfied_runs, REPETITIONS, average_satisfied, average_iterations
+        );
+    }
+}
+
+// Run the algorithm until all clauses are satisfied or the iteration limit is reached.
+// Returns the number of runs satisfying the formula, the average number of satisfied clauses
+// and the average number of iterations of the runs satisfying the formula
+fn run<A, C>(clauses: usize, mut create: C) -> (usize, f64, f64)
+where
+    A: AlgorithmCore,
+    C: FnMut() -> A,
+{
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let mut satisfied_runs = 0;
+    let mut total_satisfied = 0.0;
+    let mut total_iterations = 0;
+    for _ in 0..REPETITIONS {
+        let mut algorithm = create();
+        while algorithm.current_fitness() < clauses as f64
+            && algorithm.iterations() < MAX_ITERATIONS
+        {
+            algorithm.iterate(&mut rng);
+        }
+        total_satisfied += algorithm.current_fitness();
+        if algorithm.current_fitness() == clauses as f64 {
+            satisfied_runs += 1;
+            total_iterations += algorithm.iterations();
+        }
+    }
+    (
+        satisfied_runs,
+        total_satisfied / REPETITIONS as f64,
+        total_iterations as f64 / satisfied_runs.max(1) as f64,
+    )
+}
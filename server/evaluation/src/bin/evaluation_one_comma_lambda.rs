@@ -0,0 +1,73 @@
+use std::time::Instant;
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::one_comma_lambda_ea::{OffspringPopulation, OneCommaLambdaEA};
+use eas::fitness::FitnessFunction;
+use eas::fitness::leading_ones::LeadingOnes;
+use eas::fitness::one_max::OneMax;
+use eas::mutation::Bitflip;
+use eas::search_space::Bitstring;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 50;
+const SEED: u64 = 90215573;
+
+// Compares the self-adjusting (1,lambda) EA using the one-fifth success rule (s = 4)
+// against a smaller success ratio (s = 1/2). For s = 4 the runtime on OneMax becomes exponential
+fn main() {
+    let problem_sizes = [25, 50, 75, 100];
+    let success_ratios = [0.5, 4.0];
+    let mut rng = Pcg64::seed_from_u64(SEED);
+
+    let start = Instant::now();
+
+    println!("problem, s, n, average generations, average evaluations");
+    for s in success_ratios {
+        for n in problem_sizes {
+            let (generations, evaluations) = average_runtime(n, s, || OneMax, &mut rng);
+            println!(
+                "OneMax, {}, {}, {:.2}, {:.2}",
+                s, n, generations, evaluations
+            );
+        }
+        for n in problem_sizes {
+            let (generations, evaluations) = average_runtime(n, s, || LeadingOnes, &mut rng);
+            println!(
+                "LeadingOnes, {}, {}, {:.2}, {:.2}",
+                s, n, generations, evaluations
+            );
+        }
+    }
+
+    let duration = start.elapsed();
+    println!("Time elapsed: {:?}", duration);
+    println!("Repetitions: {}", REPETITIONS);
+    println!("Seed: {}", SEED);
+}
+
+// Average number of generations and fitness evaluations until the optimum n is found
+fn average_runtime<F, G, R>(size: usize, success_ratio: f64, fitness: G, rng: &mut R) -> (f64, f64)
+where
+    F: FitnessFunction<Bitstring>,
+    G: Fn() -> F,
+    R: Rng,
+{
+    let mut avg_generations = 0.0;
+    let mut avg_evaluations = 0.0;
+    for _ in 0..REPETITIONS {
+        let population = OffspringPopulation::SelfAdjusting {
+            update_factor: 1.5,
+            success_ratio,
+        };
+        let mut ea = OneCommaLambdaEA::new(size, population, Bitflip, fitness(), rng);
+        let mut evaluations = 0;
+        while ea.current_fitness() != size as f64 {
+            evaluations += ea.offspring_population_size();
+            ea.iterate(rng);
+        }
+        avg_generations += (ea.iterations() as f64) / (REPETITIONS as f64);
+        avg_evaluations += (evaluations as f64) / (REPETITIONS as f64);
+    }
+    (avg_generations, avg_evaluations)
+}
@@ -0,0 +1,60 @@
+use std::time::Instant;
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::eda::{CompactGA, PBIL, UMDA};
+use eas::fitness::one_max::OneMax;
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 50;
+const SEED: u64 = 31415926;
+
+// Average number of iterations of cGA, UMDA and PBIL until the optimum of OneMax is found.
+// K = sqrt(n) ln(n) for cGA, and lambda = sqrt(n) ln(n), mu = lambda / 2 for UMDA and PBIL
+fn main() {
+    let problem_sizes = vec![100, 200, 500, 1000];
+    let mut averages = vec![];
+    let mut rng = Pcg64::seed_from_u64(SEED);
+
+    let start = Instant::now();
+
+    for n in problem_sizes {
+        let k = (n as f64).sqrt() * (n as f64).ln();
+        let lambda = k.ceil() as usize;
+        let mu = lambda / 2;
+
+        let mut avg_cga = 0.0;
+        let mut avg_umda = 0.0;
+        let mut avg_pbil = 0.0;
+        for _ in 0..REPETITIONS {
+            let mut cga = CompactGA::new(OneMax, n, k, &mut rng);
+            while cga.current_fitness() != n as f64 {
+                cga.iterate(&mut rng);
+            }
+            avg_cga += (cga.iterations() as f64) / (REPETITIONS as f64);
+
+            let mut umda = UMDA::new(OneMax, n, mu, lambda, &mut rng);
+            while umda.current_fitness() != n as f64 {
+                umda.iterate(&mut rng);
+            }
+            avg_umda += (umda.iterations() as f64) / (REPETITIONS as f64);
+
+            let mut pbil = PBIL::new(OneMax, n, mu, lambda, 0.5, &mut rng);
+            while pbil.current_fitness() != n as f64 {
+                pbil.iterate(&mut rng);
+            }
+            avg_pbil += (pbil.iterations() as f64) / (REPETITIONS as f64);
+        }
+        averages.push((n, avg_cga, avg_umda, avg_pbil));
+    }
+
+    let duration = start.elapsed();
+    println!("Time elapsed: {:?}", duration);
+    println!("Repetitions: {}", REPETITIONS);
+    println!("Seed: {}", SEED);
+
+    println!("n, average cGA, average UMDA, average PBIL");
+    for (n, a, b, c) in &averages {
+        println!("{}, {:.2}, {:.2}, {:.2}", n, a, b, c);
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Instant;
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::mu_plus_lambda_ea::{DuplicateHandling, MuPlusLambdaEA, TieBreaking};
+use eas::fitness::one_max::OneMax;
+use eas::mutation::Bitflip;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 50;
+const SEED: u64 = 4817263;
+
+fn main() {
+    let problem_sizes = [100, 200, 500, 1000];
+    let population_sizes = [(1, 1), (1, 10), (10, 1), (10, 10)];
+    let mut averages = vec![];
+    let mut rng = Pcg64::seed_from_u64(SEED);
+
+    let start = Instant::now();
+
+    for (mu, lambda) in population_sizes {
+        let mut size_averages = vec![];
+        for n in problem_sizes {
+            let mut avg = 0.0;
+            for _ in 0..REPETITIONS {
+                let iterations = mu_plus_lambda_onemax_optimize(n, mu, lambda, &mut rng);
+                avg += (iterations as f64) / (REPETITIONS as f64);
+            }
+            size_averages.push((n, avg));
+        }
+        averages.push((mu, lambda, size_averages));
+    }
+
+    let duration = start.elapsed();
+    println!("Time elapsed: {:?}", duration);
+    println!("Repetitions: {}", REPETITIONS);
+    println!("Seed: {}", SEED);
+    println!("Results (generations):");
+
+    for (mu, lambda, size_averages) in averages {
+        println!("\n(mu, lambda) = ({}, {})", mu, lambda);
+        for (n, avg) in size_averages {
+            print!("({}, {:.2}) ", n, avg);
+        }
+    }
+    println!();
+}
+
+fn mu_plus_lambda_onemax_optimize<R: Rng>(
+    size: usize,
+    mu: usize,
+    lambda: usize,
+    rng: &mut R,
+) -> u64 {
+    let mut ea = MuPlusLambdaEA::new(
+        size,
+        mu,
+        lambda,
+        TieBreaking::PreferOffspring,
+        DuplicateHandling::Allow,
+        Bitflip,
+        OneMax,
+        rng,
+    );
+    while ea.current_fitness() != size as f64 {
+        ea.iterate(rng);
+    }
+    ea.iterations()
+}
@@ -0,0 +1,60 @@
+use std::time::Instant;
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::one_comma_lambda_ea::OffspringPopulation;
+use eas::algorithms::one_plus_lambda_lambda_ga::OnePlusLambdaLambdaGA;
+use eas::algorithms::one_plus_one_ea::OnePlusOneEA;
+use eas::fitness::one_max::OneMax;
+use eas::mutation::Bitflip;
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 10;
+const SEED: u64 = 31415;
+
+// Compare the number of fitness evaluations until the optimum of OneMax is found
+// for the (1+1) EA and the (1+(lambda,lambda)) GA with static and self-adjusting lambda.
+// The static lambda uses the optimal choice sqrt(ln n) of a fixed lambda
+fn main() {
+    let start = Instant::now();
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    println!("repetitions: {}", REPETITIONS);
+    println!("n, (1+1) EA, (1+(l,l)) GA static, (1+(l,l)) GA self-adjusting");
+    for n in [500, 1000, 2000, 4000] {
+        let mut one_plus_one = 0;
+        let mut static_lambda = 0;
+        let mut self_adjusting = 0;
+        for _ in 0..REPETITIONS {
+            let mut ea = OnePlusOneEA::new(n, Bitflip, OneMax, &mut rng);
+            while ea.current_fitness() < n as f64 {
+                ea.iterate(&mut rng);
+            }
+            // One evaluation per iteration, and one of the initial solution
+            one_plus_one += ea.iterations() + 1;
+
+            let lambda = ((n as f64).ln().sqrt().round() as usize).max(1);
+            let population = OffspringPopulation::Static(lambda);
+            static_lambda += run_until_optimum(n, population, &mut rng);
+            let population = OffspringPopulation::new_one_fifth();
+            self_adjusting += run_until_optimum(n, population, &mut rng);
+        }
+        let average = |evaluations: u64| evaluations as f64 / REPETITIONS as f64;
+        println!(
+            "{}, {:.0}, {:.0}, {:.0}",
+            n,
+            average(one_plus_one),
+            average(static_lambda),
+            average(self_adjusting)
+        );
+    }
+    println!("Time elapsed: {:?}", start.elapsed());
+}
+
+// Run the (1+(lambda,lambda)) GA until the optimum is found, returning the number of evaluations
+fn run_until_optimum(n: usize, population: OffspringPopulation, rng: &mut Pcg64) -> u64 {
+    let mut ga = OnePlusLambdaLambdaGA::new(n, population, OneMax, rng);
+    while ga.current_fitness() < n as f64 {
+        ga.iterate(rng);
+    }
+    ga.evaluations()
+}
@@ -0,0 +1,99 @@
+use std::{env, fs, time::Instant};
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::mmas::MMASqap;
+use eas::algorithms::one_plus_one_ea::OnePlusOneEA;
+use eas::algorithms::simulated_annealing::{CoolingSchedule, SimulatedAnnealing};
+use eas::fitness::qap::QAP;
+use eas::mutation::Swap;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 10;
+const SEED: u64 = 9001;
+
+// Compare (1+1) EA and SA using swap mutation with MMAS on a QAP instance, using the same number of evaluations.
+// Usage: evaluation_qap_1p1_sa_mmas [path to QAPLIB .dat file],
+// defaulting to a random instance of size 30 with uniform flows and distances in [0, 100)
+fn main() {
+    let input = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(path).expect("failed to read QAPLIB file"),
+        None => random_instance(30),
+    };
+    let qap = || QAP::from_qaplib(&input).unwrap();
+    let size = qap().size();
+    let ants = 10;
+    let evaluations = 1_000 * size as u64 * ants;
+    println!(
+        "Size: {}, evaluations: {}, repetitions: {}",
+        size, evaluations, REPETITIONS
+    );
+
+    let start = Instant::now();
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let results = vec![
+        (
+            "(1+1) EA",
+            run(evaluations, &mut rng, |rng| {
+                OnePlusOneEA::new(size, Swap, qap(), rng)
+            }),
+        ),
+        (
+            "SA",
+            run(evaluations, &mut rng, |rng| {
+                let c = CoolingSchedule::from_max_iterations_tsp(size as u64, evaluations);
+                SimulatedAnnealing::new(size, Swap, qap(), c, rng)
+            }),
+        ),
+        (
+            "MMAS",
+            run(evaluations / ants, &mut rng, |rng| {
+                MMASqap::new(qap(), size, ants as usize, 1.0, 0.2, rng)
+            }),
+        ),
+    ];
+    println!("Time elapsed: {:?}\n", start.elapsed());
+
+    println!("algorithm, best, average, worst");
+    for (name, fitness) in results {
+        let best = fitness.iter().copied().fold(f64::INFINITY, f64::min);
+        let worst = fitness.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let average = fitness.iter().sum::<f64>() / fitness.len() as f64;
+        println!("{}, {}, {:.1}, {}", name, best, average, worst);
+    }
+}
+
+// Run the algorithm for a fixed number of iterations, returning the final fitness of each repetition
+fn run<A, C>(iterations: u64, rng: &mut Pcg64, create: C) -> Vec<f64>
+where
+    A: AlgorithmCore,
+    C: Fn(&mut Pcg64) -> A,
+{
+    (0..REPETITIONS)
+        .map(|_| {
+            let mut algorithm = create(rng);
+            for _ in 0..iterations {
+                algorithm.iterate(rng);
+            }
+            algorithm.current_fitness()
+        })
+        .collect()
+}
+
+// Random instance in the QAPLIB format with zero diagonals
+fn random_instance(size: usize) -> String {
+    let mut rng = Pcg64::seed_from_u64(SEED);
+    let mut text = format!("{}\n", size);
+    for _ in 0..2 {
+        text.push('\n');
+        for i in 0..size {
+            let row = (0..size)
+                .map(|j| if i == j { 0 } else { rng.random_range(0..100) })
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>();
+            text.push_str(&row.join(" "));
+            text.push('\n');
+        }
+    }
+    text
+}
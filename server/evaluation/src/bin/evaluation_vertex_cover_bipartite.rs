@@ -0,0 +1,57 @@
+use std::time::Instant;
+
+use eas::algorithms::AlgorithmCore;
+use eas::algorithms::one_plus_one_ea::OnePlusOneEA;
+use eas::fitness::graph::Graph;
+use eas::fitness::vertex_cover::VertexCover;
+use eas::mutation::Bitflip;
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+const REPETITIONS: usize = 50;
+const MAX_ITERATIONS: u64 = 200_000;
+const SEED: u64 = 2718;
+
+// Run the (1+1) EA on the complete bipartite graph K_(εn, (1-ε)n) for minimum vertex cover,
+// counting how often it finds the optimal cover (the smaller side) and how often it gets stuck on the larger side
+fn main() {
+    let n = 60;
+    let start = Instant::now();
+    let mut rng = Pcg64::seed_from_u64(SEED);
+
+    println!(
+        "n: {}, repetitions: {}, max iterations: {}",
+        n, REPETITIONS, MAX_ITERATIONS
+    );
+    println!("epsilon, optimal runs, larger side runs, average iterations of optimal runs");
+    for epsilon in [0.1, 0.2, 0.3, 0.4] {
+        let left = (epsilon * n as f64) as usize;
+        let right = n - left;
+        let mut optimal = 0;
+        let mut larger_side = 0;
+        let mut iterations = 0;
+        for _ in 0..REPETITIONS {
+            let fitness = VertexCover::new(Graph::complete_bipartite(left, right));
+            let mut ea = OnePlusOneEA::new(n, Bitflip, fitness, &mut rng);
+            while ea.current_fitness() > left as f64 && ea.iterations() < MAX_ITERATIONS {
+                ea.iterate(&mut rng);
+            }
+            if ea.current_fitness() == left as f64 {
+                optimal += 1;
+                iterations += ea.iterations();
+            } else if ea.current_fitness() == right as f64 {
+                larger_side += 1;
+            }
+        }
+        println!(
+            "{}, {}/{}, {}/{}, {:.2}",
+            epsilon,
+            optimal,
+            REPETITIONS,
+            larger_side,
+            REPETITIONS,
+            iterations as f64 / optimal.max(1) as f64
+        );
+    }
+    println!("Time elapsed: {:?}", start.elapsed());
+}
@@ -1,49 +1,234 @@
 use axum::{
+    Json,
     http::StatusCode,
     response::{IntoResponse, Response},
 };
 use eas::{
     algorithms::{
-        Algorithm,
-        mmas::{MMASbs, MMAStsp, PheromoneUpdateStrategy},
+        Algorithm, AlgorithmCore, CheckpointError,
+        ant_system::{AntSystem, AntSystemVariant},
+        eda::{CompactGA, PBIL, UMDA},
+        genetic_algorithm::{self, GeneticAlgorithm, Replacement},
+        mmas::{self, MMASbs, MMASqap, MMAStsp, PheromoneUpdateStrategy},
+        mu_plus_lambda_ea::{self, MuPlusLambdaEA},
+        one_comma_lambda_ea::{OffspringPopulation, OneCommaLambdaEA},
+        one_plus_lambda_lambda_ga::OnePlusLambdaLambdaGA,
         one_plus_one_ea::OnePlusOneEA,
         simulated_annealing::{CoolingSchedule, SimulatedAnnealing},
     },
-    fitness::{leading_ones::LeadingOnes, one_max::OneMax, tsp::TSP},
-    mutation::{Bitflip, SingleBitflip, TwoOpt},
+    crossover::{
+        CycleCrossover, EdgeRecombination, KPointCrossover, OnePointCrossover, OrderCrossover,
+        PartiallyMappedCrossover, UniformCrossover,
+    },
+    fitness::{
+        FitnessFunction,
+        bin_val::BinVal,
+        dimacs,
+        graph::Graph,
+        jump::Jump,
+        knapsack::{self, Knapsack},
+        leading_ones::LeadingOnes,
+        linear::Linear,
+        max_cut::MaxCut,
+        max_sat::MaxSat,
+        nk_landscape::{self, NKLandscape},
+        one_max::OneMax,
+        plateau::Plateau,
+        qap::QAP,
+        qaplib,
+        ridge::Ridge,
+        royal_road::RoyalRoad,
+        trap::Trap,
+        tsp::TSP,
+        tsplib::ParseError,
+        two_max::TwoMax,
+        vertex_cover::VertexCover,
+    },
+    local_search::{self, LocalSearch},
+    mutation::{BitstringMutation, HeavyTailed, PermutationMutation},
+    search_space::{Bitstring, Permutation},
 };
 use rand::Rng;
 use rand_pcg::Pcg64;
+use serde_json::json;
 
-use crate::{AlgorithmConfig, Problem, Task, UpdateStrategy};
+use crate::{
+    AcoVariant, AlgorithmConfig, Checkpoint, CrossoverOperator, DuplicateHandling, GraphInstance,
+    KnapsackConstraintHandling, KnapsackCorrelation, KnapsackInstance, LocalSearchConfig,
+    LocalSearchOperator, LocalSearchScope, MutationOperator, NKNeighborhood, Problem, Selection,
+    Task, TieBreaking, UpdateStrategy,
+};
 
 #[derive(Debug)]
 pub enum CreateError {
-    InvalidTSP,
+    InvalidTSP(ParseError),
+    InvalidTour(ParseError),
+    InvalidCNF(dimacs::ParseError),
+    InvalidKnapsack(knapsack::ParseError),
+    InvalidGraph(dimacs::ParseError),
+    InvalidQAP(qaplib::ParseError),
+    InvalidCheckpoint(CheckpointError),
+    InvalidParameter(&'static str),
+    UnsupportedProblem,
 }
 
 impl IntoResponse for CreateError {
     fn into_response(self) -> Response {
         match self {
-            CreateError::InvalidTSP => {
-                (StatusCode::BAD_REQUEST, "invalid tsp instance".to_string())
-            }
+            // Parse errors are returned as JSON, allowing the client to point to the offending line
+            CreateError::InvalidTSP(error) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid tsp instance",
+                    "line": error.line,
+                    "reason": error.reason.to_string(),
+                })),
+            )
+                .into_response(),
+            CreateError::InvalidTour(error) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid tour",
+                    "line": error.line,
+                    "reason": error.reason.to_string(),
+                })),
+            )
+                .into_response(),
+            CreateError::InvalidCNF(error) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid cnf instance",
+                    "line": error.line,
+                    "reason": error.reason.to_string(),
+                })),
+            )
+                .into_response(),
+            CreateError::InvalidKnapsack(error) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid knapsack instance",
+                    "line": error.line,
+                    "reason": error.reason.to_string(),
+                })),
+            )
+                .into_response(),
+            CreateError::InvalidGraph(error) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid graph instance",
+                    "line": error.line,
+                    "reason": error.reason.to_string(),
+                })),
+            )
+                .into_response(),
+            CreateError::InvalidQAP(error) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid qap instance",
+                    "line": error.line,
+                    "reason": error.reason.to_string(),
+                })),
+            )
+                .into_response(),
+            CreateError::InvalidCheckpoint(error) => (
+                StatusCode::BAD_REQUEST,
+                format!("invalid checkpoint: {}", error),
+            )
+                .into_response(),
+            CreateError::InvalidParameter(reason) => (
+                StatusCode::BAD_REQUEST,
+                format!("invalid parameter: {}", reason),
+            )
+                .into_response(),
+            CreateError::UnsupportedProblem => (
+                StatusCode::BAD_REQUEST,
+                "algorithm does not support the given problem".to_string(),
+            )
+                .into_response(),
         }
-        .into_response()
     }
 }
 
 // Create a Algorithm trait object to allow iterating any implemented algorithm
-// Returns a CreateError if an invalid TSP instance is provided
+// Returns a CreateError if an invalid problem instance or invalid parameters are provided
 pub fn create_ea<R: Rng>(
     task: &Task,
     rng: &mut R,
 ) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
     match task.algorithm {
-        AlgorithmConfig::OnePlusOneEA => create_oneplusone_runner(&task.problem, rng),
-        AlgorithmConfig::SimulatedAnnealing { cooling_schedule } => {
-            create_sa_runner(&task.problem, rng, cooling_schedule)
+        AlgorithmConfig::OnePlusOneEA { mutation } => {
+            create_oneplusone_runner(&task.problem, mutation, rng)
+        }
+        AlgorithmConfig::MuPlusLambdaEA {
+            mu,
+            lambda,
+            tie_breaking,
+            duplicates,
+            mutation,
+        } => create_mupluslambda_runner(
+            &task.problem,
+            mu,
+            lambda,
+            map_tie_breaking(tie_breaking.unwrap_or(TieBreaking::PreferOffspring)),
+            map_duplicates(duplicates.unwrap_or(DuplicateHandling::Allow)),
+            mutation,
+            rng,
+        ),
+        AlgorithmConfig::OneCommaLambdaEA {
+            lambda,
+            self_adjusting,
+            update_factor,
+            success_ratio,
+            mutation,
+        } => {
+            let population =
+                offspring_population(lambda, self_adjusting, update_factor, success_ratio)?;
+            create_onecommalambda_runner(&task.problem, population, mutation, rng)
+        }
+        AlgorithmConfig::OnePlusLambdaLambdaGA {
+            lambda,
+            self_adjusting,
+            update_factor,
+            success_ratio,
+        } => {
+            let population =
+                offspring_population(lambda, self_adjusting, update_factor, success_ratio)?;
+            create_onepluslambdalambda_runner(&task.problem, population, rng)
         }
+        AlgorithmConfig::GeneticAlgorithm {
+            population_size,
+            crossover_probability,
+            crossover,
+            selection,
+            steady_state,
+            elitism,
+            mutation,
+        } => {
+            let config = GAConfig {
+                population_size,
+                crossover_probability,
+                selection: map_selection(selection)?,
+                replacement: if steady_state {
+                    Replacement::SteadyState
+                } else {
+                    Replacement::Generational {
+                        elitism: elitism.unwrap_or(0),
+                    }
+                },
+            };
+            create_ga_runner(&task.problem, crossover, mutation, config, rng)
+        }
+        AlgorithmConfig::CompactGA { k } => create_cga_runner(&task.problem, k, rng),
+        AlgorithmConfig::UMDA { mu, lambda } => create_umda_runner(&task.problem, mu, lambda, rng),
+        AlgorithmConfig::PBIL {
+            mu,
+            lambda,
+            learning_rate,
+        } => create_pbil_runner(&task.problem, mu, lambda, learning_rate, rng),
+        AlgorithmConfig::SimulatedAnnealing {
+            cooling_schedule,
+            mutation,
+        } => create_sa_runner(&task.problem, rng, cooling_schedule, mutation),
         AlgorithmConfig::ACO {
             alpha,
             beta,
@@ -53,112 +238,681 @@ pub fn create_ea<R: Rng>(
             q,
             nn,
             update_strategy,
-        } => create_aco_runner(
-            &task.problem,
-            alpha,
-            beta,
-            evap_factor,
-            ants,
-            p_best.unwrap_or(0.0),
-            q.unwrap_or(0.0),
-            nn,
-            update_strategy,
-            rng,
-        ),
+            variant,
+            candidates,
+            local_search,
+        } => match map_aco_variant(variant.unwrap_or(AcoVariant::MMAS))? {
+            None => create_aco_runner(
+                &task.problem,
+                alpha,
+                beta,
+                evap_factor,
+                ants,
+                p_best.unwrap_or(0.0),
+                q.unwrap_or(0.0),
+                nn,
+                update_strategy,
+                candidates,
+                local_search,
+                rng,
+            ),
+            Some(variant) => create_ant_system_runner(
+                &task.problem,
+                variant,
+                alpha,
+                beta,
+                evap_factor,
+                ants,
+                nn,
+                rng,
+            ),
+        },
+    }
+}
+
+// Create the algorithm of a task from a checkpoint, returning it together with the RNG to continue the task with.
+// The algorithm is created as usual before its state is replaced by the state of the checkpoint
+pub fn resume_ea(
+    task: &Task,
+    checkpoint: &Checkpoint,
+) -> Result<(Box<dyn Algorithm<Pcg64>>, Pcg64), CreateError> {
+    let rng: Pcg64 = serde_json::from_str(&checkpoint.rng).map_err(|e| {
+        CreateError::InvalidCheckpoint(CheckpointError::InvalidField("rng", e.to_string()))
+    })?;
+    let mut runner = create_ea(task, &mut rng.clone())?;
+    runner
+        .restore(&checkpoint.algorithm)
+        .map_err(CreateError::InvalidCheckpoint)?;
+    Ok((runner, rng))
+}
+
+// Create a checkpoint of a running task, allowing it to be resumed later using resume_ea
+pub fn create_checkpoint(
+    runner: &dyn Algorithm<Pcg64>,
+    rng: &Pcg64,
+) -> Result<Checkpoint, CheckpointError> {
+    let rng = serde_json::to_string(rng)
+        .map_err(|e| CheckpointError::InvalidField("rng", e.to_string()))?;
+    Ok(Checkpoint {
+        algorithm: runner.checkpoint(),
+        rng,
+    })
+}
+
+// Fitness function of a pseudo-boolean problem, chosen at runtime
+type BitstringFitness = Box<dyn FitnessFunction<Bitstring> + Send>;
+
+// Create the bitstring size and fitness function of a pseudo-boolean problem
+// Returns a CreateError if the problem is not defined on bitstrings or has invalid parameters
+fn bitstring_problem(problem: &Problem) -> Result<(usize, BitstringFitness), CreateError> {
+    let (size, fitness): (usize, BitstringFitness) = match *problem {
+        Problem::OneMax { bitstring_size } => (bitstring_size, Box::new(OneMax)),
+        Problem::LeadingOnes { bitstring_size } => (bitstring_size, Box::new(LeadingOnes)),
+        Problem::Jump { bitstring_size, k } => {
+            if k == 0 || k > bitstring_size {
+                return Err(CreateError::InvalidParameter(
+                    "jump size k must be between 1 and the bitstring size",
+                ));
+            }
+            (bitstring_size, Box::new(Jump::new(k)))
+        }
+        Problem::TwoMax { bitstring_size } => (bitstring_size, Box::new(TwoMax)),
+        Problem::Trap { bitstring_size } => (bitstring_size, Box::new(Trap)),
+        Problem::BinVal { bitstring_size } => (bitstring_size, Box::new(BinVal)),
+        Problem::Linear {
+            bitstring_size,
+            seed,
+            max_weight,
+        } => {
+            let max_weight = max_weight.unwrap_or(bitstring_size as u64);
+            if max_weight == 0 {
+                return Err(CreateError::InvalidParameter("max weight must be positive"));
+            }
+            (
+                bitstring_size,
+                Box::new(Linear::new_random(bitstring_size, max_weight, seed)),
+            )
+        }
+        Problem::RoyalRoad {
+            bitstring_size,
+            block_size,
+        } => {
+            if block_size == 0 || bitstring_size % block_size != 0 {
+                return Err(CreateError::InvalidParameter(
+                    "block size must be positive and divide the bitstring size",
+                ));
+            }
+            (bitstring_size, Box::new(RoyalRoad::new(block_size)))
+        }
+        Problem::Plateau { bitstring_size, k } => {
+            if k == 0 || k > bitstring_size {
+                return Err(CreateError::InvalidParameter(
+                    "plateau size k must be between 1 and the bitstring size",
+                ));
+            }
+            (bitstring_size, Box::new(Plateau::new(k)))
+        }
+        Problem::Ridge { bitstring_size } => (bitstring_size, Box::new(Ridge)),
+        Problem::NKLandscape {
+            n,
+            k,
+            seed,
+            neighborhood,
+        } => {
+            if k >= n {
+                return Err(CreateError::InvalidParameter("K must be less than N"));
+            }
+            let neighborhood =
+                map_nk_neighborhood(neighborhood.unwrap_or(NKNeighborhood::Adjacent));
+            (n, Box::new(NKLandscape::new(n, k, neighborhood, seed)))
+        }
+        Problem::MaxSat {
+            ref cnf_instance,
+            cnf_name: _,
+        } => {
+            let formula = MaxSat::from_dimacs(cnf_instance).map_err(CreateError::InvalidCNF)?;
+            if formula.num_variables() == 0 {
+                return Err(CreateError::InvalidParameter(
+                    "formula must have at least one variable",
+                ));
+            }
+            (formula.num_variables(), Box::new(formula))
+        }
+        Problem::Knapsack {
+            ref instance,
+            constraint_handling,
+        } => {
+            let instance = knapsack_instance(instance)?;
+            if instance.num_items() == 0 {
+                return Err(CreateError::InvalidParameter(
+                    "knapsack instance must have at least one item",
+                ));
+            }
+            let constraint_handling = map_constraint_handling(constraint_handling);
+            (
+                instance.num_items(),
+                Box::new(Knapsack::new(instance, constraint_handling)),
+            )
+        }
+        Problem::VertexCover { ref instance } => {
+            let graph = graph_instance(instance)?;
+            (graph.num_vertices(), Box::new(VertexCover::new(graph)))
+        }
+        Problem::MaxCut { ref instance } => {
+            let graph = graph_instance(instance)?;
+            (graph.num_vertices(), Box::new(MaxCut::new(graph)))
+        }
+        Problem::TSP { .. } | Problem::QAP { .. } => {
+            return Err(CreateError::UnsupportedProblem);
+        }
+    };
+    Ok((size, fitness))
+}
+
+// Known optimal fitness of a problem, used as stopping criteria if the task does not provide one
+// Returns a CreateError if the optimal tour of a TSP instance is not a tour of the instance
+pub fn optimal_fitness(problem: &Problem) -> Result<Option<f64>, CreateError> {
+    match problem {
+        Problem::TSP {
+            tsp_instance,
+            optimal_tour: Some(optimal_tour),
+            ..
+        } => {
+            let tsp = TSP::from_tsplib(tsp_instance).map_err(CreateError::InvalidTSP)?;
+            let tour = tsp
+                .tour_from_tsplib(optimal_tour)
+                .map_err(CreateError::InvalidTour)?;
+            Ok(Some(tsp.evaluate(&tour)))
+        }
+        _ => Ok(bitstring_problem(problem)
+            .ok()
+            .and_then(|(size, fitness)| fitness.optimal_fitness(size))),
     }
 }
 
-// Create a (1+1) EA instance given a problem
+// Create a (1+1) EA instance given a problem and optionally the mutation operator on permutations
 pub fn create_oneplusone_runner<R: Rng>(
     problem: &Problem,
+    mutation: Option<MutationOperator>,
     rng: &mut R,
 ) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
     Ok(match problem {
-        Problem::OneMax { bitstring_size } => {
-            Box::new(OnePlusOneEA::new(*bitstring_size, Bitflip, OneMax, rng))
+        Problem::TSP { tsp_instance, .. } => {
+            let tsp = TSP::from_tsplib(tsp_instance).map_err(CreateError::InvalidTSP)?;
+            let size = tsp.num_cities();
+            let mutation = permutation_mutation(mutation, PermutationMutation::TwoOpt, size)?;
+            Box::new(OnePlusOneEA::new(size, mutation, tsp, rng))
         }
-        Problem::LeadingOnes { bitstring_size } => Box::new(OnePlusOneEA::new(
-            *bitstring_size,
-            Bitflip,
-            LeadingOnes,
-            rng,
+        Problem::QAP {
+            qap_instance,
+            qap_name: _,
+        } => {
+            let qap = QAP::from_qaplib(qap_instance).map_err(CreateError::InvalidQAP)?;
+            let size = qap.size();
+            let mutation = permutation_mutation(mutation, PermutationMutation::Swap, size)?;
+            Box::new(OnePlusOneEA::new(size, mutation, qap, rng))
+        }
+        _ => {
+            let (size, fitness) = bitstring_problem(problem)?;
+            let mutation = bitstring_mutation(mutation, BitstringMutation::Bitflip, size)?;
+            Box::new(OnePlusOneEA::new(size, mutation, fitness, rng))
+        }
+    })
+}
+
+// Create a (mu+lambda) EA instance given a problem and population sizes
+pub fn create_mupluslambda_runner<R: Rng>(
+    problem: &Problem,
+    mu: usize,
+    lambda: usize,
+    tie_breaking: mu_plus_lambda_ea::TieBreaking,
+    duplicates: mu_plus_lambda_ea::DuplicateHandling,
+    mutation: Option<MutationOperator>,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    if mu == 0 {
+        return Err(CreateError::InvalidParameter("mu must be positive"));
+    }
+    if lambda == 0 {
+        return Err(CreateError::InvalidParameter("lambda must be positive"));
+    }
+    Ok(match problem {
+        Problem::TSP { tsp_instance, .. } => {
+            let tsp = TSP::from_tsplib(tsp_instance).map_err(CreateError::InvalidTSP)?;
+            let size = tsp.num_cities();
+            Box::new(MuPlusLambdaEA::new(
+                size,
+                mu,
+                lambda,
+                tie_breaking,
+                duplicates,
+                permutation_mutation(mutation, PermutationMutation::TwoOpt, size)?,
+                tsp,
+                rng,
+            ))
+        }
+        Problem::QAP {
+            qap_instance,
+            qap_name: _,
+        } => {
+            let qap = QAP::from_qaplib(qap_instance).map_err(CreateError::InvalidQAP)?;
+            let size = qap.size();
+            Box::new(MuPlusLambdaEA::new(
+                size,
+                mu,
+                lambda,
+                tie_breaking,
+                duplicates,
+                permutation_mutation(mutation, PermutationMutation::Swap, size)?,
+                qap,
+                rng,
+            ))
+        }
+        _ => {
+            let (size, fitness) = bitstring_problem(problem)?;
+            Box::new(MuPlusLambdaEA::new(
+                size,
+                mu,
+                lambda,
+                tie_breaking,
+                duplicates,
+                bitstring_mutation(mutation, BitstringMutation::Bitflip, size)?,
+                fitness,
+                rng,
+            ))
+        }
+    })
+}
+
+// Create a (1,lambda) EA instance given a problem and offspring population size
+pub fn create_onecommalambda_runner<R: Rng>(
+    problem: &Problem,
+    population: OffspringPopulation,
+    mutation: Option<MutationOperator>,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    check_offspring_population(&population)?;
+    Ok(match problem {
+        Problem::TSP { tsp_instance, .. } => {
+            let tsp = TSP::from_tsplib(tsp_instance).map_err(CreateError::InvalidTSP)?;
+            let size = tsp.num_cities();
+            Box::new(OneCommaLambdaEA::new(
+                size,
+                population,
+                permutation_mutation(mutation, PermutationMutation::TwoOpt, size)?,
+                tsp,
+                rng,
+            ))
+        }
+        Problem::QAP {
+            qap_instance,
+            qap_name: _,
+        } => {
+            let qap = QAP::from_qaplib(qap_instance).map_err(CreateError::InvalidQAP)?;
+            let size = qap.size();
+            Box::new(OneCommaLambdaEA::new(
+                size,
+                population,
+                permutation_mutation(mutation, PermutationMutation::Swap, size)?,
+                qap,
+                rng,
+            ))
+        }
+        _ => {
+            let (size, fitness) = bitstring_problem(problem)?;
+            let mutation = bitstring_mutation(mutation, BitstringMutation::Bitflip, size)?;
+            Box::new(OneCommaLambdaEA::new(
+                size, population, mutation, fitness, rng,
+            ))
+        }
+    })
+}
+
+// Create a (1+(lambda,lambda)) GA instance given a bitstring problem and offspring population size
+pub fn create_onepluslambdalambda_runner<R: Rng>(
+    problem: &Problem,
+    population: OffspringPopulation,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    check_offspring_population(&population)?;
+    let (size, fitness) = bitstring_problem(problem)?;
+    Ok(Box::new(OnePlusLambdaLambdaGA::new(
+        size, population, fitness, rng,
+    )))
+}
+
+// Offspring population size of the task, where the self-adjusting variant defaults to the one-fifth rule.
+// A static offspring population requires lambda, while a self-adjusting one ignores it
+fn offspring_population(
+    lambda: Option<usize>,
+    self_adjusting: bool,
+    update_factor: Option<f64>,
+    success_ratio: Option<f64>,
+) -> Result<OffspringPopulation, CreateError> {
+    if self_adjusting {
+        Ok(OffspringPopulation::SelfAdjusting {
+            update_factor: update_factor.unwrap_or(1.5),
+            success_ratio: success_ratio.unwrap_or(4.0),
+        })
+    } else {
+        let lambda = lambda.ok_or(CreateError::InvalidParameter(
+            "lambda is required unless it is self-adjusting",
+        ))?;
+        Ok(OffspringPopulation::Static(lambda))
+    }
+}
+
+fn check_offspring_population(population: &OffspringPopulation) -> Result<(), CreateError> {
+    match *population {
+        OffspringPopulation::Static(0) => {
+            Err(CreateError::InvalidParameter("lambda must be positive"))
+        }
+        OffspringPopulation::SelfAdjusting {
+            update_factor,
+            success_ratio,
+        } if update_factor <= 1.0 || success_ratio <= 0.0 => Err(CreateError::InvalidParameter(
+            "update factor must be greater than 1 and success ratio positive",
         )),
-        Problem::TSP {
-            tsp_instance,
-            tsp_name: _,
+        _ => Ok(()),
+    }
+}
+
+// Parameters of the genetic algorithm shared by all problems and crossover operators
+struct GAConfig {
+    population_size: usize,
+    crossover_probability: f64,
+    selection: genetic_algorithm::Selection,
+    replacement: Replacement,
+}
+
+// Create a genetic algorithm instance given a problem, crossover operator and GA parameters
+// Returns a CreateError if the crossover operator is not defined on the search space of the problem
+fn create_ga_runner<R: Rng>(
+    problem: &Problem,
+    crossover: CrossoverOperator,
+    mutation: Option<MutationOperator>,
+    config: GAConfig,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    if config.population_size == 0 {
+        return Err(CreateError::InvalidParameter(
+            "population size must be positive",
+        ));
+    }
+    if !(0.0..=1.0).contains(&config.crossover_probability) {
+        return Err(CreateError::InvalidParameter(
+            "crossover probability must be between 0 and 1",
+        ));
+    }
+    match problem {
+        Problem::TSP { tsp_instance, .. } => {
+            let tsp = TSP::from_tsplib(tsp_instance).map_err(CreateError::InvalidTSP)?;
+            let size = tsp.num_cities();
+            let mutation = permutation_mutation(mutation, PermutationMutation::TwoOpt, size)?;
+            create_permutation_ga(size, tsp, crossover, mutation, config, rng)
+        }
+        Problem::QAP {
+            qap_instance,
+            qap_name: _,
         } => {
-            let tsp = TSP::from_euc2d(tsp_instance).ok_or(CreateError::InvalidTSP)?;
-            Box::new(OnePlusOneEA::new(tsp.num_cities(), TwoOpt, tsp, rng))
+            let qap = QAP::from_qaplib(qap_instance).map_err(CreateError::InvalidQAP)?;
+            let size = qap.size();
+            let mutation = permutation_mutation(mutation, PermutationMutation::Swap, size)?;
+            create_permutation_ga(size, qap, crossover, mutation, config, rng)
+        }
+        _ => {
+            let (size, fitness) = bitstring_problem(problem)?;
+            let mutation = bitstring_mutation(mutation, BitstringMutation::Bitflip, size)?;
+            create_bitstring_ga(size, fitness, crossover, mutation, config, rng)
+        }
+    }
+}
+
+// Crossover recombines parents at cut points or segments, which requires at least two elements
+fn check_crossover_size(size: usize) -> Result<(), CreateError> {
+    if size < 2 {
+        return Err(CreateError::InvalidParameter(
+            "crossover requires a problem size of at least 2",
+        ));
+    }
+    Ok(())
+}
+
+fn create_bitstring_ga<F, R>(
+    size: usize,
+    fitness: F,
+    crossover: CrossoverOperator,
+    mutator: BitstringMutation,
+    config: GAConfig,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError>
+where
+    F: FitnessFunction<Bitstring> + Send + 'static,
+    R: Rng,
+{
+    check_crossover_size(size)?;
+    Ok(match crossover {
+        CrossoverOperator::Uniform => {
+            build_ga(size, config, UniformCrossover, mutator, fitness, rng)
+        }
+        CrossoverOperator::OnePoint => {
+            build_ga(size, config, OnePointCrossover, mutator, fitness, rng)
+        }
+        CrossoverOperator::KPoint { k: 0 } => {
+            return Err(CreateError::InvalidParameter(
+                "number of crossover points must be positive",
+            ));
+        }
+        CrossoverOperator::KPoint { k } => {
+            build_ga(size, config, KPointCrossover(k), mutator, fitness, rng)
+        }
+        _ => {
+            return Err(CreateError::InvalidParameter(
+                "crossover operator is not defined on bitstrings",
+            ));
+        }
+    })
+}
+
+fn create_permutation_ga<F, R>(
+    size: usize,
+    fitness: F,
+    crossover: CrossoverOperator,
+    mutator: PermutationMutation,
+    config: GAConfig,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError>
+where
+    F: FitnessFunction<Permutation> + Send + 'static,
+    R: Rng,
+{
+    check_crossover_size(size)?;
+    Ok(match crossover {
+        CrossoverOperator::Order => build_ga(size, config, OrderCrossover, mutator, fitness, rng),
+        CrossoverOperator::PartiallyMapped => build_ga(
+            size,
+            config,
+            PartiallyMappedCrossover,
+            mutator,
+            fitness,
+            rng,
+        ),
+        CrossoverOperator::Cycle => build_ga(size, config, CycleCrossover, mutator, fitness, rng),
+        CrossoverOperator::EdgeRecombination => {
+            build_ga(size, config, EdgeRecombination, mutator, fitness, rng)
+        }
+        _ => {
+            return Err(CreateError::InvalidParameter(
+                "crossover operator is not defined on permutations",
+            ));
         }
     })
 }
 
+fn build_ga<S, F, C, M, R>(
+    size: usize,
+    config: GAConfig,
+    crossover: C,
+    mutator: M,
+    fitness: F,
+    rng: &mut R,
+) -> Box<dyn Algorithm<Pcg64>>
+where
+    GeneticAlgorithm<S, F, C, M>: AlgorithmCore + Send + 'static,
+    S: eas::search_space::SearchSpace,
+    F: FitnessFunction<S>,
+    C: eas::crossover::Crossover<S>,
+    M: eas::mutation::Mutation<S>,
+    R: Rng,
+{
+    Box::new(GeneticAlgorithm::new(
+        size,
+        config.population_size,
+        config.crossover_probability,
+        config.selection,
+        config.replacement,
+        crossover,
+        mutator,
+        fitness,
+        rng,
+    ))
+}
+
+// Create a cGA instance given a bitstring problem and hypothetical population size K
+pub fn create_cga_runner<R: Rng>(
+    problem: &Problem,
+    k: f64,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    if k <= 0.0 {
+        return Err(CreateError::InvalidParameter("K must be positive"));
+    }
+    let (size, fitness) = bitstring_problem(problem)?;
+    Ok(Box::new(CompactGA::new(fitness, size, k, rng)))
+}
+
+// Create a UMDA instance given a bitstring problem and the number of selected and sampled bitstrings
+pub fn create_umda_runner<R: Rng>(
+    problem: &Problem,
+    mu: usize,
+    lambda: usize,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    if mu == 0 || mu > lambda {
+        return Err(CreateError::InvalidParameter(
+            "mu must be positive and at most lambda",
+        ));
+    }
+    let (size, fitness) = bitstring_problem(problem)?;
+    Ok(Box::new(UMDA::new(fitness, size, mu, lambda, rng)))
+}
+
+// Create a PBIL instance given a bitstring problem, the number of selected and sampled bitstrings
+// and the learning rate
+pub fn create_pbil_runner<R: Rng>(
+    problem: &Problem,
+    mu: usize,
+    lambda: usize,
+    learning_rate: f64,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    if mu == 0 || mu > lambda {
+        return Err(CreateError::InvalidParameter(
+            "mu must be positive and at most lambda",
+        ));
+    }
+    if learning_rate <= 0.0 || learning_rate > 1.0 {
+        return Err(CreateError::InvalidParameter(
+            "learning rate must be in (0, 1]",
+        ));
+    }
+    let (size, fitness) = bitstring_problem(problem)?;
+    Ok(Box::new(PBIL::new(
+        fitness,
+        size,
+        mu,
+        lambda,
+        learning_rate,
+        rng,
+    )))
+}
+
 // Create a Simulated Annealing instance, given provided problem and cooling schedule
 pub fn create_sa_runner<R: Rng>(
     problem: &Problem,
     rng: &mut R,
     cooling_schedule: crate::CoolingSchedule,
+    mutation: Option<MutationOperator>,
 ) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
     Ok(match problem {
-        Problem::OneMax { bitstring_size } => {
+        Problem::TSP { tsp_instance, .. } => {
+            let tsp = TSP::from_tsplib(tsp_instance).map_err(CreateError::InvalidTSP)?;
             let c = match cooling_schedule {
                 crate::CoolingSchedule::Static { temperature } => {
                     CoolingSchedule::new_static(temperature)
                 }
                 crate::CoolingSchedule::Exponential { cooling_rate } => {
-                    CoolingSchedule::new_default_bitstring(*bitstring_size as u64, cooling_rate)
+                    CoolingSchedule::new_default_tsp(tsp.num_cities() as u64, cooling_rate)
                 }
             };
+            let mutation =
+                permutation_mutation(mutation, PermutationMutation::TwoOpt, tsp.num_cities())?;
             Box::new(SimulatedAnnealing::new(
-                *bitstring_size,
-                SingleBitflip,
-                OneMax,
+                tsp.num_cities(),
+                mutation,
+                tsp,
                 c,
                 rng,
             ))
         }
-        Problem::LeadingOnes { bitstring_size } => {
+        Problem::QAP {
+            qap_instance,
+            qap_name: _,
+        } => {
+            let qap = QAP::from_qaplib(qap_instance).map_err(CreateError::InvalidQAP)?;
             let c = match cooling_schedule {
                 crate::CoolingSchedule::Static { temperature } => {
                     CoolingSchedule::new_static(temperature)
                 }
                 crate::CoolingSchedule::Exponential { cooling_rate } => {
-                    CoolingSchedule::new_default_bitstring(*bitstring_size as u64, cooling_rate)
+                    CoolingSchedule::new_default_tsp(qap.size() as u64, cooling_rate)
                 }
             };
-            Box::new(SimulatedAnnealing::new(
-                *bitstring_size,
-                SingleBitflip,
-                LeadingOnes,
-                c,
-                rng,
-            ))
+            let mutation = permutation_mutation(mutation, PermutationMutation::Swap, qap.size())?;
+            Box::new(SimulatedAnnealing::new(qap.size(), mutation, qap, c, rng))
         }
-        Problem::TSP {
-            tsp_instance,
-            tsp_name: _,
-        } => {
-            let tsp = TSP::from_euc2d(tsp_instance).ok_or(CreateError::InvalidTSP)?;
+        _ => {
+            let (size, fitness) = bitstring_problem(problem)?;
+            let mutation = bitstring_mutation(mutation, BitstringMutation::SingleBitflip, size)?;
             let c = match cooling_schedule {
                 crate::CoolingSchedule::Static { temperature } => {
                     CoolingSchedule::new_static(temperature)
                 }
                 crate::CoolingSchedule::Exponential { cooling_rate } => {
-                    CoolingSchedule::new_default_tsp(tsp.num_cities() as u64, cooling_rate)
+                    CoolingSchedule::new_default_bitstring(size as u64, cooling_rate)
                 }
             };
-            Box::new(SimulatedAnnealing::new(
-                tsp.num_cities(),
-                TwoOpt,
-                tsp,
-                c,
-                rng,
-            ))
+            Box::new(SimulatedAnnealing::new(size, mutation, fitness, c, rng))
         }
     })
 }
 
+// Parse a TSP instance for the ant colony algorithms, whose pheromone updates and local search
+// treat the tour edges as undirected, so asymmetric instances are rejected
+fn symmetric_tsp(tsp_instance: &str) -> Result<TSP, CreateError> {
+    let tsp = TSP::from_tsplib(tsp_instance).map_err(CreateError::InvalidTSP)?;
+    if !tsp.is_symmetric() {
+        return Err(CreateError::InvalidParameter(
+            "ant colony optimization is only supported for symmetric TSP instances",
+        ));
+    }
+    Ok(tsp)
+}
+
 // Create an MMAS instance given a problem and MMAS parameters
+// If a candidate list size is given, MMAS on TSP only considers the nearest neighbors during construction,
+// and if a local search is given it is applied to the constructed tours before the pheromone update
+#[allow(clippy::too_many_arguments)]
 pub fn create_aco_runner<R: Rng>(
     problem: &Problem,
     alpha: f64,
@@ -169,33 +923,32 @@ pub fn create_aco_runner<R: Rng>(
     q: f64,
     nn: bool,
     strategy: UpdateStrategy,
+    candidates: Option<usize>,
+    local_search: Option<LocalSearchConfig>,
     rng: &mut R,
 ) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    if ants == 0 {
+        return Err(CreateError::InvalidParameter(
+            "number of ants must be positive",
+        ));
+    }
+    if candidates == Some(0) {
+        return Err(CreateError::InvalidParameter(
+            "candidate list size must be positive",
+        ));
+    }
+    if local_search.is_some_and(|ls| ls.neighbors == Some(0)) {
+        return Err(CreateError::InvalidParameter(
+            "local search neighbor list size must be positive",
+        ));
+    }
     Ok(match problem {
-        Problem::OneMax { bitstring_size } => Box::new(MMASbs::new(
-            OneMax,
-            *bitstring_size,
-            ants,
-            alpha,
-            evap_factor,
-            rng,
-        )),
-        Problem::LeadingOnes { bitstring_size } => Box::new(MMASbs::new(
-            LeadingOnes,
-            *bitstring_size,
-            ants,
-            alpha,
-            evap_factor,
-            rng,
-        )),
-        Problem::TSP {
-            tsp_instance,
-            tsp_name: _,
-        } => {
-            let tsp = TSP::from_euc2d(tsp_instance).ok_or(CreateError::InvalidTSP)?;
+        Problem::TSP { tsp_instance, .. } => {
+            let tsp = symmetric_tsp(tsp_instance)?;
             let size = tsp.num_cities();
-            Box::new(MMAStsp::new(
-                tsp.distances(),
+            let distances = tsp.distances();
+            let mut mmas = MMAStsp::new(
+                distances.clone(),
                 tsp,
                 size,
                 ants,
@@ -207,11 +960,248 @@ pub fn create_aco_runner<R: Rng>(
                 p_best,
                 q,
                 rng,
+            );
+            if let Some(k) = candidates {
+                mmas = mmas.with_candidate_lists(k);
+            }
+            if let Some(config) = local_search {
+                let local_search = LocalSearch::new(
+                    distances,
+                    config.neighbors.unwrap_or(10),
+                    map_local_search_operator(config.operator),
+                );
+                mmas = mmas.with_local_search(local_search, map_local_search_scope(config.scope));
+            }
+            Box::new(mmas)
+        }
+        // Candidate lists and local search rely on the distances of a tour, so are only defined for TSP
+        Problem::QAP {
+            qap_instance,
+            qap_name: _,
+        } => {
+            if candidates.is_some() || local_search.is_some() {
+                return Err(CreateError::InvalidParameter(
+                    "candidate lists and local search are only supported for TSP",
+                ));
+            }
+            let qap = QAP::from_qaplib(qap_instance).map_err(CreateError::InvalidQAP)?;
+            let size = qap.size();
+            Box::new(MMASqap::new(qap, size, ants, alpha, evap_factor, rng))
+        }
+        _ => {
+            let (size, fitness) = bitstring_problem(problem)?;
+            Box::new(MMASbs::new(fitness, size, ants, alpha, evap_factor, rng))
+        }
+    })
+}
+
+// Create an Ant System variant instance (Ant Colony System, rank-based or elitist AS)
+// given a TSP problem and ACO parameters. The MMAS specific parameters are not used by these variants
+#[allow(clippy::too_many_arguments)]
+pub fn create_ant_system_runner<R: Rng>(
+    problem: &Problem,
+    variant: AntSystemVariant,
+    alpha: f64,
+    beta: f64,
+    evap_factor: f64,
+    ants: usize,
+    nn: bool,
+    rng: &mut R,
+) -> Result<Box<dyn Algorithm<Pcg64>>, CreateError> {
+    if ants == 0 {
+        return Err(CreateError::InvalidParameter(
+            "number of ants must be positive",
+        ));
+    }
+    match problem {
+        Problem::TSP { tsp_instance, .. } => {
+            let tsp = symmetric_tsp(tsp_instance)?;
+            let size = tsp.num_cities();
+            Ok(Box::new(AntSystem::new(
+                tsp.distances(),
+                tsp,
+                size,
+                ants,
+                alpha,
+                beta,
+                evap_factor,
+                variant,
+                nn,
+                rng,
+            )))
+        }
+        _ => Err(CreateError::UnsupportedProblem),
+    }
+}
+
+// Parse an uploaded knapsack instance or generate a seeded instance
+fn knapsack_instance(
+    instance: &KnapsackInstance,
+) -> Result<knapsack::KnapsackInstance, CreateError> {
+    match *instance {
+        KnapsackInstance::Upload {
+            ref knapsack_instance,
+            knapsack_name: _,
+        } => knapsack::KnapsackInstance::parse(knapsack_instance)
+            .map_err(CreateError::InvalidKnapsack),
+        KnapsackInstance::Generated {
+            size,
+            range,
+            correlation,
+            seed,
+        } => {
+            if range == 0 {
+                return Err(CreateError::InvalidParameter("range must be positive"));
+            }
+            let correlation = match correlation {
+                KnapsackCorrelation::Uncorrelated => knapsack::Correlation::Uncorrelated,
+                KnapsackCorrelation::WeaklyCorrelated => knapsack::Correlation::WeaklyCorrelated,
+                KnapsackCorrelation::StronglyCorrelated => {
+                    knapsack::Correlation::StronglyCorrelated
+                }
+            };
+            Ok(knapsack::KnapsackInstance::generate(
+                size,
+                range,
+                correlation,
+                seed,
             ))
         }
+    }
+}
+
+// Parse an uploaded graph or generate a graph, requiring at least one vertex
+fn graph_instance(instance: &GraphInstance) -> Result<Graph, CreateError> {
+    let graph = match *instance {
+        GraphInstance::Upload {
+            ref graph_instance,
+            graph_name: _,
+        } => Graph::from_dimacs(graph_instance).map_err(CreateError::InvalidGraph)?,
+        GraphInstance::Random {
+            vertices,
+            edge_probability,
+            seed,
+        } => {
+            if !(0.0..=1.0).contains(&edge_probability) {
+                return Err(CreateError::InvalidParameter(
+                    "edge probability must be between 0 and 1",
+                ));
+            }
+            Graph::random_gnp(vertices, edge_probability, seed)
+        }
+        GraphInstance::CompleteBipartite { left, right } => Graph::complete_bipartite(left, right),
+    };
+    if graph.num_vertices() == 0 {
+        return Err(CreateError::InvalidParameter(
+            "graph must have at least one vertex",
+        ));
+    }
+    Ok(graph)
+}
+
+fn map_constraint_handling(
+    constraint_handling: KnapsackConstraintHandling,
+) -> knapsack::ConstraintHandling {
+    match constraint_handling {
+        KnapsackConstraintHandling::Penalty => knapsack::ConstraintHandling::Penalty,
+        KnapsackConstraintHandling::Repair => knapsack::ConstraintHandling::Repair,
+        KnapsackConstraintHandling::Death => knapsack::ConstraintHandling::Death,
+    }
+}
+
+// Mutation operator on permutations given by the task, or the default operator of the problem.
+// Returns a CreateError if the operator cannot sample a move on permutations of the given size
+fn permutation_mutation(
+    mutation: Option<MutationOperator>,
+    default: PermutationMutation,
+    size: usize,
+) -> Result<PermutationMutation, CreateError> {
+    let mutation = match mutation {
+        None => default,
+        Some(MutationOperator::TwoOpt) => PermutationMutation::TwoOpt,
+        Some(MutationOperator::Swap) => PermutationMutation::Swap,
+        Some(MutationOperator::Insertion) => PermutationMutation::Insertion,
+        Some(MutationOperator::OrOpt) => PermutationMutation::OrOpt,
+        Some(MutationOperator::Inversion { max_length }) => {
+            if max_length < 2 {
+                return Err(CreateError::InvalidParameter(
+                    "inversion length must be at least 2",
+                ));
+            }
+            PermutationMutation::Inversion { max_length }
+        }
+        Some(MutationOperator::Scramble) => PermutationMutation::Scramble,
+        Some(MutationOperator::DoubleBridge) => PermutationMutation::DoubleBridge,
+        Some(MutationOperator::ThreeOpt) => PermutationMutation::ThreeOpt,
+        Some(_) => {
+            return Err(CreateError::InvalidParameter(
+                "mutation operator is not defined on permutations",
+            ));
+        }
+    };
+    if size < mutation.min_size() {
+        return Err(CreateError::InvalidParameter(
+            "problem is too small for the mutation operator",
+        ));
+    }
+    Ok(mutation)
+}
+
+// Mutation operator on bitstrings given by the task, or the default operator of the algorithm
+fn bitstring_mutation(
+    mutation: Option<MutationOperator>,
+    default: BitstringMutation,
+    size: usize,
+) -> Result<BitstringMutation, CreateError> {
+    Ok(match mutation {
+        None => default,
+        Some(MutationOperator::NaiveBitflip) => BitstringMutation::NaiveBitflip,
+        Some(MutationOperator::Bitflip) => BitstringMutation::Bitflip,
+        Some(MutationOperator::SingleBitflip) => BitstringMutation::SingleBitflip,
+        Some(MutationOperator::ShiftBitflip) => BitstringMutation::ShiftBitflip,
+        Some(MutationOperator::ResamplingBitflip) => BitstringMutation::ResamplingBitflip,
+        Some(MutationOperator::HeavyTailed { beta }) => {
+            if beta <= 1.0 {
+                return Err(CreateError::InvalidParameter("beta must be greater than 1"));
+            }
+            BitstringMutation::HeavyTailed(HeavyTailed::new(size, beta))
+        }
+        Some(MutationOperator::KBitflip { k }) => {
+            if k == 0 {
+                return Err(CreateError::InvalidParameter("k must be positive"));
+            }
+            BitstringMutation::KBitflip { k }
+        }
+        Some(_) => {
+            return Err(CreateError::InvalidParameter(
+                "mutation operator is not defined on bitstrings",
+            ));
+        }
     })
 }
 
+fn map_nk_neighborhood(neighborhood: NKNeighborhood) -> nk_landscape::Neighborhood {
+    match neighborhood {
+        NKNeighborhood::Adjacent => nk_landscape::Neighborhood::Adjacent,
+        NKNeighborhood::Random => nk_landscape::Neighborhood::Random,
+    }
+}
+
+fn map_local_search_operator(operator: LocalSearchOperator) -> local_search::LocalSearchOperator {
+    match operator {
+        LocalSearchOperator::TwoOpt => local_search::LocalSearchOperator::TwoOpt,
+        LocalSearchOperator::OrOpt => local_search::LocalSearchOperator::OrOpt,
+        LocalSearchOperator::TwoOptOrOpt => local_search::LocalSearchOperator::TwoOptOrOpt,
+    }
+}
+
+fn map_local_search_scope(scope: LocalSearchScope) -> mmas::LocalSearchScope {
+    match scope {
+        LocalSearchScope::AllAnts => mmas::LocalSearchScope::AllAnts,
+        LocalSearchScope::IterationBest => mmas::LocalSearchScope::IterationBest,
+    }
+}
+
 fn map_strategy(strategy: UpdateStrategy) -> PheromoneUpdateStrategy {
     match strategy {
         UpdateStrategy::BestSoFar => PheromoneUpdateStrategy::BestSoFar,
@@ -219,3 +1209,64 @@ fn map_strategy(strategy: UpdateStrategy) -> PheromoneUpdateStrategy {
         UpdateStrategy::AllAnts => PheromoneUpdateStrategy::AllAnts,
     }
 }
+
+fn map_tie_breaking(tie_breaking: TieBreaking) -> mu_plus_lambda_ea::TieBreaking {
+    match tie_breaking {
+        TieBreaking::PreferOffspring => mu_plus_lambda_ea::TieBreaking::PreferOffspring,
+        TieBreaking::PreferParents => mu_plus_lambda_ea::TieBreaking::PreferParents,
+        TieBreaking::Random => mu_plus_lambda_ea::TieBreaking::Random,
+    }
+}
+
+fn map_duplicates(duplicates: DuplicateHandling) -> mu_plus_lambda_ea::DuplicateHandling {
+    match duplicates {
+        DuplicateHandling::Allow => mu_plus_lambda_ea::DuplicateHandling::Allow,
+        DuplicateHandling::Reject => mu_plus_lambda_ea::DuplicateHandling::Reject,
+    }
+}
+
+fn map_selection(selection: Selection) -> Result<genetic_algorithm::Selection, CreateError> {
+    match selection {
+        Selection::Tournament { size: 0 } => Err(CreateError::InvalidParameter(
+            "tournament size must be positive",
+        )),
+        Selection::Tournament { size } => Ok(genetic_algorithm::Selection::Tournament(size)),
+        Selection::Roulette => Ok(genetic_algorithm::Selection::Roulette),
+        Selection::Truncation { fraction } if fraction <= 0.0 || fraction > 1.0 => Err(
+            CreateError::InvalidParameter("truncation fraction must be in (0, 1]"),
+        ),
+        Selection::Truncation { fraction } => {
+            Ok(genetic_algorithm::Selection::Truncation(fraction))
+        }
+    }
+}
+
+// Map the ACO variant to an Ant System variant, or None if MMAS is used
+fn map_aco_variant(variant: AcoVariant) -> Result<Option<AntSystemVariant>, CreateError> {
+    let variant = match variant {
+        AcoVariant::MMAS => return Ok(None),
+        AcoVariant::AntColonySystem { q0, .. } if !(0.0..=1.0).contains(&q0) => {
+            return Err(CreateError::InvalidParameter("q0 must be between 0 and 1"));
+        }
+        AcoVariant::AntColonySystem { xi, .. } if !(0.0..=1.0).contains(&xi) => {
+            return Err(CreateError::InvalidParameter("xi must be between 0 and 1"));
+        }
+        AcoVariant::AntColonySystem { q0, xi } => AntSystemVariant::AntColonySystem { q0, xi },
+        // A single rank gives an initial pheromone of 0, leaving no probabilities to construct tours with
+        AcoVariant::RankBased { ranks } if ranks < 2 => {
+            return Err(CreateError::InvalidParameter(
+                "number of ranks must be at least 2",
+            ));
+        }
+        AcoVariant::RankBased { ranks } => AntSystemVariant::RankBased { ranks },
+        AcoVariant::Elitist { elitist_weight }
+            if !(elitist_weight.is_finite() && elitist_weight >= 0.0) =>
+        {
+            return Err(CreateError::InvalidParameter(
+                "elitist weight must be a non-negative number",
+            ));
+        }
+        AcoVariant::Elitist { elitist_weight } => AntSystemVariant::Elitist { elitist_weight },
+    };
+    Ok(Some(variant))
+}
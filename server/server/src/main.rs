@@ -8,25 +8,32 @@ use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
 };
+use tokio::sync::Semaphore;
 use tower_http::cors::{Any, CorsLayer};
 use uuid::Uuid;
 use ws::handle_websocket_connect;
 
 mod create;
 mod schedule;
+mod stop;
 mod ws;
 
 #[derive()]
 struct AppState {
     pending_schedules: HashMap<Uuid, TaskSchedule>,
+    // Permits for running schedules, limiting the number of concurrent simulations to the number of cores.
+    // Further schedules wait for a permit before they start
+    simulations: Arc<Semaphore>,
 }
 
 type SharedState = Arc<Mutex<AppState>>;
 
 #[tokio::main]
 async fn main() {
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
     let state: SharedState = Arc::new(Mutex::new(AppState {
         pending_schedules: HashMap::new(),
+        simulations: Arc::new(Semaphore::new(cores)),
     }));
 
     // Permissive CORS layer, allowing web-client to fetch from another origin
@@ -59,20 +66,104 @@ struct Task {
     algorithm: AlgorithmConfig,
     problem: Problem,
     stop_cond: StopCondition,
+    // Checkpoint of a previous run of the task to resume from.
+    // Not sent back to the client, as it contains the full algorithm state
+    #[serde(default, skip_serializing)]
+    checkpoint: Option<Checkpoint>,
 }
 
+// Checkpoint of a running task, containing the state of the algorithm and of the RNG.
+// The RNG state consists of 128-bit integers that cannot be represented as JSON numbers,
+// so it is stored as a serialized string instead
+#[derive(Deserialize, Serialize, Clone, Debug)]
+struct Checkpoint {
+    algorithm: serde_json::Value,
+    rng: String,
+}
+
+// Conditions for stopping a task, checked after each iteration.
+// The task always stops after max_iterations, while the remaining conditions are combined
+// such that the task stops when any or all of the given conditions are met
 #[derive(Deserialize, Serialize, Clone, Debug)]
 struct StopCondition {
     max_iterations: u64,
+    // Target fitness, which is reached when the fitness is at least as good as the target
+    // within the larger of the absolute and relative tolerance
     optimal_fitness: Option<f64>,
+    absolute_tolerance: Option<f64>,
+    relative_tolerance: Option<f64>,
+    // Budget of fitness evaluations, allowing algorithms with different costs per iteration to be compared.
+    // Checked after each iteration, so the last iteration may exceed the budget
+    max_evaluations: Option<u64>,
+    // Wall-clock limit of each run of the task in milliseconds
+    max_time_ms: Option<u64>,
+    // Stop when the best fitness has not improved for the given number of iterations or evaluations
+    stagnation_iterations: Option<u64>,
+    stagnation_evaluations: Option<u64>,
+    #[serde(default)]
+    combination: StopCombination,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq)]
+enum StopCombination {
+    #[default]
+    Any,
+    All,
 }
 
 #[derive(Deserialize, Serialize, Clone, Copy, Debug)]
 #[serde(tag = "type")]
+#[allow(clippy::upper_case_acronyms)]
 enum AlgorithmConfig {
-    OnePlusOneEA,
+    OnePlusOneEA {
+        mutation: Option<MutationOperator>,
+    },
+    MuPlusLambdaEA {
+        mu: usize,
+        lambda: usize,
+        tie_breaking: Option<TieBreaking>,
+        duplicates: Option<DuplicateHandling>,
+        mutation: Option<MutationOperator>,
+    },
+    OneCommaLambdaEA {
+        // Required unless lambda is self-adjusting
+        lambda: Option<usize>,
+        self_adjusting: bool,
+        update_factor: Option<f64>,
+        success_ratio: Option<f64>,
+        mutation: Option<MutationOperator>,
+    },
+    OnePlusLambdaLambdaGA {
+        // Required unless lambda is self-adjusting
+        lambda: Option<usize>,
+        self_adjusting: bool,
+        update_factor: Option<f64>,
+        success_ratio: Option<f64>,
+    },
+    GeneticAlgorithm {
+        population_size: usize,
+        crossover_probability: f64,
+        crossover: CrossoverOperator,
+        selection: Selection,
+        steady_state: bool,
+        elitism: Option<usize>,
+        mutation: Option<MutationOperator>,
+    },
+    CompactGA {
+        k: f64,
+    },
+    UMDA {
+        mu: usize,
+        lambda: usize,
+    },
+    PBIL {
+        mu: usize,
+        lambda: usize,
+        learning_rate: f64,
+    },
     SimulatedAnnealing {
         cooling_schedule: CoolingSchedule,
+        mutation: Option<MutationOperator>,
     },
     ACO {
         alpha: f64,
@@ -83,6 +174,9 @@ enum AlgorithmConfig {
         q: Option<f64>,
         nn: bool,
         update_strategy: UpdateStrategy,
+        variant: Option<AcoVariant>,
+        candidates: Option<usize>,
+        local_search: Option<LocalSearchConfig>,
     },
 }
 
@@ -93,8 +187,61 @@ enum CoolingSchedule {
     Exponential { cooling_rate: f64 },
 }
 
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(tag = "type")]
+#[allow(clippy::upper_case_acronyms)]
+enum AcoVariant {
+    MMAS,
+    AntColonySystem { q0: f64, xi: f64 },
+    RankBased { ranks: usize },
+    Elitist { elitist_weight: f64 },
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(tag = "type")]
+enum CrossoverOperator {
+    Uniform,
+    OnePoint,
+    KPoint { k: usize },
+    Order,
+    PartiallyMapped,
+    Cycle,
+    EdgeRecombination,
+}
+
+// Mutation operators on bitstrings and permutations. If not given, bitstring algorithms use standard bit mutation
+// (single bitflips for Simulated Annealing), while 2-opt is used for TSP and swap for QAP
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(tag = "type")]
+enum MutationOperator {
+    NaiveBitflip,
+    Bitflip,
+    SingleBitflip,
+    ShiftBitflip,
+    ResamplingBitflip,
+    HeavyTailed { beta: f64 },
+    KBitflip { k: usize },
+    TwoOpt,
+    Swap,
+    Insertion,
+    OrOpt,
+    Inversion { max_length: usize },
+    Scramble,
+    DoubleBridge,
+    ThreeOpt,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(tag = "type")]
+enum Selection {
+    Tournament { size: usize },
+    Roulette,
+    Truncation { fraction: f64 },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "type")]
+#[allow(clippy::upper_case_acronyms)]
 enum Problem {
     OneMax {
         bitstring_size: usize,
@@ -102,15 +249,157 @@ enum Problem {
     LeadingOnes {
         bitstring_size: usize,
     },
+    Jump {
+        bitstring_size: usize,
+        k: usize,
+    },
+    TwoMax {
+        bitstring_size: usize,
+    },
+    Trap {
+        bitstring_size: usize,
+    },
+    BinVal {
+        bitstring_size: usize,
+    },
+    Linear {
+        bitstring_size: usize,
+        seed: u64,
+        max_weight: Option<u64>,
+    },
+    RoyalRoad {
+        bitstring_size: usize,
+        block_size: usize,
+    },
+    Plateau {
+        bitstring_size: usize,
+        k: usize,
+    },
+    Ridge {
+        bitstring_size: usize,
+    },
+    NKLandscape {
+        n: usize,
+        k: usize,
+        seed: u64,
+        neighborhood: Option<NKNeighborhood>,
+    },
+    MaxSat {
+        cnf_instance: String,
+        cnf_name: String,
+    },
+    Knapsack {
+        instance: KnapsackInstance,
+        constraint_handling: KnapsackConstraintHandling,
+    },
+    VertexCover {
+        instance: GraphInstance,
+    },
+    MaxCut {
+        instance: GraphInstance,
+    },
     TSP {
         tsp_instance: String,
         tsp_name: String,
+        // Known optimal tour in the TSPLIB TOUR format, whose length is used as the optimal fitness
+        optimal_tour: Option<String>,
+    },
+    QAP {
+        qap_instance: String,
+        qap_name: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+enum KnapsackInstance {
+    Upload {
+        knapsack_instance: String,
+        knapsack_name: String,
+    },
+    Generated {
+        size: usize,
+        range: u64,
+        correlation: KnapsackCorrelation,
+        seed: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+enum KnapsackCorrelation {
+    Uncorrelated,
+    WeaklyCorrelated,
+    StronglyCorrelated,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+enum KnapsackConstraintHandling {
+    Penalty,
+    Repair,
+    Death,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+enum GraphInstance {
+    Upload {
+        graph_instance: String,
+        graph_name: String,
+    },
+    Random {
+        vertices: usize,
+        edge_probability: f64,
+        seed: u64,
+    },
+    CompleteBipartite {
+        left: usize,
+        right: usize,
     },
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+enum NKNeighborhood {
+    Adjacent,
+    Random,
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 enum UpdateStrategy {
     BestSoFar,
     GenerationBest,
     AllAnts,
 }
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+struct LocalSearchConfig {
+    operator: LocalSearchOperator,
+    scope: LocalSearchScope,
+    neighbors: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[allow(clippy::enum_variant_names)]
+enum LocalSearchOperator {
+    TwoOpt,
+    OrOpt,
+    TwoOptOrOpt,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+enum LocalSearchScope {
+    AllAnts,
+    IterationBest,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+enum TieBreaking {
+    PreferOffspring,
+    PreferParents,
+    Random,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+enum DuplicateHandling {
+    Allow,
+    Reject,
+}
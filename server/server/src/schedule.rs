@@ -2,7 +2,11 @@ use axum::{Json, extract::State, http::StatusCode};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{SharedState, Task, create::create_ea};
+use crate::{
+    SharedState, StopCombination, Task,
+    create::{create_ea, optimal_fitness, resume_ea},
+    stop::valid_stop_condition,
+};
 
 #[derive(Deserialize)]
 pub struct CreateTaskScheduleRequest {
@@ -10,6 +14,7 @@ pub struct CreateTaskScheduleRequest {
     repeat_count: u64,
     update_rate: u64,
     seed: u64,
+    checkpoint_rate: Option<u64>,
 }
 
 #[derive(Serialize, Clone)]
@@ -19,6 +24,8 @@ pub struct TaskSchedule {
     pub repeat_count: u64,
     pub update_rate: u64,
     pub seed: u64,
+    // If set, a checkpoint is sent every checkpoint_rate iterations and when a task is done
+    pub checkpoint_rate: Option<u64>,
 }
 
 // Given a valid task schedule, create the task schedule and save it,
@@ -40,23 +47,29 @@ pub async fn create_task_schedule(
         return Err(StatusCode::BAD_REQUEST);
     }
 
+    // Validate checkpoint_rate
+    if request.checkpoint_rate == Some(0) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
     // Validate tasks
     if request.tasks.is_empty() {
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    for task in &request.tasks {
-        if create_ea(task, &mut rand::rng()).is_err() {
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    }
+    // Validating the tasks creates their algorithms, and the known optimum of NK landscapes and
+    // knapsack instances is computed exactly, so both run on a blocking thread instead of the async runtime
+    let tasks = tokio::task::spawn_blocking(move || prepare_tasks(request.tasks))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
 
     let schedule = TaskSchedule {
         id: schedule_id,
-        tasks: request.tasks,
+        tasks,
         repeat_count: request.repeat_count,
         update_rate: request.update_rate,
         seed: request.seed,
+        checkpoint_rate: request.checkpoint_rate,
     };
     let schedule_result = schedule.clone();
 
@@ -69,3 +82,32 @@ pub async fn create_task_schedule(
     println!("[{}] schedule created", schedule_id);
     Ok(Json(schedule_result))
 }
+
+// Check that every task can be created, and set the optimal fitness of the tasks where it is known
+fn prepare_tasks(mut tasks: Vec<Task>) -> Result<Vec<Task>, StatusCode> {
+    for task in &tasks {
+        if !valid_stop_condition(&task.stop_cond) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let valid = match &task.checkpoint {
+            Some(checkpoint) => resume_ea(task, checkpoint).is_ok(),
+            None => create_ea(task, &mut rand::rng()).is_ok(),
+        };
+        if !valid {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    // Stop tasks once the optimum is reached if it is known and not given.
+    // Not done when all conditions must be met, as reaching the optimum would then be required.
+    // The known optimum is computed for every task to validate a given optimal tour
+    for task in tasks.iter_mut() {
+        let optimum = optimal_fitness(&task.problem).map_err(|_| StatusCode::BAD_REQUEST)?;
+        if task.stop_cond.optimal_fitness.is_none()
+            && task.stop_cond.combination == StopCombination::Any
+        {
+            task.stop_cond.optimal_fitness = optimum;
+        }
+    }
+    Ok(tasks)
+}
@@ -0,0 +1,350 @@
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+use eas::algorithms::Algorithm;
+use rand_pcg::Pcg64;
+use serde::Serialize;
+
+use crate::{StopCombination, StopCondition};
+
+// Stop conditions reported in the result of a task, listing every condition met when the task stopped
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum StopReason {
+    MaxIterations,
+    TargetFitness,
+    MaxEvaluations,
+    MaxTime,
+    StagnationIterations,
+    StagnationEvaluations,
+}
+
+// Check that the stop condition of a task is valid, rejecting negative tolerances and zero limits
+pub fn valid_stop_condition(condition: &StopCondition) -> bool {
+    let valid_tolerance =
+        |tolerance: Option<f64>| tolerance.is_none_or(|t| t >= 0.0 && t.is_finite());
+    valid_tolerance(condition.absolute_tolerance)
+        && valid_tolerance(condition.relative_tolerance)
+        && condition.max_time_ms != Some(0)
+        && condition.stagnation_iterations != Some(0)
+        && condition.stagnation_evaluations != Some(0)
+}
+
+// Keeps track of a single run of a task to decide when its stop condition is met.
+// The time limit and stagnation are measured from the start of the run,
+// so a task resumed from a checkpoint gets the full time limit and stagnation window again.
+// Fitness values are compared in the direction of the fitness function of the algorithm
+pub struct StopMonitor<'a> {
+    condition: &'a StopCondition,
+    start: Instant,
+    best_fitness: f64,
+    improved_iteration: u64,
+    improved_evaluations: u64,
+}
+
+impl<'a> StopMonitor<'a> {
+    pub fn new(condition: &'a StopCondition, runner: &dyn Algorithm<Pcg64>) -> Self {
+        StopMonitor {
+            condition,
+            start: Instant::now(),
+            best_fitness: runner.current_fitness(),
+            improved_iteration: runner.iterations(),
+            improved_evaluations: runner.evaluations(),
+        }
+    }
+
+    // Check the stop condition after an iteration, returning the conditions that are met if the task should stop,
+    // and an empty list otherwise
+    pub fn check(&mut self, runner: &dyn Algorithm<Pcg64>) -> Vec<StopReason> {
+        let fitness = runner.current_fitness();
+        if runner.compare(fitness, self.best_fitness) == Ordering::Greater {
+            self.best_fitness = fitness;
+            self.improved_iteration = runner.iterations();
+            self.improved_evaluations = runner.evaluations();
+        }
+
+        // Each combined condition is None if it is not given, and otherwise whether it is met
+        let condition = self.condition;
+        let conditions = [
+            (
+                StopReason::TargetFitness,
+                condition
+                    .optimal_fitness
+                    .map(|target| self.reached_target(runner, fitness, target)),
+            ),
+            (
+                StopReason::MaxEvaluations,
+                condition
+                    .max_evaluations
+                    .map(|max| runner.evaluations() >= max),
+            ),
+            (
+                StopReason::MaxTime,
+                condition
+                    .max_time_ms
+                    .map(|ms| self.start.elapsed() >= Duration::from_millis(ms)),
+            ),
+            (
+                StopReason::StagnationIterations,
+                condition
+                    .stagnation_iterations
+                    .map(|n| runner.iterations() - self.improved_iteration >= n),
+            ),
+            (
+                StopReason::StagnationEvaluations,
+                condition
+                    .stagnation_evaluations
+                    .map(|n| runner.evaluations() - self.improved_evaluations >= n),
+            ),
+        ];
+        let given = conditions.iter().filter(|(_, met)| met.is_some()).count();
+        let mut reasons = conditions
+            .iter()
+            .filter(|(_, met)| *met == Some(true))
+            .map(|&(reason, _)| reason)
+            .collect::<Vec<_>>();
+
+        let stop = match condition.combination {
+            StopCombination::Any => !reasons.is_empty(),
+            StopCombination::All => given > 0 && reasons.len() == given,
+        };
+        if runner.iterations() >= condition.max_iterations {
+            reasons.insert(0, StopReason::MaxIterations);
+        } else if !stop {
+            reasons.clear();
+        }
+        reasons
+    }
+
+    // The target is reached if the fitness is at least as good as the target, allowing for the tolerance,
+    // which moves the target to the worse of target - tolerance and target + tolerance
+    fn reached_target(&self, runner: &dyn Algorithm<Pcg64>, fitness: f64, target: f64) -> bool {
+        let tolerance = self
+            .condition
+            .absolute_tolerance
+            .unwrap_or(0.0)
+            .max(self.condition.relative_tolerance.unwrap_or(0.0) * target.abs());
+        let bound = match runner.compare(target - tolerance, target + tolerance) {
+            Ordering::Less => target - tolerance,
+            _ => target + tolerance,
+        };
+        runner.compare(fitness, bound) != Ordering::Less
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eas::algorithms::CheckpointError;
+    use rand::SeedableRng;
+    use serde_json::{Value, json};
+
+    // Algorithm replaying a sequence of fitness values, one per iteration and repeating the last value,
+    // with two evaluations per iteration
+    struct Replay {
+        fitness: Vec<f64>,
+        maximizing: bool,
+        iteration: u64,
+    }
+
+    impl Algorithm<Pcg64> for Replay {
+        fn iterate(&mut self, _: &mut Pcg64) {
+            self.iteration += 1;
+        }
+
+        fn current_fitness(&self) -> f64 {
+            self.fitness[(self.iteration as usize).min(self.fitness.len() - 1)]
+        }
+
+        fn iterations(&self) -> u64 {
+            self.iteration
+        }
+
+        fn evaluations(&self) -> u64 {
+            2 * self.iteration
+        }
+
+        fn compare(&self, a: f64, b: f64) -> Ordering {
+            if self.maximizing {
+                a.total_cmp(&b)
+            } else {
+                a.total_cmp(&b).reverse()
+            }
+        }
+
+        fn status_json(&self) -> Value {
+            json!({})
+        }
+
+        fn checkpoint(&self) -> Value {
+            json!({})
+        }
+
+        fn restore(&mut self, _: &Value) -> Result<(), CheckpointError> {
+            Ok(())
+        }
+    }
+
+    // Run until the monitor stops, returning the number of iterations and the stop reasons
+    fn run(condition: Value, fitness: Vec<f64>, maximizing: bool) -> (u64, Vec<StopReason>) {
+        let condition: StopCondition = serde_json::from_value(condition).unwrap();
+        let mut runner = Replay {
+            fitness,
+            maximizing,
+            iteration: 0,
+        };
+        let mut rng = Pcg64::seed_from_u64(0);
+        let mut monitor = StopMonitor::new(&condition, &runner);
+        loop {
+            runner.iterate(&mut rng);
+            let reasons = monitor.check(&runner);
+            if !reasons.is_empty() {
+                return (runner.iterations(), reasons);
+            }
+        }
+    }
+
+    #[test]
+    fn test_target_fitness() {
+        // (condition, fitness values, maximizing, expected iterations, expected reasons)
+        let testcases = vec![
+            (
+                json!({"max_iterations": 10, "optimal_fitness": 5.0}),
+                vec![1.0, 3.0, 5.0, 6.0],
+                true,
+                2,
+                vec![StopReason::TargetFitness],
+            ),
+            (
+                json!({"max_iterations": 10, "optimal_fitness": 5.0}),
+                vec![9.0, 7.0, 5.0, 4.0],
+                false,
+                2,
+                vec![StopReason::TargetFitness],
+            ),
+            // Fitness values on the wrong side of the target never reach it
+            (
+                json!({"max_iterations": 10, "optimal_fitness": 0.0}),
+                vec![1.0, 3.0, 5.0, 7.0],
+                false,
+                10,
+                vec![StopReason::MaxIterations],
+            ),
+            (
+                json!({"max_iterations": 10, "optimal_fitness": 5.0, "absolute_tolerance": 2.0}),
+                vec![1.0, 3.0, 5.0],
+                true,
+                1,
+                vec![StopReason::TargetFitness],
+            ),
+            // The relative tolerance of 0.2 allows a fitness of up to 12
+            (
+                json!({"max_iterations": 10, "optimal_fitness": 10.0, "relative_tolerance": 0.2}),
+                vec![20.0, 15.0, 12.0],
+                false,
+                2,
+                vec![StopReason::TargetFitness],
+            ),
+            // The larger of both tolerances is used
+            (
+                json!({
+                    "max_iterations": 10,
+                    "optimal_fitness": 10.0,
+                    "absolute_tolerance": 1.0,
+                    "relative_tolerance": 0.5
+                }),
+                vec![1.0, 4.0, 5.0],
+                true,
+                2,
+                vec![StopReason::TargetFitness],
+            ),
+        ];
+        for (i, t) in testcases.into_iter().enumerate() {
+            assert_eq!(run(t.0, t.1, t.2), (t.3, t.4), "testcase {}", i);
+        }
+    }
+
+    #[test]
+    fn test_budget_and_stagnation() {
+        let improving_once = vec![1.0, 2.0, 2.0];
+        // (condition, maximizing, expected iterations, expected reasons)
+        let testcases = vec![
+            (
+                json!({"max_iterations": 10, "max_evaluations": 6}),
+                true,
+                3,
+                vec![StopReason::MaxEvaluations],
+            ),
+            // The improvement in the first iteration restarts the stagnation window
+            (
+                json!({"max_iterations": 10, "stagnation_iterations": 3}),
+                true,
+                4,
+                vec![StopReason::StagnationIterations],
+            ),
+            // When minimizing, the fitness never improves on the initial fitness
+            (
+                json!({"max_iterations": 10, "stagnation_iterations": 3}),
+                false,
+                3,
+                vec![StopReason::StagnationIterations],
+            ),
+            (
+                json!({"max_iterations": 10, "stagnation_evaluations": 4}),
+                true,
+                3,
+                vec![StopReason::StagnationEvaluations],
+            ),
+        ];
+        for (i, t) in testcases.into_iter().enumerate() {
+            assert_eq!(
+                run(t.0, improving_once.clone(), t.1),
+                (t.2, t.3),
+                "testcase {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_combination() {
+        let improving_once = vec![1.0, 2.0, 2.0];
+        // (condition, expected iterations, expected reasons)
+        let testcases = vec![
+            (
+                json!({"max_iterations": 10, "max_evaluations": 4, "stagnation_iterations": 3}),
+                2,
+                vec![StopReason::MaxEvaluations],
+            ),
+            (
+                json!({
+                    "max_iterations": 10,
+                    "max_evaluations": 4,
+                    "stagnation_iterations": 3,
+                    "combination": "All"
+                }),
+                4,
+                vec![StopReason::MaxEvaluations, StopReason::StagnationIterations],
+            ),
+            // The maximum number of iterations stops the task even if not all conditions are met,
+            // reporting the conditions that are met
+            (
+                json!({
+                    "max_iterations": 5,
+                    "optimal_fitness": 100.0,
+                    "max_evaluations": 4,
+                    "combination": "All"
+                }),
+                5,
+                vec![StopReason::MaxIterations, StopReason::MaxEvaluations],
+            ),
+        ];
+        for (i, t) in testcases.into_iter().enumerate() {
+            assert_eq!(
+                run(t.0, improving_once.clone(), true),
+                (t.1, t.2),
+                "testcase {}",
+                i
+            );
+        }
+    }
+}
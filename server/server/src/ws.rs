@@ -8,10 +8,14 @@ use futures::{SinkExt, StreamExt};
 use rand::SeedableRng;
 use rand_pcg::Pcg64;
 use serde_json::{Value, json};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::sync::mpsc::{self, Sender, error::SendError};
 use uuid::Uuid;
 
-use crate::create::create_ea;
+use crate::create::{create_checkpoint, create_ea, resume_ea};
 use crate::schedule::TaskSchedule;
+use crate::stop::{StopMonitor, StopReason};
 use crate::{SharedState, Task};
 
 // Handle initial connection of websocket
@@ -21,20 +25,28 @@ pub async fn handle_websocket_connect(
     ws: WebSocketUpgrade,
     Path(id): Path<Uuid>,
 ) -> Response {
-    let Some(schedule) = state
-        .lock()
-        .expect("failed to aquire lock")
-        .pending_schedules
-        .remove(&id)
-    else {
-        return StatusCode::NOT_FOUND.into_response();
+    let (schedule, simulations) = {
+        let mut state = state.lock().expect("failed to aquire lock");
+        let Some(schedule) = state.pending_schedules.remove(&id) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        (schedule, state.simulations.clone())
     };
-    ws.on_upgrade(move |socket| handle_schedule(socket, schedule))
+    ws.on_upgrade(move |socket| handle_schedule(socket, schedule, simulations))
 }
 
-// Run a TaskSchedule, periodically sending data updates on the WebSocket connection
-async fn handle_schedule(socket: WebSocket, schedule: TaskSchedule) {
-    println!("[{}] schedule execution started", schedule.id);
+// Number of messages the simulation can send ahead of the WebSocket writer,
+// before it waits for the client to receive them
+const MESSAGE_BUFFER: usize = 32;
+
+// Run a TaskSchedule, periodically sending data updates on the WebSocket connection.
+// The simulation runs on a blocking thread once it holds one of the simulation permits,
+// sending its messages through a channel to the WebSocket writer,
+// such that iterating an algorithm never blocks the async runtime serving the other connections,
+// and at most one simulation per core runs at a time
+async fn handle_schedule(socket: WebSocket, schedule: TaskSchedule, simulations: Arc<Semaphore>) {
+    let id = schedule.id;
+    println!("[{}] schedule execution started", id);
 
     // Split socket into a transmit and receive part
     let (mut tx, mut rx) = socket.split();
@@ -44,62 +56,121 @@ async fn handle_schedule(socket: WebSocket, schedule: TaskSchedule) {
     let mut receive_task =
         tokio::spawn(async move { while let Some(Ok(Message::Text(_))) = rx.next().await {} });
 
-    // Task running the actual simulation
-    let mut simulation_task = tokio::spawn(async move {
-        // Use a seeded RNG to be able to reproduce results given the same seed and schedule
-        let mut rng = Pcg64::seed_from_u64(schedule.seed);
-
-        // Loop over each task and perform them the given amount of times
-        for task in schedule.tasks {
-            for _ in 0..schedule.repeat_count {
-                let Ok(mut runner) = create_ea(&task, &mut rng) else {
-                    return;
-                };
-
-                // Send initial task data
-                let _ = send_json(
-                    &mut tx,
-                    json!({
-                        "messageType": "setTask",
-                        "task": task
-                    }
-                    ),
-                )
-                .await;
-                // Run task until a stopping criteria is met
-                run_task(&task, schedule.update_rate, &mut runner, &mut rng, &mut tx).await;
-                // Send resulting task data
-                if send_json(
-                    &mut tx,
-                    json!({
-                        "messageType": "result",
-                        "result": {
-                            "task": task,
-                            "iterations": runner.iterations(),
-                            "fitness": runner.current_fitness(),
-                        },
-                    }),
-                )
-                .await
-                .is_err()
-                {
-                    return;
-                }
+    // Worker running the actual simulation, which stops once the channel is closed.
+    // Waits for a permit first, and does not start if the client disconnected in the meantime
+    let (sender, mut receiver) = mpsc::channel(MESSAGE_BUFFER);
+    tokio::spawn(async move {
+        let Ok(permit) = simulations.acquire_owned().await else {
+            return;
+        };
+        if sender.is_closed() {
+            return;
+        }
+        tokio::task::spawn_blocking(move || {
+            run_schedule(schedule, sender);
+            drop(permit);
+        });
+    });
+
+    // Task forwarding the messages of the simulation to the client
+    let mut send_task = tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            if send_json(&mut tx, message).await.is_err() {
+                return;
             }
         }
-        println!("[{}] schedule completed successfully", schedule.id);
     });
 
-    // Run both receive and simulation task until either one finishes
+    // Run both receive and send task until either one finishes.
+    // Aborting the send task closes the channel, stopping the simulation
     tokio::select! {
         _ = &mut receive_task => {
-                println!("[{}] schedule aborted early", schedule.id);
-                simulation_task.abort()
+                println!("[{}] schedule aborted early", id);
+                send_task.abort()
         },
-        _ = &mut simulation_task => receive_task.abort(),
+        _ = &mut send_task => receive_task.abort(),
     }
 
-    println!("[{}] schedule done", schedule.id);
+    println!("[{}] schedule done", id);
+}
+
+// Run each task of a schedule the given amount of times, sending the messages of the simulation through the channel.
+// Must be run on a thread that can block, as sending waits for the WebSocket writer to catch up
+fn run_schedule(schedule: TaskSchedule, sender: Sender<Value>) {
+    // Use a seeded RNG to be able to reproduce results given the same seed and schedule
+    let mut rng = Pcg64::seed_from_u64(schedule.seed);
+
+    // Loop over each task and perform them the given amount of times
+    for task in schedule.tasks {
+        for repetition in 0..schedule.repeat_count {
+            let (mut runner, mut resumed_rng) = match &task.checkpoint {
+                Some(checkpoint) => {
+                    let (runner, resumed_rng) = match resume_ea(&task, checkpoint) {
+                        Ok(resumed) => resumed,
+                        Err(error) => {
+                            let _ = send_error(
+                                &sender,
+                                format!("failed to resume the task: {:?}", error),
+                            );
+                            return;
+                        }
+                    };
+                    // Only the first run continues with the RNG of the checkpoint, such that it continues identically.
+                    // Later repetitions resume the same state with the RNG of the schedule, giving independent runs
+                    (runner, (repetition == 0).then_some(resumed_rng))
+                }
+                None => {
+                    let runner = match create_ea(&task, &mut rng) {
+                        Ok(runner) => runner,
+                        Err(error) => {
+                            let _ = send_error(
+                                &sender,
+                                format!("failed to create the task: {:?}", error),
+                            );
+                            return;
+                        }
+                    };
+                    (runner, None)
+                }
+            };
+            let run_rng = match resumed_rng.as_mut() {
+                Some(resumed_rng) => resumed_rng,
+                None => &mut rng,
+            };
+
+            // Send initial task data
+            let _ = sender.blocking_send(json!({
+                "messageType": "setTask",
+                "task": task
+            }));
+            // Run task until a stopping criteria is met
+            let stopped_by = run_task(
+                &task,
+                schedule.update_rate,
+                schedule.checkpoint_rate,
+                &mut runner,
+                run_rng,
+                &sender,
+            );
+            // Send resulting task data
+            if sender
+                .blocking_send(json!({
+                    "messageType": "result",
+                    "result": {
+                        "task": task,
+                        "iterations": runner.iterations(),
+                        "evaluations": runner.evaluations(),
+                        "fitness": runner.current_fitness(),
+                        "stopped_by": stopped_by,
+                    },
+                }))
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+    println!("[{}] schedule completed successfully", schedule.id);
 }
 
 // Send a JSON value as text over WebSocket connection
@@ -111,58 +182,82 @@ async fn send_json(
     socket.send(Message::Text(message_text.into())).await
 }
 
-// Run a task until a stopping criteria is met
-// Sends simulation status periodically based on update_rate
-async fn run_task(
+// Send a checkpoint of a running task, which can be provided with the task to resume it.
+// If the checkpoint cannot be created, an error is sent instead, as the task itself can continue
+fn send_checkpoint(
+    sender: &Sender<Value>,
+    task: &Task,
+    runner: &dyn Algorithm<Pcg64>,
+    rng: &Pcg64,
+) -> Result<(), SendError<Value>> {
+    match create_checkpoint(runner, rng) {
+        Ok(checkpoint) => sender.blocking_send(json!({
+            "messageType": "checkpoint",
+            "task": task,
+            "checkpoint": checkpoint,
+        })),
+        Err(error) => send_error(sender, format!("failed to create checkpoint: {}", error)),
+    }
+}
+
+// Send an error message, describing why the schedule could not continue as requested
+fn send_error(sender: &Sender<Value>, error: String) -> Result<(), SendError<Value>> {
+    sender.blocking_send(json!({
+        "messageType": "error",
+        "error": error,
+    }))
+}
+
+// Send the status of the algorithm as a data update
+fn send_status(
+    sender: &Sender<Value>,
+    runner: &dyn Algorithm<Pcg64>,
+) -> Result<(), SendError<Value>> {
+    sender.blocking_send(json!({
+        "messageType": "dataUpdate",
+        "data": runner.status_json(),
+    }))
+}
+
+// Run a task until a stopping criteria is met, returning the stop conditions that were met,
+// or no conditions if the client disconnected.
+// Sends simulation status periodically based on update_rate,
+// and checkpoints based on checkpoint_rate if given
+fn run_task(
     task: &Task,
     update_rate: u64,
+    checkpoint_rate: Option<u64>,
     runner: &mut Box<dyn Algorithm<Pcg64>>,
     rng: &mut Pcg64,
-    socket: &mut SplitSink<WebSocket, Message>,
-) {
-    let _ = send_json(
-        socket,
-        json!({
-            "messageType": "dataUpdate",
-            "data": runner.status_json(),
-        }),
-    )
-    .await;
-
-    loop {
+    sender: &Sender<Value>,
+) -> Vec<StopReason> {
+    let _ = send_status(sender, runner.as_ref());
+
+    let mut monitor = StopMonitor::new(&task.stop_cond, runner.as_ref());
+    let stopped_by = loop {
         runner.iterate(rng);
-        if runner.iterations() >= task.stop_cond.max_iterations {
-            break;
+        let reasons = monitor.check(runner.as_ref());
+        if !reasons.is_empty() {
+            break reasons;
         }
-        // If optimal solution is provided, stop if it is reached
-        if let Some(optimal) = task.stop_cond.optimal_fitness {
-            if optimal == runner.current_fitness() {
-                break;
-            }
+        // Stop as soon as the client is gone, without waiting for the next message
+        if sender.is_closed() {
+            return Vec::new();
         }
         // Every update_rate iterations, send a data update to the client
-        if runner.iterations() % update_rate == 0 {
-            if let Err(_) = send_json(
-                socket,
-                json!({
-                    "messageType": "dataUpdate",
-                    "data": runner.status_json(),
-                }),
-            )
-            .await
-            {
-                return;
-            }
-            let _ = socket.flush().await;
+        if runner.iterations() % update_rate == 0 && send_status(sender, runner.as_ref()).is_err() {
+            return Vec::new();
         }
-    }
+        if checkpoint_rate.is_some_and(|rate| runner.iterations() % rate == 0)
+            && send_checkpoint(sender, task, runner.as_ref(), rng).is_err()
+        {
+            return Vec::new();
+        }
+    };
     // Send a final data update once the simulation is done
-    let _ = send_json(
-        socket,
-        json!({
-            "messageType": "dataUpdate",
-            "data": runner.status_json(),
-        }),
-    )
-    .await;
+    let _ = send_status(sender, runner.as_ref());
+    if checkpoint_rate.is_some() {
+        let _ = send_checkpoint(sender, task, runner.as_ref(), rng);
+    }
+    stopped_by
 }